@@ -15,7 +15,7 @@ use std::{
 
 use library::{
     interface::VirtualImage,
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     space::*,
     utils::{ObjParser, PpmEncoder},
 };
@@ -75,17 +75,19 @@ fn prepare_world(model: Arc<Group>) -> World {
     World {
         objects,
         light_source,
+        fog: None,
+        media: vec![],
     }
 }
 
 fn prepare_camera(horizontal_resolution: u16) -> Camera {
     let mut camera = Camera::new(horizontal_resolution, horizontal_resolution / 2, PI / 3.0);
 
-    camera.transform = Matrix::view_transform(
-        &Tuple::point(50, -50, -20),
-        &Tuple::point(-70, 30, -10),
-        &Tuple::vector(0, 1, 0),
-    );
+    camera.transform = Matrix4::view_transform(
+        &Point3::new(50, -50, -20),
+        &Point3::new(-70, 30, -10),
+        &Vector3::new(0, 1, 0),
+    ).into();
 
     camera
 }
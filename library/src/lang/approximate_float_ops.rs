@@ -1,20 +1,21 @@
+use super::Float;
 use crate::math::EPSILON;
 
-// Approximate f64 operations, very useful to keep the code clean, and to avoid easy ε-related errors.
+// Approximate Float operations, very useful to keep the code clean, and to avoid easy ε-related errors.
 // Naming is quite difficult, in particular, for the `approximate()` method.
 //
-pub trait ApproximateFloat64Ops {
-    fn approximate(self) -> f64;
-    fn approximate_equals(self, rhs: f64) -> bool;
-    fn approximate_greater_or_equal(self, rhs: f64) -> bool;
-    fn approximate_less_or_equal(self, rhs: f64) -> bool;
+pub trait ApproximateFloatOps {
+    fn approximate(self) -> Float;
+    fn approximate_equals(self, rhs: Float) -> bool;
+    fn approximate_greater_or_equal(self, rhs: Float) -> bool;
+    fn approximate_less_or_equal(self, rhs: Float) -> bool;
     fn within_epsilon(self) -> bool;
 }
 
 // Returns 0 if the absolute value is smaller than EPSILON, and the value otherwise.
 // In some contexts, values very close to 0 can cause havoc, for example when running floor().
 //
-impl ApproximateFloat64Ops for f64 {
+impl ApproximateFloatOps for Float {
     fn approximate(self) -> Self {
         if self.abs() < EPSILON {
             0.0
@@ -23,15 +24,15 @@ impl ApproximateFloat64Ops for f64 {
         }
     }
 
-    fn approximate_equals(self, rhs: f64) -> bool {
+    fn approximate_equals(self, rhs: Float) -> bool {
         (self - rhs).abs() < EPSILON
     }
 
-    fn approximate_greater_or_equal(self, rhs: f64) -> bool {
+    fn approximate_greater_or_equal(self, rhs: Float) -> bool {
         self >= rhs - EPSILON
     }
 
-    fn approximate_less_or_equal(self, rhs: f64) -> bool {
+    fn approximate_less_or_equal(self, rhs: Float) -> bool {
         self <= rhs + EPSILON
     }
 
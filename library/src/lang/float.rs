@@ -0,0 +1,15 @@
+// Floating point type used across the renderer; the `f32` feature halves the memory of the geometry
+// (relevant for large meshes) and doubles the SIMD lanes, at the cost of precision.
+//
+// Constructors keep accepting `Into<f64>` values, since integers don't convert to f32, and cast to
+// Float.
+//
+#[cfg(not(feature = "f32"))]
+pub type Float = f64;
+#[cfg(feature = "f32")]
+pub type Float = f32;
+
+#[cfg(feature = "f32")]
+pub use std::f32::consts;
+#[cfg(not(feature = "f32"))]
+pub use std::f64::consts;
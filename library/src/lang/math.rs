@@ -1,5 +1,6 @@
+use super::Float;
 // Makes math formulas look better, since having sqrt() at the right of the expression is confusing.
 //
-pub fn sqrt<T: Into<f64>>(value: T) -> f64 {
-    value.into().sqrt()
+pub fn sqrt<T: Into<f64>>(value: T) -> Float {
+    (value.into() as Float).sqrt()
 }
@@ -1,4 +1,6 @@
-mod approximate_float64_ops;
+mod approximate_float_ops;
+mod float;
 pub mod math;
 
-pub use approximate_float64_ops::ApproximateFloat64Ops;
+pub use approximate_float_ops::ApproximateFloatOps;
+pub use float::{consts, Float};
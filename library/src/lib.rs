@@ -1,5 +1,3 @@
-#![feature(get_mut_unchecked)]
-
 pub mod interface;
 pub mod lang;
 pub mod math;
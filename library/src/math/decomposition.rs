@@ -0,0 +1,45 @@
+use super::{Matrix4, Quaternion, Vector3};
+use crate::lang::Float;
+
+// Affine transformation, split in its translation/rotation/scale components; it's the representation
+// used for keyframe interpolation and scene export.
+//
+// Shearing and projective components are not represented; decomposing a matrix that has them yields
+// an approximation.
+//
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decomposition {
+    pub translation: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+impl Decomposition {
+    // Applies scale, then rotation, then translation; this is the inverse of Matrix4::decompose().
+    //
+    pub fn compose(&self) -> Matrix4 {
+        Matrix4::scaling(self.scale.x, self.scale.y, self.scale.z)
+            .apply_transformation(self.rotation.to_matrix())
+            .translate(self.translation.x, self.translation.y, self.translation.z)
+    }
+
+    // Linear interpolation of translation and scale, and spherical interpolation of the rotation.
+    //
+    pub fn interpolate(&self, rhs: &Self, t: Float) -> Self {
+        Self {
+            translation: self.translation + &((rhs.translation - &self.translation) * t),
+            rotation: self.rotation.slerp(&rhs.rotation, t),
+            scale: self.scale + &((rhs.scale - &self.scale) * t),
+        }
+    }
+}
+
+impl Default for Decomposition {
+    fn default() -> Self {
+        Self {
+            translation: Vector3::new(0, 0, 0),
+            rotation: Quaternion::identity(),
+            scale: Vector3::new(1, 1, 1),
+        }
+    }
+}
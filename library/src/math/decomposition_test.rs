@@ -0,0 +1,53 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Decomposition" {
+        use crate::math::*;
+        use crate::Axis;
+        use crate::lang::consts::PI;
+
+        it "should decompose a matrix, and compose it back" {
+            let matrix = Matrix4::scaling(2.0, 3.0, 0.5)
+                .rotate(Axis::X, PI / 5.0)
+                .rotate(Axis::Y, PI / 3.0)
+                .translate(1, -2, 3);
+
+            let decomposition = matrix.decompose();
+
+            assert_eq!(decomposition.translation, Vector3::new(1, -2, 3));
+            assert_eq!(decomposition.scale, Vector3::new(2, 3, 0.5));
+            assert_eq!(
+                decomposition.rotation,
+                Quaternion::from_axis_angle(&Vector3::new(0, 1, 0), PI / 3.0)
+                    * &Quaternion::from_axis_angle(&Vector3::new(1, 0, 0), PI / 5.0)
+            );
+            assert_eq!(decomposition.compose(), matrix);
+        }
+
+        it "should represent a reflection as negative scale" {
+            let matrix = Matrix4::scaling(-2, 1, 1).rotate(Axis::Z, PI / 4.0);
+
+            let decomposition = matrix.decompose();
+
+            assert_eq!(decomposition.scale, Vector3::new(-2, 1, 1));
+            assert_eq!(decomposition.compose(), matrix);
+        }
+
+        it "should interpolate the components" {
+            let start = Decomposition::default();
+            let end = Decomposition {
+                translation: Vector3::new(2, 0, 0),
+                rotation: Quaternion::from_axis_angle(&Vector3::new(0, 0, 1), PI / 2.0),
+                scale: Vector3::new(3, 1, 1),
+            };
+
+            let expected_decomposition = Decomposition {
+                translation: Vector3::new(1, 0, 0),
+                rotation: Quaternion::from_axis_angle(&Vector3::new(0, 0, 1), PI / 4.0),
+                scale: Vector3::new(2, 1, 1),
+            };
+
+            assert_eq!(start.interpolate(&end, 0.5), expected_decomposition);
+        }
+    }
+}
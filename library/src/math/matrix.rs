@@ -1,11 +1,14 @@
 use super::Tuple;
-use crate::{lang::ApproximateFloat64Ops, Axis};
+use crate::lang::{ApproximateFloatOps, Float};
 
 use std::ops::{Index, IndexMut, Mul};
 
-#[derive(Debug)]
+// Generic matrix, of arbitrary order; it's used for the mathematical APIs (submatrix/cofactor...) that
+// require orders other than 4. Transformations use the (stack-allocated) Matrix4.
+//
+#[derive(Clone, Debug)]
 pub struct Matrix {
-    pub values: Vec<Vec<f64>>,
+    pub values: Vec<Vec<Float>>,
 }
 
 impl Matrix {
@@ -19,7 +22,7 @@ impl Matrix {
     // So, screw rustfmt, and just use `#[rustfmt::skip]`.
     //
     pub fn new<T: Copy + Into<f64>>(source_values: &[T]) -> Self {
-        let order = (source_values.len() as f64).sqrt() as usize;
+        let order = (source_values.len() as Float).sqrt() as usize;
 
         if source_values.len() != order.pow(2) {
             panic!("Number of source values is not a square value");
@@ -31,7 +34,7 @@ impl Matrix {
             values.push(
                 source_row
                     .iter()
-                    .map(|value| (*value).into())
+                    .map(|value| (*value).into() as Float)
                     .collect::<Vec<_>>(),
             );
         }
@@ -39,85 +42,6 @@ impl Matrix {
         Self { values }
     }
 
-    pub fn translation<T: Into<f64>>(x: T, y: T, z: T) -> Self {
-        let (x, y, z) = (x.into(), y.into(), z.into());
-
-        #[rustfmt::skip]
-        let transformation_values = [
-            1.0, 0.0, 0.0, x,
-            0.0, 1.0, 0.0, y,
-            0.0, 0.0, 1.0, z,
-            0.0, 0.0, 0.0, 1.0,
-        ];
-
-        Self::new(&transformation_values)
-    }
-
-    pub fn scaling<T: Into<f64>>(x: T, y: T, z: T) -> Self {
-        let (x, y, z) = (x.into(), y.into(), z.into());
-
-        #[rustfmt::skip]
-        let transformation_values = [
-            x,   0.0, 0.0, 0.0,
-            0.0, y,   0.0, 0.0,
-            0.0, 0.0, z,   0.0,
-            0.0, 0.0, 0.0, 1.0,
-        ];
-
-        Self::new(&transformation_values)
-    }
-
-    // r: radians.
-    //
-    pub fn rotation(axis: Axis, r: f64) -> Self {
-        let (cos_r, sin_r) = (r.cos(), r.sin());
-
-        #[rustfmt::skip]
-        let transformation_values = match axis {
-            Axis::X => [
-                1.0, 0.0,   0.0,    0.0,
-                0.0, cos_r, -sin_r, 0.0,
-                0.0, sin_r, cos_r,  0.0,
-                0.0, 0.0,   0.0,    1.0,
-            ],
-            Axis::Y => [
-                cos_r,  0.0, sin_r,  0.0,
-                0.0,    1.0, 0.0,    0.0,
-                -sin_r, 0.0, cos_r,  0.0,
-                0.0,    0.0, 0.0,    1.0,
-            ],
-            Axis::Z => [
-                cos_r,  -sin_r, 0.0, 0.0,
-                sin_r,  cos_r,  0.0, 0.0,
-                0.0,    0.0,    1.0, 0.0,
-                0.0,    0.0,    0.0, 1.0,
-            ]
-        };
-
-        Self::new(&transformation_values)
-    }
-
-    pub fn shearing<T: Into<f64>>(x_py: T, x_pz: T, y_px: T, y_pz: T, z_px: T, z_py: T) -> Self {
-        let (x_py, x_pz, y_px, y_pz, z_px, z_py) = (
-            x_py.into(),
-            x_pz.into(),
-            y_px.into(),
-            y_pz.into(),
-            z_px.into(),
-            z_py.into(),
-        );
-
-        #[rustfmt::skip]
-        let transformation_values = [
-            1.0,  x_py, x_pz, 0.0,
-            y_px, 1.0,  y_pz, 0.0,
-            z_px, z_py, 1.0,  0.0,
-            0.0,  0.0,  0.0,  1.0,
-        ];
-
-        Self::new(&transformation_values)
-    }
-
     pub fn identity(order: usize) -> Self {
         let mut source_values = vec![0; order.pow(2)];
 
@@ -128,51 +52,6 @@ impl Matrix {
         Self::new(&source_values)
     }
 
-    pub fn view_transform(from: &Tuple, to: &Tuple, up: &Tuple) -> Self {
-        let forward = (*to - from).normalize();
-        let normalized_up = up.normalize();
-        let left = forward.cross_product(normalized_up);
-        let true_up = left.cross_product(forward);
-
-        #[rustfmt::skip]
-        let orientation_values = [
-            left.x,     left.y,     left.z,     0.0,
-            true_up.x,  true_up.y,  true_up.z,  0.0,
-            -forward.x, -forward.y, -forward.z, 0.0,
-            0.0,        0.0,        0.0,        1.0,
-        ];
-
-        Self::new(&orientation_values) * &Matrix::translation(-from.x, -from.y, -from.z)
-    }
-
-    // Builder APIs.
-    //
-    // WATCH OUT! The transformation applied in the intuitive, not mathematical, fashion - the transform
-    // applied is the left multiplication operand.
-    //
-    // The methods could be a Shape trait (e.g. `ShapeBuilder: Shape + Sized`), but they'd be less generic,
-    // e.g. they couldn't be applied to Camera; both designs are valid.
-
-    pub fn apply_transformation(&self, transform: Matrix) -> Self {
-        transform * self
-    }
-
-    pub fn scale<T: Into<f64>>(&self, x: T, y: T, z: T) -> Self {
-        Matrix::scaling(x, y, z) * self
-    }
-
-    pub fn equiscale<T: Into<f64> + Copy>(&self, s: T) -> Self {
-        Matrix::scaling(s, s, s) * self
-    }
-
-    pub fn translate<T: Into<f64>>(&self, x: T, y: T, z: T) -> Self {
-        Matrix::translation(x, y, z) * self
-    }
-
-    pub fn rotate(&self, axis: Axis, r: f64) -> Self {
-        Matrix::rotation(axis, r) * self
-    }
-
     // Mathematical APIs
 
     pub fn transpose(&self) -> Self {
@@ -188,7 +67,7 @@ impl Matrix {
         Self { values: result }
     }
 
-    pub fn determinant(&self) -> f64 {
+    pub fn determinant(&self) -> Float {
         if self.values.len() == 2 {
             self[0][0] * self[1][1] - self[0][1] * self[1][0]
         } else {
@@ -222,28 +101,20 @@ impl Matrix {
         Self { values: result }
     }
 
-    pub fn minor(&self, y: usize, x: usize) -> f64 {
+    pub fn minor(&self, y: usize, x: usize) -> Float {
         self.submatrix(y, x).determinant()
     }
 
-    // Mad lulz here. Note that for portability, the bit shift should change depending on the arch.
-    //
-    pub fn cofactor(&self, y: usize, x: usize) -> f64 {
+    pub fn cofactor(&self, y: usize, x: usize) -> Float {
         let minor = self.minor(y, x);
 
-        // The data type is irrelevant here, as long as it supports bit shifts (float doesn't).
-        // usize is used for convenience on the next operation.
-        //
-        let minor_bits = minor.to_bits();
-
-        // This is (0 for even/1 for odd), shifted to be the leftmost bit, so that it's in the sign position
-        // of f64 values.
+        // This used to flip the sign bit directly, but the bit layout depends on the Float precision.
         //
-        let sign_bits = ((x + y) << 63) as u64;
-
-        // Xor keeps the <destination sign> if the <sign operand> is 0, and changes it, if the <sign operand> is 1.
-        //
-        f64::from_bits(minor_bits ^ sign_bits)
+        if (x + y).is_multiple_of(2) {
+            minor
+        } else {
+            -minor
+        }
     }
 
     pub fn inverse(&self) -> Self {
@@ -270,7 +141,7 @@ impl Matrix {
 }
 
 impl Index<usize> for Matrix {
-    type Output = Vec<f64>;
+    type Output = Vec<Float>;
 
     fn index(&self, y: usize) -> &Self::Output {
         &self.values[y]
@@ -278,7 +149,7 @@ impl Index<usize> for Matrix {
 }
 
 impl IndexMut<usize> for Matrix {
-    fn index_mut(&mut self, y: usize) -> &mut Vec<f64> {
+    fn index_mut(&mut self, y: usize) -> &mut Vec<Float> {
         &mut self.values[y]
     }
 }
@@ -323,10 +194,10 @@ impl Mul<&Matrix> for &Matrix {
                         //
                         (0..order).map(|k| self[y][k] * rhs[k][x]).sum()
                     })
-                    .collect::<Vec<f64>>()
+                    .collect::<Vec<Float>>()
                 //
             })
-            .collect::<Vec<Vec<f64>>>();
+            .collect::<Vec<Vec<Float>>>();
 
         Self::Output { values }
     }
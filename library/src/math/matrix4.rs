@@ -0,0 +1,470 @@
+use super::{Decomposition, Point3, Quaternion, Tuple, Vector3};
+use crate::{
+    lang::Float,
+    lang::{math::sqrt, ApproximateFloatOps},
+    Axis,
+};
+
+use std::ops::{Index, IndexMut, Mul};
+
+// Fixed-size, stack-allocated matrix, used for all the transformations.
+//
+// The generic (Vec-based) Matrix allocates on each operation, which is a significant cost on the
+// ray/shading hot paths; this type has no allocations, and the operations are fully unrolled loops over
+// fixed-size arrays, which the compiler can autovectorize.
+//
+// There's no explicit SIMD, since `std::simd` is not available on the stable toolchain.
+//
+#[derive(Clone, Copy, Debug)]
+pub struct Matrix4 {
+    pub values: [[Float; 4]; 4],
+}
+
+impl Matrix4 {
+    // See Matrix::new() for the rationale of the interface.
+    //
+    pub fn new<T: Copy + Into<f64>>(source_values: &[T]) -> Self {
+        if source_values.len() != 16 {
+            panic!("Number of source values must be 16");
+        }
+
+        let mut values = [[0.0; 4]; 4];
+
+        for (row, source_row) in values.iter_mut().zip(source_values.chunks_exact(4)) {
+            for (value, source_value) in row.iter_mut().zip(source_row) {
+                *value = (*source_value).into() as Float;
+            }
+        }
+
+        Self { values }
+    }
+
+    pub fn identity() -> Self {
+        #[rustfmt::skip]
+        let values = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+
+        Self { values }
+    }
+
+    pub fn translation<T: Into<f64>>(x: T, y: T, z: T) -> Self {
+        let (x, y, z) = (x.into() as Float, y.into() as Float, z.into() as Float);
+
+        #[rustfmt::skip]
+        let values = [
+            [1.0, 0.0, 0.0, x  ],
+            [0.0, 1.0, 0.0, y  ],
+            [0.0, 0.0, 1.0, z  ],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+
+        Self { values }
+    }
+
+    pub fn scaling<T: Into<f64>>(x: T, y: T, z: T) -> Self {
+        let (x, y, z) = (x.into() as Float, y.into() as Float, z.into() as Float);
+
+        #[rustfmt::skip]
+        let values = [
+            [x,   0.0, 0.0, 0.0],
+            [0.0, y,   0.0, 0.0],
+            [0.0, 0.0, z,   0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+
+        Self { values }
+    }
+
+    // r: radians.
+    //
+    pub fn rotation<T: Into<f64>>(axis: Axis, r: T) -> Self {
+        let r = r.into() as Float;
+        let (cos_r, sin_r) = (r.cos(), r.sin());
+
+        #[rustfmt::skip]
+        let values = match axis {
+            Axis::X => [
+                [1.0, 0.0,   0.0,    0.0],
+                [0.0, cos_r, -sin_r, 0.0],
+                [0.0, sin_r, cos_r,  0.0],
+                [0.0, 0.0,   0.0,    1.0],
+            ],
+            Axis::Y => [
+                [cos_r,  0.0, sin_r, 0.0],
+                [0.0,    1.0, 0.0,   0.0],
+                [-sin_r, 0.0, cos_r, 0.0],
+                [0.0,    0.0, 0.0,   1.0],
+            ],
+            Axis::Z => [
+                [cos_r, -sin_r, 0.0, 0.0],
+                [sin_r, cos_r,  0.0, 0.0],
+                [0.0,   0.0,    1.0, 0.0],
+                [0.0,   0.0,    0.0, 1.0],
+            ]
+        };
+
+        Self { values }
+    }
+
+    // Rotation around an arbitrary axis, passing through the origin; the axis doesn't need to be normalized.
+    //
+    pub fn rotation_around<T: Into<f64>>(axis: &Vector3, r: T) -> Self {
+        Quaternion::from_axis_angle(axis, r.into() as Float).to_matrix()
+    }
+
+    pub fn shearing<T: Into<f64>>(x_py: T, x_pz: T, y_px: T, y_pz: T, z_px: T, z_py: T) -> Self {
+        let (x_py, x_pz, y_px, y_pz, z_px, z_py) = (
+            x_py.into() as Float,
+            x_pz.into() as Float,
+            y_px.into() as Float,
+            y_pz.into() as Float,
+            z_px.into() as Float,
+            z_py.into() as Float,
+        );
+
+        #[rustfmt::skip]
+        let values = [
+            [1.0,  x_py, x_pz, 0.0],
+            [y_px, 1.0,  y_pz, 0.0],
+            [z_px, z_py, 1.0,  0.0],
+            [0.0,  0.0,  0.0,  1.0],
+        ];
+
+        Self { values }
+    }
+
+    pub fn view_transform(from: &Point3, to: &Point3, up: &Vector3) -> Self {
+        let forward = (*to - from).normalize();
+        let normalized_up = up.normalize();
+        let left = forward.cross_product(&normalized_up);
+        let true_up = left.cross_product(&forward);
+
+        #[rustfmt::skip]
+        let values = [
+            [left.x,     left.y,     left.z,     0.0],
+            [true_up.x,  true_up.y,  true_up.z,  0.0],
+            [-forward.x, -forward.y, -forward.z, 0.0],
+            [0.0,        0.0,        0.0,        1.0],
+        ];
+
+        Self { values } * &Matrix4::translation(-from.x, -from.y, -from.z)
+    }
+
+    // Object counterpart of view_transform(): places an object at `from`, with its -z axis pointing to
+    // `to`, and its y axis (approximately) oriented as `up`.
+    //
+    // Differently from view_transform(), `left` is normalized, so that the result is a pure rotation +
+    // translation even when `up` is not perpendicular to the viewing direction.
+    //
+    pub fn look_at(from: &Point3, to: &Point3, up: &Vector3) -> Self {
+        let forward = (*to - from).normalize();
+        let normalized_up = up.normalize();
+        let left = forward.cross_product(&normalized_up).normalize();
+        let true_up = left.cross_product(&forward);
+
+        // The orientation is orthonormal, so the inverse is the transpose.
+        //
+        #[rustfmt::skip]
+        let values = [
+            [left.x, true_up.x, -forward.x, from.x],
+            [left.y, true_up.y, -forward.y, from.y],
+            [left.z, true_up.z, -forward.z, from.z],
+            [0.0,    0.0,       0.0,        1.0   ],
+        ];
+
+        Self { values }
+    }
+
+    // Builder APIs.
+    //
+    // WATCH OUT! The transformation applied in the intuitive, not mathematical, fashion - the transform
+    // applied is the left multiplication operand.
+    //
+    // The methods could be a Shape trait (e.g. `ShapeBuilder: Shape + Sized`), but they'd be less generic,
+    // e.g. they couldn't be applied to Camera; both designs are valid.
+
+    pub fn apply_transformation(&self, transform: Matrix4) -> Self {
+        transform * self
+    }
+
+    pub fn scale<T: Into<f64>>(&self, x: T, y: T, z: T) -> Self {
+        Matrix4::scaling(x, y, z) * self
+    }
+
+    pub fn equiscale<T: Into<f64> + Copy>(&self, s: T) -> Self {
+        Matrix4::scaling(s, s, s) * self
+    }
+
+    pub fn translate<T: Into<f64>>(&self, x: T, y: T, z: T) -> Self {
+        Matrix4::translation(x, y, z) * self
+    }
+
+    pub fn rotate<T: Into<f64>>(&self, axis: Axis, r: T) -> Self {
+        Matrix4::rotation(axis, r) * self
+    }
+
+    pub fn rotate_around<T: Into<f64>>(&self, axis: &Vector3, r: T) -> Self {
+        Matrix4::rotation_around(axis, r) * self
+    }
+
+    // Mathematical APIs
+
+    pub fn transpose(&self) -> Self {
+        let mut values = [[0.0; 4]; 4];
+
+        for (y, row) in values.iter_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                *value = self.values[x][y];
+            }
+        }
+
+        Self { values }
+    }
+
+    pub fn determinant(&self) -> Float {
+        let (s, c) = self.subfactors();
+
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    // Closed form (Laplace expansion via 2x2 subdeterminants of the top and bottom row pairs); the 2x2
+    // subdeterminants are shared between the determinant and the adjugate.
+    //
+    pub fn inverse(&self) -> Self {
+        let (s, c) = self.subfactors();
+
+        let determinant =
+            s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0];
+
+        if determinant == 0.0 {
+            panic!("The matrix has zero determinant!")
+        }
+
+        let m = &self.values;
+        let inv_det = 1.0 / determinant;
+
+        #[rustfmt::skip]
+        let values = [
+            [
+                ( m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3]) * inv_det,
+                (-m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3]) * inv_det,
+                ( m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3]) * inv_det,
+                (-m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3]) * inv_det,
+            ],
+            [
+                (-m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1]) * inv_det,
+                ( m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1]) * inv_det,
+                (-m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1]) * inv_det,
+                ( m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1]) * inv_det,
+            ],
+            [
+                ( m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0]) * inv_det,
+                (-m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0]) * inv_det,
+                ( m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0]) * inv_det,
+                (-m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0]) * inv_det,
+            ],
+            [
+                (-m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0]) * inv_det,
+                ( m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0]) * inv_det,
+                (-m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0]) * inv_det,
+                ( m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0]) * inv_det,
+            ],
+        ];
+
+        Self { values }
+    }
+
+    // Splits an affine matrix into translation, rotation and scale; see Decomposition.
+    //
+    // A negative determinant means that there's a reflection, which is represented as a negative x scale.
+    //
+    pub fn decompose(&self) -> Decomposition {
+        let m = &self.values;
+
+        let translation = Vector3::new(m[0][3], m[1][3], m[2][3]);
+
+        let column_length = |x: usize| sqrt(m[0][x].powi(2) + m[1][x].powi(2) + m[2][x].powi(2));
+
+        let mut scale = Vector3::new(column_length(0), column_length(1), column_length(2));
+
+        if self.determinant() < 0.0 {
+            scale.x = -scale.x;
+        }
+
+        let mut rotation_matrix = Matrix4::identity();
+
+        for y in 0..3 {
+            for x in 0..3 {
+                rotation_matrix[y][x] = m[y][x] / scale[x];
+            }
+        }
+
+        Decomposition {
+            translation,
+            rotation: Quaternion::from_rotation_matrix(&rotation_matrix),
+            scale,
+        }
+    }
+
+    // 2x2 subdeterminants of the top rows (s) and of the bottom rows (c).
+    //
+    fn subfactors(&self) -> ([Float; 6], [Float; 6]) {
+        let m = &self.values;
+
+        let s = [
+            m[0][0] * m[1][1] - m[1][0] * m[0][1],
+            m[0][0] * m[1][2] - m[1][0] * m[0][2],
+            m[0][0] * m[1][3] - m[1][0] * m[0][3],
+            m[0][1] * m[1][2] - m[1][1] * m[0][2],
+            m[0][1] * m[1][3] - m[1][1] * m[0][3],
+            m[0][2] * m[1][3] - m[1][2] * m[0][3],
+        ];
+
+        let c = [
+            m[2][0] * m[3][1] - m[3][0] * m[2][1],
+            m[2][0] * m[3][2] - m[3][0] * m[2][2],
+            m[2][0] * m[3][3] - m[3][0] * m[2][3],
+            m[2][1] * m[3][2] - m[3][1] * m[2][2],
+            m[2][1] * m[3][3] - m[3][1] * m[2][3],
+            m[2][2] * m[3][3] - m[3][2] * m[2][3],
+        ];
+
+        (s, c)
+    }
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Index<usize> for Matrix4 {
+    type Output = [Float; 4];
+
+    fn index(&self, y: usize) -> &Self::Output {
+        &self.values[y]
+    }
+}
+
+impl IndexMut<usize> for Matrix4 {
+    fn index_mut(&mut self, y: usize) -> &mut [Float; 4] {
+        &mut self.values[y]
+    }
+}
+
+// Due to the epsilon handling, we can't use a direct/bitwise comparison.
+//
+impl PartialEq for Matrix4 {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.values
+            .iter()
+            .flatten()
+            .zip(rhs.values.iter().flatten())
+            .all(|(value, rhs_value)| value.approximate_equals(*rhs_value))
+    }
+}
+
+// Since Matrix4 is Copy, the owned versions of the operations are the canonical ones.
+
+impl Mul<&Matrix4> for &Matrix4 {
+    type Output = Matrix4;
+
+    fn mul(self, rhs: &Matrix4) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Mul<&Matrix4> for Matrix4 {
+    type Output = Self;
+
+    fn mul(self, rhs: &Matrix4) -> Self::Output {
+        let mut values = [[0.0; 4]; 4];
+
+        for (y, row) in values.iter_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                *value = self[y][0] * rhs[0][x]
+                    + self[y][1] * rhs[1][x]
+                    + self[y][2] * rhs[2][x]
+                    + self[y][3] * rhs[3][x];
+            }
+        }
+
+        Self::Output { values }
+    }
+}
+
+impl Mul<&Tuple> for &Matrix4 {
+    type Output = Tuple;
+
+    fn mul(self, rhs: &Tuple) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Mul<&Tuple> for Matrix4 {
+    type Output = Tuple;
+
+    fn mul(self, rhs: &Tuple) -> Self::Output {
+        let row_product =
+            |row: &[Float; 4]| row[0] * rhs.x + row[1] * rhs.y + row[2] * rhs.z + row[3] * rhs.w;
+
+        Tuple {
+            x: row_product(&self[0]),
+            y: row_product(&self[1]),
+            z: row_product(&self[2]),
+            w: row_product(&self[3]),
+        }
+    }
+}
+
+impl Mul<&Point3> for &Matrix4 {
+    type Output = Point3;
+
+    fn mul(self, rhs: &Point3) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Mul<&Point3> for Matrix4 {
+    type Output = Point3;
+
+    fn mul(self, rhs: &Point3) -> Self::Output {
+        let row_product =
+            |row: &[Float; 4]| row[0] * rhs.x + row[1] * rhs.y + row[2] * rhs.z + row[3];
+
+        Point3 {
+            x: row_product(&self[0]),
+            y: row_product(&self[1]),
+            z: row_product(&self[2]),
+        }
+    }
+}
+
+// Vectors are not affected by the translation.
+//
+impl Mul<&Vector3> for &Matrix4 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: &Vector3) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Mul<&Vector3> for Matrix4 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: &Vector3) -> Self::Output {
+        let row_product = |row: &[Float; 4]| row[0] * rhs.x + row[1] * rhs.y + row[2] * rhs.z;
+
+        Vector3 {
+            x: row_product(&self[0]),
+            y: row_product(&self[1]),
+            z: row_product(&self[2]),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Matrix4" {
+        use crate::math::*;
+        use crate::Axis;
+        use crate::lang::consts::PI;
+
+        it "should construct a matrix from values, and access them" {
+            let matrix = Matrix4::new(&[
+                1.0, 2.0, 3.0, 4.0,
+                5.5, 6.5, 7.5, 8.5,
+                9.0, 10.0, 11.0, 12.0,
+                13.5, 14.5, 15.5, 16.5
+            ]);
+
+            assert_float_absolute_eq!(matrix[0][3], 4.0);
+            assert_float_absolute_eq!(matrix[1][2], 7.5);
+            assert_float_absolute_eq!(matrix[3][0], 13.5);
+        }
+
+        it "should multiply like the generic matrix" {
+            let values1 = [
+                1, 2, 3, 4,
+                5, 6, 7, 8,
+                9, 8, 7, 6,
+                5, 4, 3, 2,
+            ];
+            let values2 = [
+                -2, 1, 2,  3,
+                 3, 2, 1, -1,
+                 4, 3, 6,  5,
+                 1, 2, 7,  8,
+            ];
+            let tuple = Tuple::new(1, 2, 3, 1);
+
+            let product = Matrix4::new(&values1) * &Matrix4::new(&values2);
+            let expected_product = Matrix::new(&values1) * &Matrix::new(&values2);
+
+            assert_eq!(product.values.concat(), expected_product.values.concat());
+            assert_eq!(Matrix4::new(&values1) * &tuple, Matrix::new(&values1) * &tuple);
+        }
+
+        it "should compute the determinant and inverse like the generic matrix" {
+            let values = [
+                -5,  2,  6, -8,
+                 1, -5,  1,  8,
+                 7,  7, -6, -7,
+                 1, -3,  7,  4,
+            ];
+
+            let matrix = Matrix4::new(&values);
+            let generic_matrix = Matrix::new(&values);
+
+            assert_float_absolute_eq!(matrix.determinant(), generic_matrix.determinant());
+
+            let inverse = matrix.inverse();
+
+            for (row, generic_row) in inverse.values.iter().zip(generic_matrix.inverse().values) {
+                for (value, generic_value) in row.iter().zip(generic_row) {
+                    assert_float_absolute_eq!(*value, generic_value);
+                }
+            }
+
+            assert_eq!(inverse * &matrix, Matrix4::identity());
+        }
+
+        #[should_panic]
+        it "should panic if the matrix is not invertible" {
+            let matrix = Matrix4::new(&[
+                -4,  2, -2, -3,
+                 9,  6,  2,  6,
+                 0, -5,  1, -5,
+                 0,  0,  0,  0,
+            ]);
+
+            matrix.inverse();
+        }
+
+        it "should rotate around an arbitrary axis" {
+            let axis = Vector3::new(1, 1, 1);
+            let matrix = Matrix4::rotation_around(&axis, 2.0 * PI / 3.0);
+
+            assert_eq!(matrix * &Point3::new(1, 0, 0), Point3::new(0, 1, 0));
+            assert_eq!(Matrix4::rotation_around(&Vector3::new(0, 0, 3), 0.7), Matrix4::rotation(Axis::Z, 0.7));
+        }
+
+        it "should be the inverse of the view transform, for a perpendicular up vector" {
+            let from = Point3::new(1, 3, 2);
+            let to = Point3::new(4, 3, 8);
+            let up = Vector3::new(0, 1, 0);
+
+            assert_eq!(Matrix4::look_at(&from, &to, &up), Matrix4::view_transform(&from, &to, &up).inverse());
+        }
+
+        it "should place an object looking at a point" {
+            let from = Point3::new(1, 3, 2);
+            let to = Point3::new(4, -2, 8);
+            let up = Vector3::new(1, 1, 0);
+
+            let matrix = Matrix4::look_at(&from, &to, &up);
+            let expected_forward = (to - &from).normalize();
+
+            assert_eq!(matrix * &Point3::new(0, 0, 0), from);
+            assert_eq!(matrix * &Vector3::new(0, 0, -1), expected_forward);
+            assert_eq!(matrix.decompose().scale, Vector3::new(1, 1, 1));
+        }
+
+        context "view tranformation" {
+            it "should return the identity matrix when looking at positive z" {
+                let from = Point3::new(0, 0, 0);
+                let to = Point3::new(0, 0, 1);
+                let up = Vector3::new(0, 1, 0);
+
+                let expected_matrix = Matrix4::scaling(-1, 1, -1);
+
+                assert_eq!(Matrix4::view_transform(&from, &to, &up), expected_matrix);
+            }
+
+            it "should mirror x and z when looking at negative z" {
+                let from = Point3::new(0, 0, 8);
+                let to = Point3::new(0, 0, 0);
+                let up = Vector3::new(0, 1, 0);
+
+                let expected_matrix = Matrix4::translation(0, 0, -8);
+
+                assert_eq!(Matrix4::view_transform(&from, &to, &up), expected_matrix);
+            }
+
+            it "applies an arbitrary transformation" {
+                let from = Point3::new(1, 3, 2);
+                let to = Point3::new(4, -2, 8);
+                let up = Vector3::new(1, 1, 0);
+
+                let expected_matrix = Matrix4::new(&[
+                    -0.50709, 0.50709,  0.67612, -2.36643,
+                     0.76772, 0.60609,  0.12122, -2.82843,
+                    -0.35857, 0.59761, -0.71714,  0.00000,
+                     0.00000, 0.00000,  0.00000,  1.00000,
+                ]);
+
+                assert_eq!(Matrix4::view_transform(&from, &to, &up), expected_matrix);
+            }
+        } // context "view tranformation"
+    }
+}
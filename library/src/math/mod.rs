@@ -1,13 +1,60 @@
+mod decomposition;
 mod matrix;
+mod matrix4;
+pub mod noise;
+mod normal3;
+mod point3;
+mod quaternion;
+mod transform;
 mod tuple;
+mod vector3;
 
+pub use decomposition::Decomposition;
 pub use matrix::Matrix;
+pub use matrix4::Matrix4;
+pub use normal3::Normal3;
+pub use point3::Point3;
+pub use quaternion::Quaternion;
+pub use transform::Transform;
 pub use tuple::Tuple;
+pub use vector3::Vector3;
 
-pub const EPSILON: f64 = 1e-4;
+use crate::lang::Float;
+
+// Scaled to the precision: f32 has ~7 significant digits, so with scene-sized coordinates, 1e-4 is
+// below the representation error.
+//
+#[cfg(not(feature = "f32"))]
+pub const EPSILON: Float = 1e-4;
+#[cfg(feature = "f32")]
+pub const EPSILON: Float = 1e-3;
+
+#[cfg(test)]
+mod decomposition_test;
 
 #[cfg(test)]
 mod matrix_test;
 
+#[cfg(test)]
+mod matrix4_test;
+
+#[cfg(test)]
+mod noise_test;
+
+#[cfg(test)]
+mod normal3_test;
+
+#[cfg(test)]
+mod point3_test;
+
+#[cfg(test)]
+mod quaternion_test;
+
+#[cfg(test)]
+mod transform_test;
+
 #[cfg(test)]
 mod tuple_test;
+
+#[cfg(test)]
+mod vector3_test;
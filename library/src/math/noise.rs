@@ -0,0 +1,313 @@
+use super::Point3;
+use crate::lang::Float;
+
+// Procedural noise functions, used by the noise patterns.
+//
+// All the functions are deterministic (they're based on Ken Perlin's reference permutation), so that
+// renders are reproducible, and continuous, with the exception of the Worley cell borders derivative.
+//
+// Perlin, simplex and fBm return values in [-1, 1]; turbulence and Worley in [0, 1]. The ranges are
+// approximate, so the callers needing strict bounds should clamp.
+//
+
+const PERMUTATION: [u8; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
+    142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
+    203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122, 60, 211, 133, 230,
+    220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54, 65, 25, 63, 161, 1, 216, 80, 73, 209, 76,
+    132, 187, 208, 89, 18, 169, 200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173,
+    186, 3, 64, 52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212, 207, 206,
+    59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213, 119, 248, 152, 2, 44, 154, 163,
+    70, 221, 153, 101, 155, 167, 43, 172, 9, 129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232,
+    178, 185, 112, 104, 218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162,
+    241, 81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157, 184, 84, 204,
+    176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93, 222, 114, 67, 29, 24, 72, 243, 141,
+    128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+// Gradients of the simplex noise: the midpoints of the edges of a cube.
+//
+const SIMPLEX_GRADIENTS: [[Float; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+// Masking is equivalent to the doubled table of the reference implementation, and additionally supports
+// negative lattice coordinates.
+//
+fn permutation(i: i32) -> i32 {
+    PERMUTATION[(i & 255) as usize] as i32
+}
+
+fn hash(x: i32, y: i32, z: i32) -> i32 {
+    permutation(x + permutation(y + permutation(z)))
+}
+
+// Splits a coordinate into the lattice cell and the position inside it.
+//
+fn split(value: Float) -> (i32, Float) {
+    let floor = value.floor();
+
+    (floor as i32, value - floor)
+}
+
+fn fade(t: Float) -> Float {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: Float, a: Float, b: Float) -> Float {
+    a + t * (b - a)
+}
+
+// Dot product of the distance vector with one of 12 gradients (the low 4 bits select it).
+//
+fn gradient(hash: i32, x: Float, y: Float, z: Float) -> Float {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+// Improved Perlin noise (2002). It's zero on the integer lattice.
+//
+pub fn perlin(point: &Point3) -> Float {
+    let (xi, x) = split(point.x);
+    let (yi, y) = split(point.y);
+    let (zi, z) = split(point.z);
+
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+
+    let a = permutation(xi) + yi;
+    let aa = permutation(a) + zi;
+    let ab = permutation(a + 1) + zi;
+    let b = permutation(xi + 1) + yi;
+    let ba = permutation(b) + zi;
+    let bb = permutation(b + 1) + zi;
+
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(permutation(aa), x, y, z),
+                gradient(permutation(ba), x - 1.0, y, z),
+            ),
+            lerp(
+                u,
+                gradient(permutation(ab), x, y - 1.0, z),
+                gradient(permutation(bb), x - 1.0, y - 1.0, z),
+            ),
+        ),
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(permutation(aa + 1), x, y, z - 1.0),
+                gradient(permutation(ba + 1), x - 1.0, y, z - 1.0),
+            ),
+            lerp(
+                u,
+                gradient(permutation(ab + 1), x, y - 1.0, z - 1.0),
+                gradient(permutation(bb + 1), x - 1.0, y - 1.0, z - 1.0),
+            ),
+        ),
+    )
+}
+
+// Simplex noise (Perlin 2001, as described by Stefan Gustavson). Compared to Perlin noise, it's cheaper
+// (4 corners instead of 8), and has no visible axis-aligned artifacts.
+//
+pub fn simplex(point: &Point3) -> Float {
+    const SKEW: Float = 1.0 / 3.0;
+    const UNSKEW: Float = 1.0 / 6.0;
+
+    // Cell of the skewed lattice, and distances from its origin (in unskewed space).
+    //
+    let skew = (point.x + point.y + point.z) * SKEW;
+    let (i, j, k) = (
+        (point.x + skew).floor() as i32,
+        (point.y + skew).floor() as i32,
+        (point.z + skew).floor() as i32,
+    );
+    let unskew = (i + j + k) as Float * UNSKEW;
+    let x0 = point.x - (i as Float - unskew);
+    let y0 = point.y - (j as Float - unskew);
+    let z0 = point.z - (k as Float - unskew);
+
+    // Offsets of the second and third corners of the simplex (tetrahedron); they depend on the order
+    // of the coordinates.
+    //
+    let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+        if y0 >= z0 {
+            ((1, 0, 0), (1, 1, 0))
+        } else if x0 >= z0 {
+            ((1, 0, 0), (1, 0, 1))
+        } else {
+            ((0, 0, 1), (1, 0, 1))
+        }
+    } else if y0 < z0 {
+        ((0, 0, 1), (0, 1, 1))
+    } else if x0 < z0 {
+        ((0, 1, 0), (0, 1, 1))
+    } else {
+        ((0, 1, 0), (1, 1, 0))
+    };
+
+    let corners = [
+        (0, 0, 0, x0, y0, z0),
+        (
+            i1,
+            j1,
+            k1,
+            x0 - i1 as Float + UNSKEW,
+            y0 - j1 as Float + UNSKEW,
+            z0 - k1 as Float + UNSKEW,
+        ),
+        (
+            i2,
+            j2,
+            k2,
+            x0 - i2 as Float + 2.0 * UNSKEW,
+            y0 - j2 as Float + 2.0 * UNSKEW,
+            z0 - k2 as Float + 2.0 * UNSKEW,
+        ),
+        (
+            1,
+            1,
+            1,
+            x0 - 1.0 + 3.0 * UNSKEW,
+            y0 - 1.0 + 3.0 * UNSKEW,
+            z0 - 1.0 + 3.0 * UNSKEW,
+        ),
+    ];
+
+    let sum: Float = corners
+        .iter()
+        .map(|&(di, dj, dk, x, y, z)| {
+            let t = 0.6 - x * x - y * y - z * z;
+
+            if t < 0.0 {
+                0.0
+            } else {
+                let [gx, gy, gz] = SIMPLEX_GRADIENTS[(hash(i + di, j + dj, k + dk) % 12) as usize];
+
+                t.powi(4) * (gx * x + gy * y + gz * z)
+            }
+        })
+        .sum();
+
+    // Scales the result to [-1, 1].
+    //
+    32.0 * sum
+}
+
+// Fractal Brownian motion: sum of Perlin noise octaves, each with the frequency multiplied by the
+// lacunarity, and the amplitude by the gain. The sum is normalized by the total amplitude.
+//
+pub fn fbm(point: &Point3, octaves: u32, lacunarity: Float, gain: Float) -> Float {
+    fractal(point, octaves, lacunarity, gain, perlin)
+}
+
+// Like fBm, but summing the absolute values, which creates sharp creases (e.g. flames, marble veins).
+//
+pub fn turbulence(point: &Point3, octaves: u32, lacunarity: Float, gain: Float) -> Float {
+    fractal(point, octaves, lacunarity, gain, |point| {
+        perlin(point).abs()
+    })
+}
+
+fn fractal(
+    point: &Point3,
+    octaves: u32,
+    lacunarity: Float,
+    gain: Float,
+    noise: impl Fn(&Point3) -> Float,
+) -> Float {
+    let mut sum = 0.0;
+    let mut total_amplitude = 0.0;
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+
+    for _ in 0..octaves {
+        let scaled_point = Point3 {
+            x: point.x * frequency,
+            y: point.y * frequency,
+            z: point.z * frequency,
+        };
+
+        sum += amplitude * noise(&scaled_point);
+        total_amplitude += amplitude;
+
+        frequency *= lacunarity;
+        amplitude *= gain;
+    }
+
+    if total_amplitude > 0.0 {
+        sum / total_amplitude
+    } else {
+        0.0
+    }
+}
+
+// Worley (cellular) noise: distance from the closest feature point, where each lattice cell contains
+// one feature point, at a pseudorandom position.
+//
+// The feature points of the neighboring cells are all checked; the distance is capped at 1, which is
+// reached only in rare configurations.
+//
+pub fn worley(point: &Point3) -> Float {
+    let (xi, _) = split(point.x);
+    let (yi, _) = split(point.y);
+    let (zi, _) = split(point.z);
+
+    let mut min_distance_squared = Float::MAX;
+
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            for dz in -1..=1 {
+                let feature_point = worley_feature_point(xi + dx, yi + dy, zi + dz);
+                let distance_squared = (feature_point - point).magnitude().powi(2);
+
+                min_distance_squared = min_distance_squared.min(distance_squared);
+            }
+        }
+    }
+
+    min_distance_squared.sqrt().min(1.0)
+}
+
+// Position of the feature point of a cell; public, so that the callers (and tests) can locate the
+// cells centers.
+//
+pub fn worley_feature_point(x: i32, y: i32, z: i32) -> Point3 {
+    let cell_hash = hash(x, y, z);
+
+    // Three different hashes are derived from the cell one, one per axis.
+    //
+    let offset = |axis: i32| (permutation(cell_hash + axis * 85) as Float + 0.5) / 256.0;
+
+    Point3 {
+        x: x as Float + offset(0),
+        y: y as Float + offset(1),
+        z: z as Float + offset(2),
+    }
+}
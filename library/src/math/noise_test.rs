@@ -0,0 +1,82 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "noise" {
+        use crate::lang::Float;
+        use crate::math::*;
+        use crate::math::noise::*;
+
+        before {
+            #[allow(unused_variables)]
+            let sample_points = (0..1000).map(|i| {
+                let i = i as Float;
+                Point3::new(i * 0.137 - 50.0, i * 0.291 - 70.0, i * 0.053 + 10.0)
+            }).collect::<Vec<_>>();
+        }
+
+        context "Perlin" {
+            it "should be zero on the lattice" {
+                assert_eq!(perlin(&Point3::new(0, 0, 0)), 0.0);
+                assert_eq!(perlin(&Point3::new(3, -7, 12)), 0.0);
+            }
+
+            it "should be deterministic, and vary between lattice points" {
+                let point = Point3::new(1.3, 2.7, -0.4);
+
+                assert_eq!(perlin(&point), perlin(&point));
+                assert_ne!(perlin(&point), 0.0);
+            }
+
+            it "should be in [-1, 1]" {
+                assert!(sample_points.iter().all(|point| perlin(point).abs() <= 1.0));
+            }
+
+            it "should be continuous" {
+                let point = Point3::new(1.999999, 0.5, 0.5);
+                let next_point = Point3::new(2.000001, 0.5, 0.5);
+
+                assert!((perlin(&point) - perlin(&next_point)).abs() < 1e-3);
+            }
+        }
+
+        it "Simplex should be in [-1, 1], and not constant" {
+            assert!(sample_points.iter().all(|point| simplex(point).abs() <= 1.0));
+            assert!(sample_points.iter().any(|point| simplex(point).abs() > 0.1));
+        }
+
+        context "fractal" {
+            it "fBm with a single octave should be Perlin noise" {
+                let point = Point3::new(1.3, 2.7, -0.4);
+
+                assert_eq!(fbm(&point, 1, 2.0, 0.5), perlin(&point));
+            }
+
+            it "fBm should be in [-1, 1]" {
+                assert!(sample_points.iter().all(|point| fbm(point, 5, 2.0, 0.5).abs() <= 1.0));
+            }
+
+            it "turbulence should be in [0, 1]" {
+                assert!(sample_points.iter().all(|point| (0.0..=1.0).contains(&turbulence(point, 5, 2.0, 0.5))));
+            }
+        }
+
+        context "Worley" {
+            it "should be zero on a feature point" {
+                let feature_point = worley_feature_point(2, -3, 5);
+
+                assert_eq!(worley(&feature_point), 0.0);
+            }
+
+            it "should be the distance from the closest feature point" {
+                let feature_point = worley_feature_point(2, -3, 5);
+                let point = feature_point + &Vector3::new(0.01, 0, 0);
+
+                assert_float_absolute_eq!(worley(&point), 0.01, 1e-4);
+            }
+
+            it "should be in [0, 1]" {
+                assert!(sample_points.iter().all(|point| (0.0..=1.0).contains(&worley(point))));
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::{Mul, Neg};
+
+use super::{Matrix4, Tuple, Vector3};
+use crate::lang::{ApproximateFloatOps, Float};
+
+// Surface normal. It's a separate type from Vector3, because normals transform differently (via the
+// inverse-transpose of the transformation); for this reason, there's no Matrix4 * Normal3 operation,
+// and normals are transformed only via Transform, which knows its inverse-transpose.
+//
+// Scaling a normal yields a Vector3 (typically, an offset from a surface point).
+//
+#[derive(Clone, Copy, Debug)]
+pub struct Normal3 {
+    pub x: Float,
+    pub y: Float,
+    pub z: Float,
+}
+
+impl Normal3 {
+    pub fn new<T: Into<f64>, U: Into<f64>, V: Into<f64>>(x: T, y: U, z: V) -> Self {
+        Self {
+            x: x.into() as Float,
+            y: y.into() as Float,
+            z: z.into() as Float,
+        }
+    }
+
+    pub fn normalize(&self) -> Self {
+        let vector = Vector3::from(*self).normalize();
+
+        Self::new(vector.x, vector.y, vector.z)
+    }
+
+    pub fn dot_product(&self, rhs: &Vector3) -> Float {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    // Applies the given inverse-transpose matrix; the result is normalized.
+    //
+    pub(crate) fn transform(&self, inverse_transpose: &Matrix4) -> Self {
+        let m = &inverse_transpose.values;
+
+        // Only the upper 3x3 is relevant; the translation part (which, in the inverse-transpose, is in
+        // the bottom row) must be ignored.
+        //
+        Self {
+            x: m[0][0] * self.x + m[0][1] * self.y + m[0][2] * self.z,
+            y: m[1][0] * self.x + m[1][1] * self.y + m[1][2] * self.z,
+            z: m[2][0] * self.x + m[2][1] * self.y + m[2][2] * self.z,
+        }
+        .normalize()
+    }
+}
+
+impl PartialEq for Normal3 {
+    // Values are considered as equal if within ε.
+    //
+    fn eq(&self, rhs: &Self) -> bool {
+        self.x.approximate_equals(rhs.x)
+            && self.y.approximate_equals(rhs.y)
+            && self.z.approximate_equals(rhs.z)
+    }
+}
+
+impl Neg for Normal3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl Mul<Float> for Normal3 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: Float) -> Self::Output {
+        Vector3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl From<Normal3> for Vector3 {
+    fn from(normal: Normal3) -> Self {
+        Vector3::new(normal.x, normal.y, normal.z)
+    }
+}
+
+impl From<Normal3> for Tuple {
+    fn from(normal: Normal3) -> Self {
+        Tuple::vector(normal.x, normal.y, normal.z)
+    }
+}
+
+// Vectors (e.g. the result of a cross product) can be used as normals; the opposite conversion is
+// explicit, via From.
+//
+impl From<Vector3> for Normal3 {
+    fn from(vector: Vector3) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Tuple> for Normal3 {
+    fn from(tuple: Tuple) -> Self {
+        Vector3::from(tuple).into()
+    }
+}
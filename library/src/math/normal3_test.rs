@@ -0,0 +1,22 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Normal3" {
+        use crate::math::*;
+
+        it "should be transformed via the inverse-transpose, ignoring the translation" {
+            let transform = Transform::from(Matrix4::scaling(1, 2, 1).translate(3, 4, 5));
+            let normal = Normal3::new(1, 1, 0).normalize();
+
+            // The surface is stretched along y, so the normal tilts towards x.
+            //
+            assert_eq!(&transform * &normal, Normal3::new(0.89443, 0.44721, 0));
+        }
+
+        it "should be scaled to a vector" {
+            let normal = Normal3::new(0, 1, 0);
+
+            assert_eq!(normal * 2.0, Vector3::new(0, 2, 0));
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::ops::{Add, Index, Sub};
+
+use super::{tuple::POINT_TYPE, Tuple, Vector3};
+use crate::lang::{ApproximateFloatOps, Float};
+
+// Position in space. Only the operations that are meaningful for positions are implemented; in
+// particular, points can't be added to each other, and the difference between two points is a Vector3.
+//
+// Transformations are applied via Matrix4/Transform multiplication, and include the translation.
+//
+#[derive(Clone, Copy, Debug)]
+pub struct Point3 {
+    pub x: Float,
+    pub y: Float,
+    pub z: Float,
+}
+
+impl Point3 {
+    pub fn new<T: Into<f64>, U: Into<f64>, V: Into<f64>>(x: T, y: U, z: V) -> Self {
+        Self {
+            x: x.into() as Float,
+            y: y.into() as Float,
+            z: z.into() as Float,
+        }
+    }
+
+    pub fn origin() -> Self {
+        Self::new(0, 0, 0)
+    }
+
+    // Vector from the origin to this point; convenient for shapes centered on the origin (e.g. the
+    // sphere normal).
+    //
+    pub fn to_vector(&self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+}
+
+impl Index<usize> for Point3 {
+    type Output = Float;
+
+    fn index(&self, y: usize) -> &Self::Output {
+        match y {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index too high!"),
+        }
+    }
+}
+
+impl PartialEq for Point3 {
+    // Values are considered as equal if within ε.
+    //
+    fn eq(&self, rhs: &Self) -> bool {
+        self.x.approximate_equals(rhs.x)
+            && self.y.approximate_equals(rhs.y)
+            && self.z.approximate_equals(rhs.z)
+    }
+}
+
+impl Add<&Vector3> for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: &Vector3) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl Sub<&Vector3> for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: &Vector3) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl Sub<&Point3> for Point3 {
+    type Output = Vector3;
+
+    fn sub(self, rhs: &Point3) -> Self::Output {
+        Vector3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl From<Point3> for Tuple {
+    fn from(point: Point3) -> Self {
+        Tuple::point(point.x, point.y, point.z)
+    }
+}
+
+impl From<Tuple> for Point3 {
+    fn from(tuple: Tuple) -> Self {
+        if tuple.w != POINT_TYPE {
+            panic!("The tuple is not a point: {:?}", tuple);
+        }
+
+        Self::new(tuple.x, tuple.y, tuple.z)
+    }
+}
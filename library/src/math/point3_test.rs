@@ -0,0 +1,40 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Point3" {
+        use crate::math::*;
+
+        it "should subtract a point, returning a vector" {
+            let p1 = Point3::new(3, 2, 1);
+            let p2 = Point3::new(5, 6, 7);
+
+            assert_eq!(p1 - &p2, Vector3::new(-2, -4, -6));
+        }
+
+        it "should add and subtract a vector" {
+            let point = Point3::new(3, 2, 1);
+            let vector = Vector3::new(5, 6, 7);
+
+            assert_eq!(point + &vector, Point3::new(8, 8, 8));
+            assert_eq!(point - &vector, Point3::new(-2, -4, -6));
+        }
+
+        it "should be translated by a matrix" {
+            let point = Point3::new(-3, 4, 5);
+
+            assert_eq!(Matrix4::translation(5, -3, 2) * &point, Point3::new(2, 1, 7));
+        }
+
+        it "should convert from/to a tuple" {
+            let point = Point3::new(1, 2, 3);
+
+            assert_eq!(Tuple::from(point), Tuple::point(1, 2, 3));
+            assert_eq!(Point3::from(Tuple::point(1, 2, 3)), point);
+        }
+
+        #[should_panic]
+        it "should not be converted from a vector tuple" {
+            let _ = Point3::from(Tuple::vector(1, 2, 3));
+        }
+    }
+}
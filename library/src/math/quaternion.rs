@@ -0,0 +1,227 @@
+use crate::lang::Float;
+use std::ops::Mul;
+
+use super::{Matrix4, Point3, Vector3};
+use crate::lang::{math::sqrt, ApproximateFloatOps};
+
+// Rotation quaternion (w + xi + yj + zk). All the rotation APIs assume (and produce) unit quaternions;
+// the only exception is the raw constructor.
+//
+#[derive(Clone, Copy, Debug)]
+pub struct Quaternion {
+    pub w: Float,
+    pub x: Float,
+    pub y: Float,
+    pub z: Float,
+}
+
+impl Quaternion {
+    pub fn new<T: Into<f64>>(w: T, x: T, y: T, z: T) -> Self {
+        Self {
+            w: w.into() as Float,
+            x: x.into() as Float,
+            y: y.into() as Float,
+            z: z.into() as Float,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1, 0, 0, 0)
+    }
+
+    // r: radians. The axis doesn't need to be normalized.
+    //
+    pub fn from_axis_angle(axis: &Vector3, r: Float) -> Self {
+        let axis = axis.normalize();
+        let (sin_half_r, cos_half_r) = (r / 2.0).sin_cos();
+
+        Self {
+            w: cos_half_r,
+            x: axis.x * sin_half_r,
+            y: axis.y * sin_half_r,
+            z: axis.z * sin_half_r,
+        }
+    }
+
+    // Uses the upper 3x3 of the matrix, which must be a pure rotation (orthonormal, determinant 1).
+    //
+    // Shepperd's method: the largest among the diagonal-derived components is computed first, so that the
+    // divisions are numerically stable.
+    //
+    pub fn from_rotation_matrix(matrix: &Matrix4) -> Self {
+        let m = &matrix.values;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+
+        let quaternion = if trace > 0.0 {
+            let s = sqrt(trace + 1.0) * 2.0;
+
+            Self {
+                w: s / 4.0,
+                x: (m[2][1] - m[1][2]) / s,
+                y: (m[0][2] - m[2][0]) / s,
+                z: (m[1][0] - m[0][1]) / s,
+            }
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = sqrt(1.0 + m[0][0] - m[1][1] - m[2][2]) * 2.0;
+
+            Self {
+                w: (m[2][1] - m[1][2]) / s,
+                x: s / 4.0,
+                y: (m[0][1] + m[1][0]) / s,
+                z: (m[0][2] + m[2][0]) / s,
+            }
+        } else if m[1][1] > m[2][2] {
+            let s = sqrt(1.0 + m[1][1] - m[0][0] - m[2][2]) * 2.0;
+
+            Self {
+                w: (m[0][2] - m[2][0]) / s,
+                x: (m[0][1] + m[1][0]) / s,
+                y: s / 4.0,
+                z: (m[1][2] + m[2][1]) / s,
+            }
+        } else {
+            let s = sqrt(1.0 + m[2][2] - m[0][0] - m[1][1]) * 2.0;
+
+            Self {
+                w: (m[1][0] - m[0][1]) / s,
+                x: (m[0][2] + m[2][0]) / s,
+                y: (m[1][2] + m[2][1]) / s,
+                z: s / 4.0,
+            }
+        };
+
+        quaternion.normalize()
+    }
+
+    pub fn magnitude(&self) -> Float {
+        sqrt(self.dot_product(self))
+    }
+
+    pub fn normalize(&self) -> Self {
+        let magnitude = self.magnitude();
+
+        Self {
+            w: self.w / magnitude,
+            x: self.x / magnitude,
+            y: self.y / magnitude,
+            z: self.z / magnitude,
+        }
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    pub fn dot_product(&self, rhs: &Self) -> Float {
+        self.w * rhs.w + self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    // Spherical linear interpolation; t is in [0, 1]. The shortest path is always taken.
+    //
+    pub fn slerp(&self, rhs: &Self, t: Float) -> Self {
+        let mut dot_product = self.dot_product(rhs);
+
+        // q and -q represent the same rotation; flipping the target guarantees the shortest arc.
+        //
+        let rhs = if dot_product < 0.0 {
+            dot_product = -dot_product;
+            Self::new(-rhs.w, -rhs.x, -rhs.y, -rhs.z)
+        } else {
+            *rhs
+        };
+
+        // When the quaternions are (almost) parallel, sin(theta) is (almost) zero, so the division is
+        // unstable; a normalized linear interpolation is indistinguishable at this point.
+        //
+        let (self_weight, rhs_weight) = if dot_product.approximate_equals(1.0) {
+            (1.0 - t, t)
+        } else {
+            let theta = dot_product.acos();
+            let sin_theta = theta.sin();
+
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Self {
+            w: self.w * self_weight + rhs.w * rhs_weight,
+            x: self.x * self_weight + rhs.x * rhs_weight,
+            y: self.y * self_weight + rhs.y * rhs_weight,
+            z: self.z * self_weight + rhs.z * rhs_weight,
+        }
+        .normalize()
+    }
+
+    pub fn to_matrix(&self) -> Matrix4 {
+        let Self { w, x, y, z } = *self;
+
+        #[rustfmt::skip]
+        let values = [
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z),       2.0 * (x * z + w * y),       0.0],
+            [2.0 * (x * y + w * z),       1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x),       0.0],
+            [2.0 * (x * z - w * y),       2.0 * (y * z + w * x),       1.0 - 2.0 * (x * x + y * y), 0.0],
+            [0.0,                         0.0,                         0.0,                         1.0],
+        ];
+
+        Matrix4 { values }
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+// q and -q represent the same rotation, so they're considered equal.
+//
+impl PartialEq for Quaternion {
+    fn eq(&self, rhs: &Self) -> bool {
+        let approximate_equals = |rhs: &Self| {
+            self.w.approximate_equals(rhs.w)
+                && self.x.approximate_equals(rhs.x)
+                && self.y.approximate_equals(rhs.y)
+                && self.z.approximate_equals(rhs.z)
+        };
+
+        approximate_equals(rhs) || approximate_equals(&Self::new(-rhs.w, -rhs.x, -rhs.y, -rhs.z))
+    }
+}
+
+// Hamilton product; the result applies `rhs` first, then `self` (like the matrix multiplication).
+//
+impl Mul<&Quaternion> for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: &Quaternion) -> Self::Output {
+        Self {
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        }
+    }
+}
+
+impl Mul<&Point3> for Quaternion {
+    type Output = Point3;
+
+    fn mul(self, rhs: &Point3) -> Self::Output {
+        self.to_matrix() * rhs
+    }
+}
+
+impl Mul<&Vector3> for Quaternion {
+    type Output = Vector3;
+
+    fn mul(self, rhs: &Vector3) -> Self::Output {
+        self.to_matrix() * rhs
+    }
+}
@@ -0,0 +1,66 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Quaternion" {
+        use crate::math::*;
+        use crate::Axis;
+        use crate::lang::consts::PI;
+
+        it "should convert an axis/angle rotation to the matching matrix" {
+            let quaternion = Quaternion::from_axis_angle(&Vector3::new(0, 2, 0), PI / 3.0);
+
+            assert_eq!(quaternion.to_matrix(), Matrix4::rotation(Axis::Y, PI / 3.0));
+        }
+
+        it "should compose rotations like the matrices" {
+            let q1 = Quaternion::from_axis_angle(&Vector3::new(1, 0, 0), PI / 2.0);
+            let q2 = Quaternion::from_axis_angle(&Vector3::new(0, 0, 1), PI / 4.0);
+
+            let expected_matrix = Matrix4::rotation(Axis::Z, PI / 4.0) * &Matrix4::rotation(Axis::X, PI / 2.0);
+
+            assert_eq!((q2 * &q1).to_matrix(), expected_matrix);
+        }
+
+        it "should rotate a tuple; the conjugate reverts the rotation" {
+            let quaternion = Quaternion::from_axis_angle(&Vector3::new(0, 0, 1), PI / 2.0);
+            let point = Point3::new(1, 0, 0);
+
+            let rotated_point = quaternion * &point;
+
+            assert_eq!(rotated_point, Point3::new(0, 1, 0));
+            assert_eq!(quaternion.conjugate() * &rotated_point, point);
+        }
+
+        it "should be extracted from a rotation matrix" {
+            let axes = [Vector3::new(1, 2, 3), Vector3::new(-1, 0, 0), Vector3::new(0, 1, -1)];
+
+            for axis in axes {
+                for r in [0.3, PI / 2.0, 0.9 * PI] {
+                    let quaternion = Quaternion::from_axis_angle(&axis, r);
+
+                    assert_eq!(Quaternion::from_rotation_matrix(&quaternion.to_matrix()), quaternion);
+                }
+            }
+        }
+
+        context "slerp" {
+            it "should interpolate the angle" {
+                let axis = Vector3::new(0, 1, 0);
+                let q1 = Quaternion::identity();
+                let q2 = Quaternion::from_axis_angle(&axis, PI / 2.0);
+
+                assert_eq!(q1.slerp(&q2, 0.0), q1);
+                assert_eq!(q1.slerp(&q2, 0.5), Quaternion::from_axis_angle(&axis, PI / 4.0));
+                assert_eq!(q1.slerp(&q2, 1.0), q2);
+            }
+
+            it "should take the shortest path" {
+                let axis = Vector3::new(0, 1, 0);
+                let q1 = Quaternion::identity();
+                let q2 = Quaternion::from_axis_angle(&axis, 1.5 * PI);
+
+                assert_eq!(q1.slerp(&q2, 0.5), Quaternion::from_axis_angle(&axis, -PI / 4.0));
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::{ops::Mul, sync::OnceLock};
+
+use super::{Matrix4, Normal3, Point3, Tuple, Vector3};
+
+// Transformation matrix, which caches its inverse and inverse-transpose; they're required for each
+// ray/shading point, so recomputing them each time is a significant waste.
+//
+// The derived matrices are computed lazily, since not all the transforms need them (and not all the
+// matrices are invertible); modifying the matrix via `matrix_mut()` invalidates them.
+//
+#[derive(Clone, Debug)]
+pub struct Transform {
+    matrix: Matrix4,
+    inverse: OnceLock<Matrix4>,
+    inverse_transpose: OnceLock<Matrix4>,
+}
+
+impl Transform {
+    pub fn new(matrix: Matrix4) -> Self {
+        Self {
+            matrix,
+            inverse: OnceLock::new(),
+            inverse_transpose: OnceLock::new(),
+        }
+    }
+
+    pub fn matrix(&self) -> &Matrix4 {
+        &self.matrix
+    }
+
+    pub fn matrix_mut(&mut self) -> &mut Matrix4 {
+        self.inverse = OnceLock::new();
+        self.inverse_transpose = OnceLock::new();
+
+        &mut self.matrix
+    }
+
+    pub fn inverse(&self) -> &Matrix4 {
+        self.inverse.get_or_init(|| self.matrix.inverse())
+    }
+
+    pub fn inverse_transpose(&self) -> &Matrix4 {
+        self.inverse_transpose
+            .get_or_init(|| self.inverse().transpose())
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::new(Matrix4::identity())
+    }
+}
+
+impl From<Matrix4> for Transform {
+    fn from(matrix: Matrix4) -> Self {
+        Self::new(matrix)
+    }
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.matrix == rhs.matrix
+    }
+}
+
+impl PartialEq<Matrix4> for Transform {
+    fn eq(&self, rhs: &Matrix4) -> bool {
+        self.matrix == *rhs
+    }
+}
+
+impl Mul<&Tuple> for &Transform {
+    type Output = Tuple;
+
+    fn mul(self, rhs: &Tuple) -> Self::Output {
+        self.matrix * rhs
+    }
+}
+
+impl Mul<&Point3> for &Transform {
+    type Output = Point3;
+
+    fn mul(self, rhs: &Point3) -> Self::Output {
+        self.matrix * rhs
+    }
+}
+
+impl Mul<&Vector3> for &Transform {
+    type Output = Vector3;
+
+    fn mul(self, rhs: &Vector3) -> Self::Output {
+        self.matrix * rhs
+    }
+}
+
+// Normals are transformed via the inverse-transpose; the result is normalized.
+//
+impl Mul<&Normal3> for &Transform {
+    type Output = Normal3;
+
+    fn mul(self, rhs: &Normal3) -> Self::Output {
+        rhs.transform(self.inverse_transpose())
+    }
+}
@@ -0,0 +1,35 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Transform" {
+        use crate::math::*;
+        use crate::Axis;
+        use crate::lang::consts::PI;
+
+        it "Caches the inverse and the inverse-transpose" {
+            let matrix = Matrix4::scaling(1, 2, 3).rotate(Axis::Y, PI / 4.0).translate(5, 0, 0);
+            let transform = Transform::from(matrix);
+
+            assert_eq!(*transform.inverse(), matrix.inverse());
+            assert_eq!(*transform.inverse_transpose(), matrix.inverse().transpose());
+        }
+
+        it "Modifying the matrix invalidates the cache" {
+            let mut transform = Transform::from(Matrix4::translation(1, 0, 0));
+
+            assert_eq!(*transform.inverse(), Matrix4::translation(-1, 0, 0));
+
+            *transform.matrix_mut() = Matrix4::translation(2, 0, 0);
+
+            assert_eq!(*transform.inverse(), Matrix4::translation(-2, 0, 0));
+            assert_eq!(*transform.inverse_transpose(), Matrix4::translation(-2, 0, 0).transpose());
+        }
+
+        it "Non-invertible matrices are allowed, when the inverse is not used" {
+            let transform = Transform::from(Matrix4::scaling(0, 0, 0));
+            let point = Tuple::point(1, 2, 3);
+
+            assert_eq!(&transform * &point, Tuple::point(0, 0, 0));
+        }
+    }
+}
@@ -1,60 +1,64 @@
 use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
 
-use super::Matrix;
+use super::Matrix4;
 use crate::{
-    lang::{math::sqrt, ApproximateFloat64Ops},
+    lang::Float,
+    lang::{math::sqrt, ApproximateFloatOps},
     Axis,
 };
 
-pub const POINT_TYPE: f64 = 1.0;
-pub const VECTOR_TYPE: f64 = 0.0;
+pub const POINT_TYPE: Float = 1.0;
+pub const VECTOR_TYPE: Float = 0.0;
 
 // At this stage, is not clear is direct operation will be carried also on the type field. If so, using
 // an enum may make things more complicated, so conservatively, a float is used.
 //
-// This struct could be divided in Vector and Point. This has the advantage of enforcing type safety,
-// e.g., disallowing adding a Point to a Point, at the cost of either duplication of the methods, or
-// of creating a trait exposing x/y/z/w, which would make attribute access uneven in cases where the
-// trait is referenced. At this stage, it's better to wait to see how the vector/point logic mix, although
-// if there isn't much mixing, splitting could be advantageous.
+// The renderer (rays, shading, shapes) uses the typed Point3/Vector3/Normal3, which enforce type safety
+// (e.g. disallowing adding a point to a point, or passing a point where a normal is expected); this
+// struct is the generic homogeneous tuple, used for the raw matrix math, and convertible from/to the
+// typed ones.
+//
+// The layout is fixed and aligned to 32 bytes (the size of the four fields), so that the compiler can
+// load/operate on a tuple as a single 256-bit vector when autovectorizing.
 //
 #[derive(Clone, Copy, Debug)]
+#[repr(C, align(32))]
 pub struct Tuple {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-    pub w: f64,
+    pub x: Float,
+    pub y: Float,
+    pub z: Float,
+    pub w: Float,
 }
 
 impl Tuple {
     pub fn new<T: Into<f64>>(x: T, y: T, z: T, w: T) -> Self {
         Self {
-            x: x.into(),
-            y: y.into(),
-            z: z.into(),
-            w: w.into(),
+            x: x.into() as Float,
+            y: y.into() as Float,
+            z: z.into() as Float,
+            w: w.into() as Float,
         }
     }
 
     pub fn point<T: Into<f64>, U: Into<f64>, V: Into<f64>>(x: T, y: U, z: V) -> Self {
         Self {
-            x: x.into(),
-            y: y.into(),
-            z: z.into(),
+            x: x.into() as Float,
+            y: y.into() as Float,
+            z: z.into() as Float,
             w: POINT_TYPE,
         }
     }
 
     pub fn vector<T: Into<f64>, U: Into<f64>, V: Into<f64>>(x: T, y: U, z: V) -> Self {
         Self {
-            x: x.into(),
-            y: y.into(),
-            z: z.into(),
+            x: x.into() as Float,
+            y: y.into() as Float,
+            z: z.into() as Float,
             w: VECTOR_TYPE,
         }
     }
 
-    pub fn magnitude(&self) -> f64 {
+    pub fn magnitude(&self) -> Float {
         sqrt(self.x.powi(2) + self.y.powi(2) + self.z.powi(2) + self.w.powi(2))
     }
 
@@ -64,7 +68,7 @@ impl Tuple {
         Self::vector(self.x / magnitude, self.y / magnitude, self.z / magnitude)
     }
 
-    pub fn dot_product(&self, rhs: &Tuple) -> f64 {
+    pub fn dot_product(&self, rhs: &Tuple) -> Float {
         self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
     }
 
@@ -77,19 +81,19 @@ impl Tuple {
     }
 
     pub fn translate<T: Into<f64>>(&self, x: T, y: T, z: T) -> Self {
-        Matrix::translation(x, y, z) * self
+        Matrix4::translation(x, y, z) * self
     }
 
     pub fn scale<T: Into<f64>>(&self, x: T, y: T, z: T) -> Self {
-        Matrix::scaling(x, y, z) * self
+        Matrix4::scaling(x, y, z) * self
     }
 
-    pub fn rotate(&self, axis: Axis, r: f64) -> Self {
-        Matrix::rotation(axis, r) * self
+    pub fn rotate<T: Into<f64>>(&self, axis: Axis, r: T) -> Self {
+        Matrix4::rotation(axis, r) * self
     }
 
     pub fn shear<T: Into<f64>>(self, x_py: T, x_pz: T, y_px: T, y_pz: T, z_px: T, z_py: T) -> Self {
-        Matrix::shearing(x_py, x_pz, y_px, y_pz, z_px, z_py) * &self
+        Matrix4::shearing(x_py, x_pz, y_px, y_pz, z_px, z_py) * &self
     }
 
     // In the book, this is `reflect(in, normal)`
@@ -102,7 +106,7 @@ impl Tuple {
 // Index[Mut] implementations are for the lulz, although they're actually convenient for matrix operations.
 //
 impl Index<usize> for Tuple {
-    type Output = f64;
+    type Output = Float;
 
     fn index(&self, y: usize) -> &Self::Output {
         match y {
@@ -116,7 +120,7 @@ impl Index<usize> for Tuple {
 }
 
 impl IndexMut<usize> for Tuple {
-    fn index_mut(&mut self, y: usize) -> &mut f64 {
+    fn index_mut(&mut self, y: usize) -> &mut Float {
         match y {
             0 => &mut self.x,
             1 => &mut self.y,
@@ -177,10 +181,10 @@ impl Neg for Tuple {
     }
 }
 
-impl Mul<f64> for Tuple {
+impl Mul<Float> for Tuple {
     type Output = Self;
 
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: Float) -> Self::Output {
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -190,10 +194,10 @@ impl Mul<f64> for Tuple {
     }
 }
 
-impl Div<f64> for Tuple {
+impl Div<Float> for Tuple {
     type Output = Self;
 
-    fn div(self, rhs: f64) -> Self::Output {
+    fn div(self, rhs: Float) -> Self::Output {
         Self {
             x: self.x / rhs,
             y: self.y / rhs,
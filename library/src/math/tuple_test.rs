@@ -4,8 +4,8 @@ demonstrate! {
     describe "Tuple" {
         use crate::*;
         use crate::math::{*, tuple::{POINT_TYPE, VECTOR_TYPE}};
-        use crate::lang::{math::sqrt, ApproximateFloat64Ops};
-        use std::f64::consts::PI;
+        use crate::lang::{math::sqrt, ApproximateFloatOps};
+        use crate::lang::consts::PI;
 
         context "with w=1_0" {
             it "is a point" {
@@ -0,0 +1,149 @@
+use crate::lang::Float;
+use std::ops::{Add, Div, Index, Mul, Neg, Sub};
+
+use super::{tuple::VECTOR_TYPE, Normal3, Tuple};
+use crate::lang::{math::sqrt, ApproximateFloatOps};
+
+// Direction/displacement in space. Transformations don't apply the translation.
+//
+#[derive(Clone, Copy, Debug)]
+pub struct Vector3 {
+    pub x: Float,
+    pub y: Float,
+    pub z: Float,
+}
+
+impl Vector3 {
+    pub fn new<T: Into<f64>, U: Into<f64>, V: Into<f64>>(x: T, y: U, z: V) -> Self {
+        Self {
+            x: x.into() as Float,
+            y: y.into() as Float,
+            z: z.into() as Float,
+        }
+    }
+
+    pub fn magnitude(&self) -> Float {
+        sqrt(self.dot_product(self))
+    }
+
+    pub fn normalize(&self) -> Self {
+        *self / self.magnitude()
+    }
+
+    pub fn dot_product(&self, rhs: &Vector3) -> Float {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn cross_product(&self, rhs: &Vector3) -> Self {
+        Self {
+            x: self.y * rhs.z - self.z * rhs.y,
+            y: self.z * rhs.x - self.x * rhs.z,
+            z: self.x * rhs.y - self.y * rhs.x,
+        }
+    }
+
+    // In the book, this is `reflect(in, normal)`
+    //
+    pub fn reflect(&self, normal: &Normal3) -> Self {
+        *self - &(*normal * (2.0 * normal.dot_product(self)))
+    }
+}
+
+impl Index<usize> for Vector3 {
+    type Output = Float;
+
+    fn index(&self, y: usize) -> &Self::Output {
+        match y {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index too high!"),
+        }
+    }
+}
+
+impl PartialEq for Vector3 {
+    // Values are considered as equal if within ε.
+    //
+    fn eq(&self, rhs: &Self) -> bool {
+        self.x.approximate_equals(rhs.x)
+            && self.y.approximate_equals(rhs.y)
+            && self.z.approximate_equals(rhs.z)
+    }
+}
+
+impl Add<&Self> for Vector3 {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl Sub<&Self> for Vector3 {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl Neg for Vector3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl Mul<Float> for Vector3 {
+    type Output = Self;
+
+    fn mul(self, rhs: Float) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl Div<Float> for Vector3 {
+    type Output = Self;
+
+    fn div(self, rhs: Float) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+        }
+    }
+}
+
+impl From<Vector3> for Tuple {
+    fn from(vector: Vector3) -> Self {
+        Tuple::vector(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Tuple> for Vector3 {
+    fn from(tuple: Tuple) -> Self {
+        if tuple.w != VECTOR_TYPE {
+            panic!("The tuple is not a vector: {:?}", tuple);
+        }
+
+        Self::new(tuple.x, tuple.y, tuple.z)
+    }
+}
@@ -0,0 +1,38 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Vector3" {
+        use crate::math::*;
+        use crate::lang::math::sqrt;
+
+        it "should compute the magnitude, and normalize" {
+            let vector = Vector3::new(1, 2, 3);
+
+            assert_float_absolute_eq!(vector.magnitude(), sqrt(14));
+            assert_eq!(vector.normalize(), Vector3::new(1.0 / sqrt(14), 2.0 / sqrt(14), 3.0 / sqrt(14)));
+        }
+
+        it "should compute the dot and cross products" {
+            let v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(2, 3, 4);
+
+            assert_float_absolute_eq!(v1.dot_product(&v2), 20.0);
+            assert_eq!(v1.cross_product(&v2), Vector3::new(-1, 2, -1));
+            assert_eq!(v2.cross_product(&v1), Vector3::new(1, -2, 1));
+        }
+
+        it "should not be translated by a matrix" {
+            let vector = Vector3::new(-3, 4, 5);
+
+            assert_eq!(Matrix4::translation(5, -3, 2) * &vector, vector);
+            assert_eq!(Matrix4::scaling(2, 3, 4) * &vector, Vector3::new(-6, 12, 20));
+        }
+
+        it "should reflect around a normal" {
+            let vector = Vector3::new(0, -1, 0);
+            let normal = Normal3::new(sqrt(2) / 2.0, sqrt(2) / 2.0, 0);
+
+            assert_eq!(vector.reflect(&normal), Vector3::new(1, 0, 0));
+        }
+    }
+}
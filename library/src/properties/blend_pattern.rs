@@ -0,0 +1,69 @@
+use super::{Color, Pattern, PatternSlot, COLOR_BLACK, COLOR_WHITE};
+use crate::{
+    lang::Float,
+    math::{Point3, Transform},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BlendMode {
+    // The blended color replaces the base one; with the default weight, this is the average.
+    //
+    #[default]
+    Normal,
+    Multiply,
+    Add,
+    // Inverse of Multiply: the colors are inverted, multiplied, then inverted again, which always
+    // brightens.
+    //
+    Screen,
+}
+
+impl BlendMode {
+    pub fn blend(&self, base: &Color, blended: &Color) -> Color {
+        match self {
+            BlendMode::Normal => *blended,
+            BlendMode::Multiply => *base * blended,
+            BlendMode::Add => *base + blended,
+            BlendMode::Screen => COLOR_WHITE - &((COLOR_WHITE - base) * &(COLOR_WHITE - blended)),
+        }
+    }
+}
+
+// Blends the `blended` input over the `base` one, like a layer in an image editor; the weight is the
+// opacity of the layer.
+//
+// Unlike `previous_pattern` composition, which averages the patterns, this allows arbitrary weights and
+// blend modes.
+//
+#[derive(Debug, SmartDefault)]
+pub struct BlendPattern {
+    #[default(_code = "COLOR_WHITE.into()")]
+    pub base: PatternSlot,
+    #[default(_code = "COLOR_BLACK.into()")]
+    pub blended: PatternSlot,
+    pub mode: BlendMode,
+    #[default(0.5)]
+    pub weight: Float,
+    pub transform: Transform,
+    #[default(None)]
+    pub previous_pattern: Option<Box<dyn Pattern>>,
+}
+
+impl Pattern for BlendPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn previous_pattern(&self) -> &Option<Box<dyn Pattern>> {
+        &self.previous_pattern
+    }
+
+    // point: In pattern space.
+    //
+    fn current_color_at(&self, point: &Point3) -> Color {
+        let base = self.base.color_at(point);
+        let blended = self.mode.blend(&base, &self.blended.color_at(point));
+
+        base * (1.0 - self.weight) + &(blended * self.weight)
+    }
+}
@@ -0,0 +1,87 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "BlendPattern" {
+        use crate::math::*;
+        use crate::properties::*;
+
+        before {
+            #[allow(unused_variables)]
+            let base = Color::new(0.5, 0.2, 1.0);
+            #[allow(unused_variables)]
+            let blended = Color::new(0.4, 0.5, 0.0);
+        }
+
+        it "should average the inputs, by default" {
+            let pattern = BlendPattern {
+                base: base.into(),
+                blended: blended.into(),
+                ..BlendPattern::default()
+            };
+
+            assert_eq!(pattern.color_at(&Point3::new(0, 0, 0)), Color::new(0.45, 0.35, 0.5));
+        }
+
+        it "should weight the blended input" {
+            let pattern = BlendPattern {
+                base: base.into(),
+                blended: blended.into(),
+                weight: 0.25,
+                ..BlendPattern::default()
+            };
+
+            assert_eq!(pattern.color_at(&Point3::new(0, 0, 0)), Color::new(0.475, 0.275, 0.75));
+        }
+
+        context "blend modes (fully weighted)" {
+            it "multiply" {
+                let pattern = BlendPattern {
+                    base: base.into(),
+                    blended: blended.into(),
+                    mode: BlendMode::Multiply,
+                    weight: 1.0,
+                    ..BlendPattern::default()
+                };
+
+                assert_eq!(pattern.color_at(&Point3::new(0, 0, 0)), Color::new(0.2, 0.1, 0.0));
+            }
+
+            it "add" {
+                let pattern = BlendPattern {
+                    base: base.into(),
+                    blended: blended.into(),
+                    mode: BlendMode::Add,
+                    weight: 1.0,
+                    ..BlendPattern::default()
+                };
+
+                assert_eq!(pattern.color_at(&Point3::new(0, 0, 0)), Color::new(0.9, 0.7, 1.0));
+            }
+
+            it "screen" {
+                let pattern = BlendPattern {
+                    base: base.into(),
+                    blended: blended.into(),
+                    mode: BlendMode::Screen,
+                    weight: 1.0,
+                    ..BlendPattern::default()
+                };
+
+                assert_eq!(pattern.color_at(&Point3::new(0, 0, 0)), Color::new(0.7, 0.6, 1.0));
+            }
+        }
+
+        it "should blend sub-patterns" {
+            let pattern = BlendPattern {
+                base: StripePattern::default().into(),
+                blended: COLOR_RED.into(),
+                mode: BlendMode::Multiply,
+                weight: 1.0,
+                ..BlendPattern::default()
+            };
+
+            assert_eq!(pattern.color_at(&Point3::new(0.5, 0, 0)), COLOR_RED);
+            assert_eq!(pattern.color_at(&Point3::new(1.5, 0, 0)), COLOR_BLACK);
+        }
+    }
+}
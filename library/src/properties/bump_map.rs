@@ -0,0 +1,105 @@
+use super::Pattern;
+use crate::{
+    lang::Float,
+    math::{Normal3, Point3, Vector3, EPSILON},
+    space::TextureCoordinates,
+};
+
+// Perturbs the shading normal of a surface, in order to simulate small details, without modifying the
+// geometry (silhouettes and shadows are unaffected).
+//
+// Both the variants sample the pattern like Material#lighting(), i.e. applying the pattern transform.
+//
+#[derive(Debug)]
+pub enum BumpMap {
+    // Height field, given by the pattern intensity, sampled at the object
+    // point. The normal is tilted against the height gradient, which is computed via finite differences;
+    // the strength scales the heights.
+    //
+    Height {
+        pattern: Box<dyn Pattern>,
+        strength: Float,
+    },
+    // Tangent-space normals, encoded as colors ([0, 1] -> [-1, 1]; the z axis is the surface normal),
+    // sampled at (u, v, 0). The strength scales the tangent components.
+    //
+    // Applies only to the shapes with texture coordinates; on the others, the normal is unchanged.
+    //
+    Normal {
+        pattern: Box<dyn Pattern>,
+        strength: Float,
+    },
+}
+
+impl BumpMap {
+    // point, normal: In object space; the normal must be normalized.
+    //
+    pub fn perturb(
+        &self,
+        point: &Point3,
+        normal: &Normal3,
+        texture_coordinates: Option<&TextureCoordinates>,
+    ) -> Normal3 {
+        let normal = Vector3::from(*normal);
+
+        match self {
+            BumpMap::Height { pattern, strength } => {
+                let gradient = Self::height_gradient(pattern.as_ref(), point);
+                let surface_gradient = gradient - &(normal * normal.dot_product(&gradient));
+
+                (normal - &(surface_gradient * *strength))
+                    .normalize()
+                    .into()
+            }
+            BumpMap::Normal { pattern, strength } => {
+                let Some(texture_coordinates) = texture_coordinates else {
+                    return normal.into();
+                };
+
+                let (u, v) = texture_coordinates.uv;
+                let color = pattern.transformed_color_at(&Point3 { x: u, y: v, z: 0.0 });
+
+                let (x, y, z) = (
+                    (color.r * 2.0 - 1.0) * strength,
+                    (color.g * 2.0 - 1.0) * strength,
+                    color.b * 2.0 - 1.0,
+                );
+
+                // The tangent frame is orthonormalized (Gram-Schmidt), since the mapping is not
+                // necessarily conformal, and interpolated normals are not orthogonal to the face.
+                //
+                let tangent = texture_coordinates.tangent;
+                let tangent = (tangent - &(normal * normal.dot_product(&tangent))).normalize();
+                let bitangent = texture_coordinates.bitangent;
+                let bitangent = (bitangent
+                    - &(normal * normal.dot_product(&bitangent))
+                    - &(tangent * tangent.dot_product(&bitangent)))
+                    .normalize();
+
+                (tangent * x + &(bitangent * y) + &(normal * z))
+                    .normalize()
+                    .into()
+            }
+        }
+    }
+
+    // Central differences, along each axis.
+    //
+    fn height_gradient(pattern: &dyn Pattern, point: &Point3) -> Vector3 {
+        let height_at = |offset: Vector3| {
+            pattern
+                .transformed_color_at(&(*point + &offset))
+                .intensity()
+        };
+
+        let derivative = |axis: Vector3| {
+            (height_at(axis * EPSILON) - height_at(axis * -EPSILON)) / (2.0 * EPSILON)
+        };
+
+        Vector3 {
+            x: derivative(Vector3::new(1, 0, 0)),
+            y: derivative(Vector3::new(0, 1, 0)),
+            z: derivative(Vector3::new(0, 0, 1)),
+        }
+    }
+}
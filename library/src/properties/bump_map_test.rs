@@ -0,0 +1,80 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "BumpMap" {
+        use crate::math::*;
+        use crate::properties::*;
+        use crate::space::*;
+
+        before {
+            #[allow(unused_variables)]
+            let point = Point3::new(0.3, 0, 0.2);
+            #[allow(unused_variables)]
+            let normal = Normal3::new(0, 1, 0);
+            // Maps the identity to the tangent space of the xz plane.
+            //
+            #[allow(unused_variables)]
+            let texture_coordinates = TextureCoordinates {
+                uv: (0.5, 0.5),
+                tangent: Vector3::new(1, 0, 0),
+                bitangent: Vector3::new(0, 0, -1),
+            };
+        }
+
+        context "height" {
+            it "should not perturb the normal, with a constant height" {
+                let bump_map = BumpMap::Height { pattern: Box::new(FlatPattern::new(0.5, 0.5, 0.5)), strength: 1.0 };
+
+                assert_eq!(bump_map.perturb(&point, &normal, None), normal);
+            }
+
+            it "should tilt the normal against the height gradient" {
+                // Height = (x + 10) / 20, so the gradient is (0.05, 0, 0).
+                //
+                let pattern = RampPattern {
+                    ramp: ColorRamp::new(vec![(-10.0, COLOR_BLACK), (10.0, COLOR_WHITE)], RampInterpolation::Linear),
+                    ..RampPattern::default()
+                };
+                let bump_map = BumpMap::Height { pattern: Box::new(pattern), strength: 20.0 };
+
+                assert_eq!(bump_map.perturb(&point, &normal, None), Normal3::new(-1, 1, 0).normalize());
+            }
+
+            it "should ignore the gradient component along the normal" {
+                let pattern = RampPattern {
+                    ramp: ColorRamp::new(vec![(-10.0, COLOR_BLACK), (10.0, COLOR_WHITE)], RampInterpolation::Linear),
+                    ..RampPattern::default()
+                };
+                let bump_map = BumpMap::Height { pattern: Box::new(pattern), strength: 20.0 };
+
+                let normal = Normal3::new(1, 0, 0);
+
+                assert_eq!(bump_map.perturb(&point, &normal, None), normal);
+            }
+        }
+
+        context "normal" {
+            it "should not perturb the normal, with the neutral color" {
+                let bump_map = BumpMap::Normal { pattern: Box::new(FlatPattern::new(0.5, 0.5, 1.0)), strength: 1.0 };
+
+                assert_eq!(bump_map.perturb(&point, &normal, Some(&texture_coordinates)), normal);
+            }
+
+            it "should map the color to the tangent frame" {
+                let bump_map = BumpMap::Normal { pattern: Box::new(FlatPattern::new(1.0, 0.5, 1.0)), strength: 1.0 };
+
+                assert_eq!(bump_map.perturb(&point, &normal, Some(&texture_coordinates)), Normal3::new(1, 1, 0).normalize());
+
+                let bump_map = BumpMap::Normal { pattern: Box::new(FlatPattern::new(0.5, 1.0, 1.0)), strength: 0.5 };
+
+                assert_eq!(bump_map.perturb(&point, &normal, Some(&texture_coordinates)), Normal3::new(0, 1, -0.5).normalize());
+            }
+
+            it "should not perturb the normal, without texture coordinates" {
+                let bump_map = BumpMap::Normal { pattern: Box::new(FlatPattern::new(1.0, 0.5, 1.0)), strength: 1.0 };
+
+                assert_eq!(bump_map.perturb(&point, &normal, None), normal);
+            }
+        }
+    }
+}
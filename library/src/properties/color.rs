@@ -1,25 +1,31 @@
 use std::ops::{Add, Div, Mul, Sub};
 
-use crate::lang::ApproximateFloat64Ops;
+use crate::lang::{ApproximateFloatOps, Float};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Color {
-    pub r: f64,
-    pub g: f64,
-    pub b: f64,
+    pub r: Float,
+    pub g: Float,
+    pub b: Float,
 }
 
 impl Color {
     pub fn new<T: Into<f64>, U: Into<f64>, V: Into<f64>>(r: T, g: U, b: V) -> Self {
         Self {
-            r: r.into(),
-            g: g.into(),
-            b: b.into(),
+            r: r.into() as Float,
+            g: g.into() as Float,
+            b: b.into() as Float,
         }
     }
 
+    // Average of the components; used when a color is interpreted as a scalar (e.g. masks and maps).
+    //
+    pub fn intensity(&self) -> Float {
+        (self.r + self.g + self.b) / 3.0
+    }
+
     pub fn u8_components(&self) -> (u8, u8, u8) {
-        fn to_u8(value: f64) -> u8 {
+        fn to_u8(value: Float) -> u8 {
             let unbounded_result = 256.0 * value;
             unbounded_result.min(255.0) as u8
         }
@@ -69,17 +75,17 @@ impl Mul<i32> for Color {
 
     fn mul(self, rhs: i32) -> Self::Output {
         Self {
-            r: self.r * rhs as f64,
-            g: self.g * rhs as f64,
-            b: self.b * rhs as f64,
+            r: self.r * rhs as Float,
+            g: self.g * rhs as Float,
+            b: self.b * rhs as Float,
         }
     }
 }
 
-impl Mul<f64> for Color {
+impl Mul<Float> for Color {
     type Output = Self;
 
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: Float) -> Self::Output {
         Self {
             r: self.r * rhs,
             g: self.g * rhs,
@@ -102,10 +108,10 @@ impl Mul<&Self> for Color {
     }
 }
 
-impl Div<f64> for Color {
+impl Div<Float> for Color {
     type Output = Self;
 
-    fn div(self, rhs: f64) -> Self::Output {
+    fn div(self, rhs: Float) -> Self::Output {
         Self {
             r: self.r / rhs,
             g: self.g / rhs,
@@ -0,0 +1,95 @@
+use super::{Color, COLOR_BLACK, COLOR_WHITE};
+use crate::lang::{consts::PI, Float};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RampInterpolation {
+    // Each stop color is held until the next stop (hard bands).
+    //
+    Constant,
+    #[default]
+    Linear,
+    // Eases in and out of each stop, using half a sine period, like GradientPattern.
+    //
+    Sine,
+}
+
+impl RampInterpolation {
+    // Maps the (linear) fraction between two stops to the interpolation one.
+    //
+    fn ease(&self, fraction: Float) -> Float {
+        match self {
+            RampInterpolation::Constant => 0.0,
+            RampInterpolation::Linear => fraction,
+            RampInterpolation::Sine => (1.0 - (PI * fraction).cos()) / 2.0,
+        }
+    }
+}
+
+// Multi-stop gradient: maps a position to a color, interpolating between the surrounding stops. Before
+// the first stop and after the last one, the colors of the respective stops are used.
+//
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorRamp {
+    // Sorted by position.
+    //
+    stops: Vec<(Float, Color)>,
+    pub interpolation: RampInterpolation,
+}
+
+impl ColorRamp {
+    // The stops don't need to be sorted; there must be at least one.
+    //
+    pub fn new(mut stops: Vec<(Float, Color)>, interpolation: RampInterpolation) -> Self {
+        if stops.is_empty() {
+            panic!("a color ramp requires at least one stop");
+        }
+
+        stops.sort_by(|(position_a, _), (position_b, _)| position_a.total_cmp(position_b));
+
+        Self {
+            stops,
+            interpolation,
+        }
+    }
+
+    pub fn stops(&self) -> &[(Float, Color)] {
+        &self.stops
+    }
+
+    pub fn color_at(&self, position: Float) -> Color {
+        let (first_position, first_color) = self.stops[0];
+        let (last_position, last_color) = self.stops[self.stops.len() - 1];
+
+        if position <= first_position {
+            return first_color;
+        } else if position >= last_position {
+            return last_color;
+        }
+
+        // The stop after the position; guaranteed to exist, and not to be the first, by the checks above.
+        //
+        let next_index = self
+            .stops
+            .iter()
+            .position(|(stop_position, _)| *stop_position > position)
+            .unwrap();
+
+        let (start_position, start_color) = self.stops[next_index - 1];
+        let (end_position, end_color) = self.stops[next_index];
+
+        let fraction = (position - start_position) / (end_position - start_position);
+
+        start_color + &((end_color - &start_color) * self.interpolation.ease(fraction))
+    }
+}
+
+// Same colors as GradientPattern.
+//
+impl Default for ColorRamp {
+    fn default() -> Self {
+        Self::new(
+            vec![(0.0, COLOR_WHITE), (1.0, COLOR_BLACK)],
+            RampInterpolation::default(),
+        )
+    }
+}
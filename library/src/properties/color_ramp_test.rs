@@ -0,0 +1,57 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "ColorRamp" {
+        use crate::properties::*;
+
+        before {
+            #[allow(unused_variables)]
+            let stops = vec![(1.0, COLOR_BLUE), (0.0, COLOR_RED), (0.5, COLOR_GREEN)];
+        }
+
+        it "should use the end stops colors outside the stops range" {
+            let ramp = ColorRamp::new(stops, RampInterpolation::Linear);
+
+            assert_eq!(ramp.color_at(-1.0), COLOR_RED);
+            assert_eq!(ramp.color_at(0.0), COLOR_RED);
+            assert_eq!(ramp.color_at(1.0), COLOR_BLUE);
+            assert_eq!(ramp.color_at(2.0), COLOR_BLUE);
+        }
+
+        it "should interpolate linearly between the surrounding stops" {
+            let ramp = ColorRamp::new(stops, RampInterpolation::Linear);
+
+            assert_eq!(ramp.color_at(0.125), Color::new(0.75, 0.25, 0));
+            assert_eq!(ramp.color_at(0.5), COLOR_GREEN);
+            assert_eq!(ramp.color_at(0.875), Color::new(0, 0.25, 0.75));
+        }
+
+        it "should hold the stop colors, with constant interpolation" {
+            let ramp = ColorRamp::new(stops, RampInterpolation::Constant);
+
+            assert_eq!(ramp.color_at(0.49), COLOR_RED);
+            assert_eq!(ramp.color_at(0.5), COLOR_GREEN);
+            assert_eq!(ramp.color_at(0.99), COLOR_GREEN);
+        }
+
+        it "should ease in and out of the stops, with sine interpolation" {
+            let ramp = ColorRamp::new(stops, RampInterpolation::Sine);
+
+            assert_eq!(ramp.color_at(0.25), Color::new(0.5, 0.5, 0));
+
+            let eased_color = ramp.color_at(0.125);
+            assert!(eased_color.r > 0.75 && eased_color.g < 0.25);
+        }
+
+        it "should sort the stops" {
+            let ramp = ColorRamp::new(stops, RampInterpolation::Linear);
+
+            assert_eq!(ramp.stops(), &[(0.0, COLOR_RED), (0.5, COLOR_GREEN), (1.0, COLOR_BLUE)]);
+        }
+
+        #[should_panic]
+        it "should require at least one stop" {
+            let _ = ColorRamp::new(vec![], RampInterpolation::Linear);
+        }
+    }
+}
@@ -1,12 +1,11 @@
 use super::{Color, Pattern, COLOR_WHITE};
-use crate::math::Matrix;
+use crate::{lang::Float, math::Transform};
 
 #[derive(Debug, SmartDefault)]
 pub struct FlatPattern {
     #[default(COLOR_WHITE)]
     pub color: Color,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
+    pub transform: Transform,
     #[default(None)]
     pub previous_pattern: Option<Box<dyn Pattern>>,
 }
@@ -15,9 +14,9 @@ impl FlatPattern {
     pub fn new<T: Into<f64>, U: Into<f64>, V: Into<f64>>(r: T, g: U, b: V) -> Self {
         Self {
             color: Color {
-                r: r.into(),
-                g: g.into(),
-                b: b.into(),
+                r: r.into() as Float,
+                g: g.into() as Float,
+                b: b.into() as Float,
             },
             ..FlatPattern::default()
         }
@@ -25,7 +24,7 @@ impl FlatPattern {
 }
 
 impl Pattern for FlatPattern {
-    fn transform(&self) -> &crate::math::Matrix {
+    fn transform(&self) -> &Transform {
         &self.transform
     }
 
@@ -35,7 +34,7 @@ impl Pattern for FlatPattern {
 
     // point: In pattern space.
     //
-    fn current_color_at(&self, _point: &crate::math::Tuple) -> Color {
+    fn current_color_at(&self, _point: &crate::math::Point3) -> Color {
         self.color
     }
 }
@@ -1,22 +1,21 @@
-use std::f64::consts::PI;
+use crate::lang::consts::PI;
 
-use super::{Color, Pattern, COLOR_BLACK, COLOR_WHITE};
-use crate::math::Matrix;
+use super::{Color, Pattern, PatternSlot, COLOR_BLACK, COLOR_WHITE};
+use crate::math::Transform;
 
 #[derive(Debug, SmartDefault)]
 pub struct GradientPattern {
-    #[default(COLOR_WHITE)]
-    pub color_a: Color,
-    #[default(COLOR_BLACK)]
-    pub color_b: Color,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
+    #[default(_code = "COLOR_WHITE.into()")]
+    pub color_a: PatternSlot,
+    #[default(_code = "COLOR_BLACK.into()")]
+    pub color_b: PatternSlot,
+    pub transform: Transform,
     #[default(None)]
     pub previous_pattern: Option<Box<dyn Pattern>>,
 }
 
 impl Pattern for GradientPattern {
-    fn transform(&self) -> &Matrix {
+    fn transform(&self) -> &Transform {
         &self.transform
     }
 
@@ -26,7 +25,7 @@ impl Pattern for GradientPattern {
 
     // point: In pattern space.
     //
-    fn current_color_at(&self, point: &crate::math::Tuple) -> Color {
+    fn current_color_at(&self, point: &crate::math::Point3) -> Color {
         // This shouldn't need float approximate, as it doesn't rely on exact transformations/operations.
         //
         // Original formula:
@@ -38,9 +37,12 @@ impl Pattern for GradientPattern {
         // This formula starts at (color_a + half_distance), for simplicity (see test suite).
         // In order to start from color_a, just shift the sin() by subtracting π/4 from x.
         //
-        let distance = self.color_b - &self.color_a;
+        let color_a = self.color_a.color_at(point);
+        let color_b = self.color_b.color_at(point);
+
+        let distance = color_b - &color_a;
         let half_distance = distance * 0.5;
 
-        self.color_a + &half_distance - &(half_distance * (2.0 * PI * point.x).sin())
+        color_a + &half_distance - &(half_distance * (2.0 * PI * point.x).sin())
     }
 }
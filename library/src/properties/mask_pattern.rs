@@ -0,0 +1,50 @@
+use super::{Color, Pattern, PatternSlot, StripePattern, COLOR_BLACK, COLOR_WHITE};
+use crate::math::{Point3, Transform};
+
+// Picks between two inputs, based on the mask intensity: where the
+// mask is black, color_a is used, and where it's white, color_b.
+//
+// Intermediate intensities (e.g. gradient or noise masks) mix the inputs, which creates smooth
+// transitions.
+//
+#[derive(Debug, SmartDefault)]
+pub struct MaskPattern {
+    // The default is phony, for default purposes.
+    //
+    #[default(_code = "StripePattern::default().into()")]
+    pub mask: PatternSlot,
+    #[default(_code = "COLOR_BLACK.into()")]
+    pub color_a: PatternSlot,
+    #[default(_code = "COLOR_WHITE.into()")]
+    pub color_b: PatternSlot,
+    pub transform: Transform,
+    #[default(None)]
+    pub previous_pattern: Option<Box<dyn Pattern>>,
+}
+
+impl Pattern for MaskPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn previous_pattern(&self) -> &Option<Box<dyn Pattern>> {
+        &self.previous_pattern
+    }
+
+    // point: In pattern space.
+    //
+    fn current_color_at(&self, point: &Point3) -> Color {
+        let intensity = self.mask.color_at(point).intensity().clamp(0.0, 1.0);
+
+        // Skip sampling the unused input, when the mask is binary.
+        //
+        if intensity == 0.0 {
+            self.color_a.color_at(point)
+        } else if intensity == 1.0 {
+            self.color_b.color_at(point)
+        } else {
+            self.color_a.color_at(point) * (1.0 - intensity)
+                + &(self.color_b.color_at(point) * intensity)
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "MaskPattern" {
+        use crate::math::*;
+        use crate::properties::*;
+
+        it "should pick the input by a binary mask" {
+            let pattern = MaskPattern {
+                mask: CheckersPattern::default().into(),
+                color_a: StripePattern {
+                    color_a: COLOR_RED.into(),
+                    color_b: COLOR_GREEN.into(),
+                    transform: Matrix4::scaling(0.5, 1.0, 1.0).into(),
+                    ..StripePattern::default()
+                }.into(),
+                color_b: COLOR_BLUE.into(),
+                ..MaskPattern::default()
+            };
+
+            // The checkers are white at the origin.
+            //
+            assert_eq!(pattern.color_at(&Point3::new(0.25, 0, 0)), COLOR_BLUE);
+            assert_eq!(pattern.color_at(&Point3::new(1.25, 0, 0)), COLOR_RED);
+            assert_eq!(pattern.color_at(&Point3::new(1.75, 0, 0)), COLOR_GREEN);
+        }
+
+        it "should mix the inputs by the mask intensity" {
+            let pattern = MaskPattern {
+                mask: Color::new(0.25, 0.25, 0.25).into(),
+                color_a: COLOR_RED.into(),
+                color_b: COLOR_BLUE.into(),
+                ..MaskPattern::default()
+            };
+
+            assert_eq!(pattern.color_at(&Point3::new(0, 0, 0)), Color::new(0.75, 0, 0.25));
+        }
+    }
+}
@@ -1,17 +1,59 @@
-use crate::{math::Tuple, properties::Color, space::PointLight};
+use crate::{
+    lang::Float,
+    math::{Normal3, Point3, Vector3},
+    properties::Color,
+    space::PointLight,
+};
 
-use super::{FlatPattern, Pattern, COLOR_BLACK};
+use super::{BumpMap, FlatPattern, Pattern, Subsurface, COLOR_BLACK};
+
+// Scalar patterns, driving the respective material values over the surface; the pattern intensity (see
+// Color#intensity()) multiplies the value, so that the scalar acts as the maximum (e.g. reflective 1.0
+// with a checkers map gives alternating mirror and matte tiles).
+//
+// The shininess is an exponent, so it's interpolated from 1 (the dullest highlight) to the value,
+// rather than multiplied; a zero exponent would make the whole lit side maximally specular.
+//
+#[derive(Debug, Default)]
+pub struct MaterialMaps {
+    pub ambient: Option<Box<dyn Pattern>>,
+    pub diffuse: Option<Box<dyn Pattern>>,
+    pub specular: Option<Box<dyn Pattern>>,
+    pub shininess: Option<Box<dyn Pattern>>,
+    pub reflective: Option<Box<dyn Pattern>>,
+    pub transparency: Option<Box<dyn Pattern>>,
+}
+
+// Material values at a given point, with the pattern and the maps applied.
+//
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceProperties {
+    pub color: Color,
+    pub ambient: Float,
+    pub diffuse: Float,
+    pub specular: Float,
+    pub shininess: Float,
+    pub reflective: Float,
+    pub transparency: Float,
+}
 
 #[derive(Debug)]
 pub struct Material {
     pub pattern: Box<dyn Pattern>,
-    pub ambient: f64,
-    pub diffuse: f64,
-    pub specular: f64,
-    pub shininess: f64,
-    pub reflective: f64,
-    pub transparency: f64,
-    pub refractive_index: f64,
+    pub ambient: Float,
+    pub diffuse: Float,
+    pub specular: Float,
+    pub shininess: Float,
+    pub reflective: Float,
+    pub transparency: Float,
+    pub refractive_index: Float,
+    // Perturbs the shading normal; see Ray#intersection_state().
+    //
+    pub bump_map: Option<BumpMap>,
+    pub maps: MaterialMaps,
+    // Makes the material translucent; see Subsurface.
+    //
+    pub subsurface: Option<Subsurface>,
 }
 
 impl Default for Material {
@@ -25,30 +67,89 @@ impl Default for Material {
             reflective: 0.0,
             transparency: 0.0,
             refractive_index: 1.0,
+            bump_map: None,
+            maps: MaterialMaps::default(),
+            subsurface: None,
         }
     }
 }
 
 impl Material {
+    // object_point: In object space, like the material patterns.
+    //
+    pub fn surface_at(&self, object_point: &Point3) -> SurfaceProperties {
+        let intensity_at = |map: &Option<Box<dyn Pattern>>| {
+            map.as_ref()
+                .map(|map| map.transformed_color_at(object_point).intensity())
+        };
+        let value_at = |value: Float, map: &Option<Box<dyn Pattern>>| match intensity_at(map) {
+            Some(intensity) => value * intensity,
+            None => value,
+        };
+        let shininess = match intensity_at(&self.maps.shininess) {
+            Some(intensity) => 1.0 + (self.shininess - 1.0) * intensity,
+            None => self.shininess,
+        };
+
+        SurfaceProperties {
+            color: self.pattern.transformed_color_at(object_point),
+            ambient: value_at(self.ambient, &self.maps.ambient),
+            diffuse: value_at(self.diffuse, &self.maps.diffuse),
+            specular: value_at(self.specular, &self.maps.specular),
+            shininess,
+            reflective: value_at(self.reflective, &self.maps.reflective),
+            transparency: value_at(self.transparency, &self.maps.transparency),
+        }
+    }
+
     pub fn lighting(
         &self,
         light: &PointLight,
-        object_point: &Tuple,
-        world_point: &Tuple,
-        eyev: &Tuple,
-        normalv: &Tuple,
+        object_point: &Point3,
+        world_point: &Point3,
+        eyev: &Vector3,
+        normalv: &Normal3,
         in_shadow: bool,
     ) -> Color {
-        let point = self.pattern.transform().inverse() * object_point;
-        let color = self.pattern.color_at(&point);
+        let surface = self.surface_at(object_point);
+
+        // The outside medium is not known here, so it's assumed to be the vacuum.
+        //
+        self.surface_lighting(
+            light,
+            &surface,
+            world_point,
+            eyev,
+            normalv,
+            self.refractive_index,
+            in_shadow,
+        )
+    }
 
-        let effective_color = color * &light.intensity;
+    // Same as `lighting()`, with the surface properties already computed (see
+    // IntersectionState#surface).
+    //
+    // relative_index: Refractive index of the material, relative to the medium on the other side of the
+    // surface; see Subsurface#diffuse_reflectance().
+    //
+    #[allow(clippy::too_many_arguments)]
+    pub fn surface_lighting(
+        &self,
+        light: &PointLight,
+        surface: &SurfaceProperties,
+        world_point: &Point3,
+        eyev: &Vector3,
+        normalv: &Normal3,
+        relative_index: Float,
+        in_shadow: bool,
+    ) -> Color {
+        let effective_color = surface.color * &light.intensity;
 
         let lightv = (light.position - world_point).normalize();
 
-        let ambient = effective_color * self.ambient;
+        let ambient = effective_color * surface.ambient;
 
-        let light_dot_normal = lightv.dot_product(&normalv);
+        let light_dot_normal = normalv.dot_product(&lightv);
 
         let (diffuse, specular) = if in_shadow || light_dot_normal < 0.0 {
             let diffuse = COLOR_BLACK;
@@ -56,17 +157,21 @@ impl Material {
 
             (diffuse, specular)
         } else {
-            let diffuse = effective_color * self.diffuse * light_dot_normal;
+            let mut diffuse = effective_color * surface.diffuse * light_dot_normal;
+
+            if let Some(subsurface) = &self.subsurface {
+                diffuse = diffuse * &subsurface.diffuse_reflectance(relative_index);
+            }
 
-            let reflectv = -lightv.reflect(&normalv);
-            let reflect_dot_eye = reflectv.dot_product(&eyev);
+            let reflectv = -lightv.reflect(normalv);
+            let reflect_dot_eye = reflectv.dot_product(eyev);
 
             let specular = if reflect_dot_eye <= 0.0 {
                 COLOR_BLACK
             } else {
-                let factor = reflect_dot_eye.powf(self.shininess);
+                let factor = reflect_dot_eye.powf(surface.shininess);
 
-                light.intensity * self.specular * factor
+                light.intensity * surface.specular * factor
             };
 
             (diffuse, specular)
@@ -74,4 +179,27 @@ impl Material {
 
         ambient + &diffuse + &specular
     }
+
+    // Light crossing the object and leaving at the point; it's zero for non-translucent materials.
+    //
+    // thickness: Distance crossed inside the object, from the point where the light entered.
+    // light_cos: Cosine between the normal and the light direction, at the point where the light entered.
+    //
+    pub fn translucency(
+        &self,
+        light: &PointLight,
+        surface: &SurfaceProperties,
+        thickness: Float,
+        light_cos: Float,
+    ) -> Color {
+        match &self.subsurface {
+            Some(subsurface) => {
+                surface.color
+                    * &light.intensity
+                    * &subsurface.transmittance(thickness)
+                    * (surface.diffuse * light_cos)
+            }
+            None => COLOR_BLACK,
+        }
+    }
 }
@@ -0,0 +1,59 @@
+use std::sync::{Arc, RwLock};
+
+use super::Material;
+
+// Shared material, assigned to the shapes; cloning the handle shares the same material (see
+// MaterialLibrary).
+//
+// The material is read while rendering, by concurrent threads, so an update can't modify it in place;
+// instead, it swaps the current material with the new one, which all the handles load from then on.
+// The readers hold the material they loaded (see load()), so a render in progress is not affected,
+// and the previous material is dropped once it's not loaded anymore.
+//
+#[derive(Clone, Debug, Default)]
+pub struct MaterialHandle(Arc<RwLock<Arc<Material>>>);
+
+impl MaterialHandle {
+    pub fn new(material: Material) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(material))))
+    }
+
+    // The current material; the lock is held only for the time of cloning the Arc.
+    //
+    pub fn load(&self) -> Arc<Material> {
+        Arc::clone(&self.0.read().unwrap())
+    }
+
+    // Replaces the material, for all the holders of the handle.
+    //
+    pub fn update(&self, material: Material) {
+        *self.0.write().unwrap() = Arc::new(material);
+    }
+
+    // Returns None if the handle is shared, or if the material is loaded; in the former case, use
+    // `update()`.
+    //
+    pub fn get_mut(&mut self) -> Option<&mut Material> {
+        let material = Arc::get_mut(&mut self.0)?.get_mut().unwrap();
+
+        Arc::get_mut(material)
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl From<Material> for MaterialHandle {
+    fn from(material: Material) -> Self {
+        Self::new(material)
+    }
+}
+
+// Allows assigning a material to a shape (whose material is optional) via `material.into()`.
+//
+impl From<Material> for Option<MaterialHandle> {
+    fn from(material: Material) -> Self {
+        Some(MaterialHandle::new(material))
+    }
+}
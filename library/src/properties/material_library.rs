@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use super::{Material, MaterialHandle};
+
+// Registry of named, shared materials; the shapes reference the entries via the handles, so that any
+// number of shapes (e.g. the triangles of a large model) use a single material instance.
+//
+// Since all the shapes share the same instance, editing an entry (see update()) applies to every user.
+//
+#[derive(Debug, Default)]
+pub struct MaterialLibrary {
+    materials: HashMap<String, MaterialHandle>,
+}
+
+impl MaterialLibrary {
+    // Adds (or replaces) the entry, and returns its handle. Replacing doesn't affect the shapes that
+    // already hold the previous handle; use `update()` for that.
+    //
+    pub fn insert(&mut self, name: &str, material: Material) -> MaterialHandle {
+        let material = MaterialHandle::new(material);
+
+        self.materials.insert(name.to_string(), material.clone());
+
+        material
+    }
+
+    // A missing entry is considered a programming error.
+    //
+    pub fn get(&self, name: &str) -> MaterialHandle {
+        self.entry(name).clone()
+    }
+
+    // Replaces the material of the entry, for all the shapes holding it. A missing entry is considered a
+    // programming error.
+    //
+    pub fn update(&mut self, name: &str, material: Material) {
+        self.entry(name).update(material);
+    }
+
+    // Returns None if the entry is missing, or if it's shared or loaded (see MaterialHandle#get_mut()).
+    //
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Material> {
+        self.materials
+            .get_mut(name)
+            .and_then(MaterialHandle::get_mut)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.materials.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.materials.keys().map(String::as_str)
+    }
+
+    fn entry(&self, name: &str) -> &MaterialHandle {
+        self.materials
+            .get(name)
+            .unwrap_or_else(|| panic!("material not found: {}", name))
+    }
+}
@@ -0,0 +1,106 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "MaterialLibrary" {
+        use std::sync::Arc;
+        use crate::properties::*;
+        use crate::space::*;
+
+        before {
+            #[allow(unused_variables, unused_mut)]
+            let mut library = MaterialLibrary::default();
+        }
+
+        it "should share the same instance between the shapes" {
+            library.insert("glass", Material { transparency: 1.0, ..Material::default() });
+
+            let shapes = (0..3).map(|_| Sphere { material: Some(library.get("glass")), ..Sphere::default() }).collect::<Vec<_>>();
+
+            assert!(shapes[0].material.as_ref().unwrap().ptr_eq(shapes[2].material.as_ref().unwrap()));
+            assert_eq!(shapes[1].material().transparency, 1.0);
+        }
+
+        it "should allow editing an entry, while not shared" {
+            library.insert("glass", Material::default());
+
+            library.get_mut("glass").unwrap().transparency = 0.9;
+
+            let sphere = Sphere { material: Some(library.get("glass")), ..Sphere::default() };
+
+            assert_eq!(sphere.material().transparency, 0.9);
+            assert!(library.get_mut("glass").is_none());
+        }
+
+        it "should apply an edit of an entry to all the shapes holding it" {
+            library.insert("glass", Material::default());
+
+            let sphere = Sphere { material: Some(library.get("glass")), ..Sphere::default() };
+            let cube = Cube { material: Some(library.get("glass")), ..Cube::default() };
+
+            library.update("glass", Material { transparency: 0.9, ..Material::default() });
+
+            assert_eq!(sphere.material().transparency, 0.9);
+            assert_eq!(cube.material().transparency, 0.9);
+            assert_eq!(library.get("glass").load().transparency, 0.9);
+
+            library.update("glass", Material { transparency: 0.5, ..Material::default() });
+
+            assert_eq!(sphere.material().transparency, 0.5);
+            assert_eq!(cube.material().transparency, 0.5);
+        }
+
+        it "should keep a loaded material, and drop it once replaced and released" {
+            library.insert("glass", Material { transparency: 0.9, ..Material::default() });
+
+            let handle = library.get("glass");
+            let loaded_material = handle.load();
+
+            assert!(library.get_mut("glass").is_none());
+
+            library.update("glass", Material { transparency: 0.5, ..Material::default() });
+
+            assert_eq!(loaded_material.transparency, 0.9);
+            assert_eq!(handle.load().transparency, 0.5);
+            assert_eq!(Arc::strong_count(&loaded_material), 1);
+        }
+
+        it "should apply an edit of an entry to a committed world" {
+            library.insert("wall", Material::default());
+
+            let world = World {
+                objects: vec![Arc::new(Sphere { material: Some(library.get("wall")), ..Sphere::default() })],
+                light_source: PointLight::new((-10, 10, -10), (1, 1, 1)),
+                fog: None,
+                media: vec![],
+            };
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            world.commit();
+
+            let mut intersections = vec![];
+            let hit = world.intersections(&ray, &mut intersections).unwrap();
+
+            assert_eq!(hit.material().ambient, 0.1);
+
+            library.update("wall", Material { ambient: 0.5, ..Material::default() });
+            world.commit();
+
+            let hit = world.intersections(&ray, &mut intersections).unwrap();
+
+            assert_eq!(hit.material().ambient, 0.5);
+        }
+
+        it "should report the entries" {
+            library.insert("glass", Material::default());
+
+            assert!(library.contains("glass"));
+            assert!(!library.contains("metal"));
+            assert_eq!(library.names().collect::<Vec<_>>(), vec!["glass"]);
+        }
+
+        #[should_panic]
+        it "should not find a missing entry" {
+            let _ = library.get("metal");
+        }
+    }
+}
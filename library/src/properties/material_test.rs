@@ -16,12 +16,12 @@ demonstrate! {
                     #[allow(unused_variables, unused_mut)]
                     let mut material = Material::default();
                     #[allow(unused_variables)]
-                    let position = Tuple::point(0, 0, 0);
+                    let position = Point3::new(0, 0, 0);
                 }
 
                 it "should be computed when the eye is between the light and the surface" {
-                    let eyev = Tuple::vector(0, 0, -1);
-                    let normalv = Tuple::vector(0, 0, -1);
+                    let eyev = Vector3::new(0, 0, -1);
+                    let normalv = Normal3::new(0, 0, -1);
                     let light = PointLight::new((0, 0, -10), (1, 1, 1));
 
                     let actual_result  = material.lighting(&light, &position, &position, &eyev, &normalv, false);
@@ -31,8 +31,8 @@ demonstrate! {
                 }
 
                 it "should be computed when the eye is in the path of the reflection vector" {
-                    let eyev = Tuple::vector(0, -sqrt(2.0) / 2.0, -sqrt(2.0) / 2.0);
-                    let normalv = Tuple::vector(0, 0, -1);
+                    let eyev = Vector3::new(0, -sqrt(2.0) / 2.0, -sqrt(2.0) / 2.0);
+                    let normalv = Normal3::new(0, 0, -1);
                     let light = PointLight::new((0, 10, -10), (1, 1, 1));
 
                     let actual_result  = material.lighting(&light, &position, &position, &eyev, &normalv, false);
@@ -42,8 +42,8 @@ demonstrate! {
                 }
 
                 it "should be computed in the shadow" {
-                    let eyev = Tuple::vector(0, 0, -1);
-                    let normalv = Tuple::vector(0, 0, -1);
+                    let eyev = Vector3::new(0, 0, -1);
+                    let normalv = Normal3::new(0, 0, -1);
                     let light = PointLight::new((0, 10, -10), (1, 1, 1));
 
                     let actual_result  = material.lighting(&light, &position, &position, &eyev, &normalv, true);
@@ -61,21 +61,146 @@ demonstrate! {
                         ..Material::default()
                     };
 
-                    let eyev = Tuple::vector(0, 0, -1);
-                    let normalv = Tuple::vector(0, 0, -1);
+                    let eyev = Vector3::new(0, 0, -1);
+                    let normalv = Normal3::new(0, 0, -1);
                     let light = PointLight::new((0, 0, -10), (1, 1, 1));
 
-                    let position_c1 = Tuple::point(0.9, 0, 0);
+                    let position_c1 = Point3::new(0.9, 0, 0);
                     let actual_result_c1 = material.lighting(&light, &position_c1, &position_c1, &eyev, &normalv, false);
 
                     assert_eq!(actual_result_c1, COLOR_WHITE);
 
-                    let position_c2 = Tuple::point(1.1, 0, 0);
+                    let position_c2 = Point3::new(1.1, 0, 0);
                     let actual_result_c2 = material.lighting(&light, &position_c2, &position_c2, &eyev, &normalv, false);
 
                     assert_eq!(actual_result_c2, COLOR_BLACK);
                 }
             }
+
+            context "maps" {
+                it "should scale the values by the map intensity, in object space" {
+                    let material = Material {
+                        reflective: 0.8,
+                        transparency: 0.5,
+                        maps: MaterialMaps {
+                            reflective: Some(Box::new(StripePattern::default())),
+                            transparency: Some(Box::new(FlatPattern::new(0.2, 0.4, 0.6))),
+                            ..MaterialMaps::default()
+                        },
+                        ..Material::default()
+                    };
+
+                    let surface = material.surface_at(&Point3::new(0.5, 0, 0));
+
+                    assert_eq!(surface.reflective, 0.8);
+                    assert_float_absolute_eq!(surface.transparency, 0.2);
+                    assert_eq!(surface.diffuse, material.diffuse);
+
+                    assert_eq!(material.surface_at(&Point3::new(1.5, 0, 0)).reflective, 0.0);
+                }
+
+                it "should interpolate the shininess from 1 to the value, by the map intensity" {
+                    let material = Material {
+                        shininess: 201.0,
+                        maps: MaterialMaps {
+                            shininess: Some(Box::new(StripePattern { color_b: Color::new(0.5, 0.5, 0.5).into(), ..StripePattern::default() })),
+                            ..MaterialMaps::default()
+                        },
+                        ..Material::default()
+                    };
+
+                    assert_eq!(material.surface_at(&Point3::new(0.5, 0, 0)).shininess, 201.0);
+                    assert_eq!(material.surface_at(&Point3::new(1.5, 0, 0)).shininess, 101.0);
+                }
+
+                it "should keep a dark shininess map dull, rather than maximally specular" {
+                    let material = Material {
+                        maps: MaterialMaps {
+                            shininess: Some(Box::new(FlatPattern::new(0, 0, 0))),
+                            ..MaterialMaps::default()
+                        },
+                        ..Material::default()
+                    };
+
+                    let position = Point3::new(0, 0, 0);
+                    let eyev = Vector3::new(0, sqrt(2) / 2.0, -sqrt(2) / 2.0);
+                    let normalv = Normal3::new(0, 0, -1);
+                    let light = PointLight::new((0, 0, -10), (1, 1, 1));
+
+                    assert_eq!(material.surface_at(&position).shininess, 1.0);
+
+                    let actual_result = material.lighting(&light, &position, &position, &eyev, &normalv, false);
+
+                    // Ambient, diffuse and specular (0.9 * cos(45°)).
+                    //
+                    assert_eq!(actual_result, Color::new(1.63640, 1.63640, 1.63640));
+                }
+
+                it "should be applied by the lighting" {
+                    let material = Material {
+                        maps: MaterialMaps {
+                            specular: Some(Box::new(FlatPattern::new(0, 0, 0))),
+                            ..MaterialMaps::default()
+                        },
+                        ..Material::default()
+                    };
+
+                    let position = Point3::new(0, 0, 0);
+                    let eyev = Vector3::new(0, 0, -1);
+                    let normalv = Normal3::new(0, 0, -1);
+                    let light = PointLight::new((0, 0, -10), (1, 1, 1));
+
+                    let actual_result = material.lighting(&light, &position, &position, &eyev, &normalv, false);
+
+                    // Ambient and diffuse only.
+                    //
+                    assert_eq!(actual_result, Color::new(1.0, 1.0, 1.0));
+                }
+            }
+
+            context "subsurface" {
+                before {
+                    #[allow(unused_variables)]
+                    let position = Point3::new(0, 0, 0);
+                    #[allow(unused_variables)]
+                    let light = PointLight::new((0, 0, -10), (1, 1, 1));
+                }
+
+                it "should scale the diffuse component by the diffuse reflectance" {
+                    let material = Material {
+                        subsurface: Some(Subsurface::default()),
+                        ..Material::default()
+                    };
+
+                    let eyev = Vector3::new(0, 0, -1);
+                    let normalv = Normal3::new(0, 0, -1);
+
+                    let actual_result = material.lighting(&light, &position, &position, &eyev, &normalv, false);
+
+                    // Ambient, diffuse (0.9 * 0.40372) and specular.
+                    //
+                    assert_eq!(actual_result, Color::new(1.36335, 1.36335, 1.36335));
+                }
+
+                it "should compute the translucency" {
+                    let material = Material {
+                        subsurface: Some(Subsurface::default()),
+                        ..Material::default()
+                    };
+
+                    let actual_result = material.translucency(&light, &material.surface_at(&position), 0.0, 0.5);
+
+                    // Diffuse * albedo * cosine.
+                    //
+                    assert_eq!(actual_result, Color::new(0.40909, 0.40909, 0.40909));
+                }
+
+                it "should have no translucency, without subsurface" {
+                    let material = Material::default();
+
+                    assert_eq!(material.translucency(&light, &material.surface_at(&position), 0.0, 1.0), COLOR_BLACK);
+                }
+            }
         }
     }
 }
@@ -1,39 +1,91 @@
+mod blend_pattern;
+mod bump_map;
 mod checkers_pattern;
 mod color;
+mod color_ramp;
 mod colors;
 mod flat_pattern;
 mod gradient_pattern;
+mod mask_pattern;
 mod material;
+mod material_handle;
+mod material_library;
+mod noise_pattern;
 mod pattern;
+mod pattern_slot;
+mod perturbed_pattern;
+mod ramp_pattern;
 mod refractive_indexes;
 mod ring_pattern;
 mod stripe_pattern;
+mod subsurface;
 
+pub use blend_pattern::{BlendMode, BlendPattern};
+pub use bump_map::BumpMap;
 pub use checkers_pattern::CheckersPattern;
 pub use color::Color;
+pub use color_ramp::{ColorRamp, RampInterpolation};
 pub use colors::*;
 pub use flat_pattern::FlatPattern;
 pub use gradient_pattern::GradientPattern;
-pub use material::Material;
+pub use mask_pattern::MaskPattern;
+pub use material::{Material, MaterialMaps, SurfaceProperties};
+pub use material_handle::MaterialHandle;
+pub use material_library::MaterialLibrary;
+pub use noise_pattern::{Noise, NoisePattern};
 pub use pattern::Pattern;
+pub use pattern_slot::PatternSlot;
+pub use perturbed_pattern::PerturbedPattern;
+pub use ramp_pattern::{RampPattern, RampShape};
 pub use refractive_indexes::*;
 pub use ring_pattern::RingPattern;
 pub use stripe_pattern::StripePattern;
+pub use subsurface::Subsurface;
+
+#[cfg(test)]
+mod blend_pattern_test;
+
+#[cfg(test)]
+mod bump_map_test;
 
 #[cfg(test)]
 mod checkers_pattern_test;
 
+#[cfg(test)]
+mod color_ramp_test;
+
 #[cfg(test)]
 mod color_test;
 
 #[cfg(test)]
 mod gradient_pattern_test;
 
+#[cfg(test)]
+mod mask_pattern_test;
+
+#[cfg(test)]
+mod material_library_test;
+
 #[cfg(test)]
 mod material_test;
 
+#[cfg(test)]
+mod noise_pattern_test;
+
+#[cfg(test)]
+mod perturbed_pattern_test;
+
+#[cfg(test)]
+mod pattern_slot_test;
+
+#[cfg(test)]
+mod ramp_pattern_test;
+
 #[cfg(test)]
 mod ring_pattern_test;
 
 #[cfg(test)]
 mod stripe_pattern_test;
+
+#[cfg(test)]
+mod subsurface_test;
@@ -0,0 +1,85 @@
+use super::{Color, Pattern, PatternSlot, COLOR_BLACK, COLOR_WHITE};
+use crate::{
+    lang::Float,
+    math::{noise, Point3, Transform},
+};
+
+// Noise function sampled by the noise-based patterns; see math::noise.
+//
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Noise {
+    #[default]
+    Perlin,
+    Simplex,
+    Fbm {
+        octaves: u32,
+        lacunarity: Float,
+        gain: Float,
+    },
+    Turbulence {
+        octaves: u32,
+        lacunarity: Float,
+        gain: Float,
+    },
+    Worley,
+}
+
+impl Noise {
+    // Noise value at the point, mapped to [0, 1], regardless of the noise function range.
+    //
+    pub fn value_at(&self, point: &Point3) -> Float {
+        let value = match *self {
+            Noise::Perlin => (noise::perlin(point) + 1.0) / 2.0,
+            Noise::Simplex => (noise::simplex(point) + 1.0) / 2.0,
+            Noise::Fbm {
+                octaves,
+                lacunarity,
+                gain,
+            } => (noise::fbm(point, octaves, lacunarity, gain) + 1.0) / 2.0,
+            Noise::Turbulence {
+                octaves,
+                lacunarity,
+                gain,
+            } => noise::turbulence(point, octaves, lacunarity, gain),
+            Noise::Worley => noise::worley(point),
+        };
+
+        value.clamp(0.0, 1.0)
+    }
+}
+
+// Interpolates linearly between the colors, by the noise value (color_a at 0, color_b at 1).
+//
+// The noise frequency is set via the transform (e.g. a scaling of 0.25 makes the noise features 4 times
+// smaller).
+//
+#[derive(Debug, SmartDefault)]
+pub struct NoisePattern {
+    pub noise: Noise,
+    #[default(_code = "COLOR_BLACK.into()")]
+    pub color_a: PatternSlot,
+    #[default(_code = "COLOR_WHITE.into()")]
+    pub color_b: PatternSlot,
+    pub transform: Transform,
+    #[default(None)]
+    pub previous_pattern: Option<Box<dyn Pattern>>,
+}
+
+impl Pattern for NoisePattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn previous_pattern(&self) -> &Option<Box<dyn Pattern>> {
+        &self.previous_pattern
+    }
+
+    // point: In pattern space.
+    //
+    fn current_color_at(&self, point: &Point3) -> Color {
+        let color_a = self.color_a.color_at(point);
+        let color_b = self.color_b.color_at(point);
+
+        color_a + &((color_b - &color_a) * self.noise.value_at(point))
+    }
+}
@@ -0,0 +1,43 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "NoisePattern" {
+        use crate::math::*;
+        use crate::properties::*;
+
+        it "should interpolate between the colors, by the noise value" {
+            let pattern = NoisePattern {
+                color_a: Color::new(1, 0, 0).into(),
+                color_b: Color::new(0, 0, 1).into(),
+                ..NoisePattern::default()
+            };
+
+            // Perlin noise is zero on the lattice, which maps to the middle of the range.
+            //
+            assert_eq!(pattern.color_at(&Point3::new(1, 2, 3)), Color::new(0.5, 0, 0.5));
+
+            let point = Point3::new(1.3, 2.7, -0.4);
+            let fraction = (noise::perlin(&point) + 1.0) / 2.0;
+
+            assert_eq!(pattern.color_at(&point), Color::new(1.0 - fraction, 0, fraction));
+        }
+
+        it "should map all the noise functions to [0, 1]" {
+            let noises = [
+                Noise::Perlin,
+                Noise::Simplex,
+                Noise::Fbm { octaves: 4, lacunarity: 2.0, gain: 0.5 },
+                Noise::Turbulence { octaves: 4, lacunarity: 2.0, gain: 0.5 },
+                Noise::Worley,
+            ];
+
+            for noise in noises {
+                for i in 0..100 {
+                    let point = Point3::new(i as f64 * 0.37, i as f64 * -0.11, 0.5);
+
+                    assert!((0.0..=1.0).contains(&noise.value_at(&point)));
+                }
+            }
+        }
+    }
+}
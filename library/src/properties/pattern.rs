@@ -1,15 +1,19 @@
+use crate::lang::Float;
 use std::fmt;
 
 use super::Color;
-use crate::math::{Matrix, Tuple};
+use crate::math::{Point3, Transform};
 
 pub trait Pattern: fmt::Debug + Sync + Send {
-    fn transform(&self) -> &Matrix;
+    fn transform(&self) -> &Transform;
     fn previous_pattern(&self) -> &Option<Box<dyn Pattern>>;
 
     // point: In pattern space.
     //
-    fn color_at(&self, point: &crate::math::Tuple) -> Color {
+    // The previous patterns are averaged equally; for weights, blend modes or nesting, see BlendPattern,
+    // MaskPattern and PatternSlot.
+    //
+    fn color_at(&self, point: &Point3) -> Color {
         let mut summed_colors = self.current_color_at(point);
         let mut colors_count = 1;
 
@@ -21,10 +25,17 @@ pub trait Pattern: fmt::Debug + Sync + Send {
             current_pattern_opt = current_pattern.previous_pattern();
         }
 
-        summed_colors / colors_count as f64
+        summed_colors / colors_count as Float
     }
 
     // point: In pattern space. Watch out! Use Shape#color_at when dealing with world coordinates.
     //
-    fn current_color_at(&self, point: &Tuple) -> Color;
+    fn current_color_at(&self, point: &Point3) -> Color;
+
+    // point: In the space the pattern is placed in (e.g. object space, for the material patterns); the
+    // pattern transform is applied.
+    //
+    fn transformed_color_at(&self, point: &Point3) -> Color {
+        self.color_at(&(self.transform().inverse() * point))
+    }
 }
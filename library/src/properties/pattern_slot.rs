@@ -0,0 +1,43 @@
+use super::{Color, Pattern};
+use crate::math::Point3;
+
+// Color input of a pattern (e.g. the stripes colors), which can be either a plain color, or another
+// pattern, allowing composition (e.g. stripes of checkers).
+//
+// Sub-patterns are sampled in their own space, so that they can be transformed independently of the
+// containing pattern (e.g. smaller checkers inside the stripes).
+//
+#[derive(Debug)]
+pub enum PatternSlot {
+    Color(Color),
+    Pattern(Box<dyn Pattern>),
+}
+
+impl PatternSlot {
+    // point: In the space of the containing pattern.
+    //
+    pub fn color_at(&self, point: &Point3) -> Color {
+        match self {
+            PatternSlot::Color(color) => *color,
+            PatternSlot::Pattern(pattern) => pattern.transformed_color_at(point),
+        }
+    }
+}
+
+impl From<Color> for PatternSlot {
+    fn from(color: Color) -> Self {
+        PatternSlot::Color(color)
+    }
+}
+
+impl From<Box<dyn Pattern>> for PatternSlot {
+    fn from(pattern: Box<dyn Pattern>) -> Self {
+        PatternSlot::Pattern(pattern)
+    }
+}
+
+impl<P: Pattern + 'static> From<P> for PatternSlot {
+    fn from(pattern: P) -> Self {
+        PatternSlot::Pattern(Box::new(pattern))
+    }
+}
@@ -0,0 +1,42 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "PatternSlot" {
+        use crate::math::*;
+        use crate::properties::*;
+
+        it "should return a plain color everywhere" {
+            let slot: PatternSlot = COLOR_RED.into();
+
+            assert_eq!(slot.color_at(&Point3::new(0, 0, 0)), COLOR_RED);
+            assert_eq!(slot.color_at(&Point3::new(1.5, -3, 7)), COLOR_RED);
+        }
+
+        it "should sample a sub-pattern, in its own space" {
+            let slot: PatternSlot = StripePattern {
+                transform: Matrix4::scaling(0.5, 1.0, 1.0).into(),
+                ..StripePattern::default()
+            }.into();
+
+            assert_eq!(slot.color_at(&Point3::new(0.25, 0, 0)), COLOR_WHITE);
+            assert_eq!(slot.color_at(&Point3::new(0.75, 0, 0)), COLOR_BLACK);
+        }
+
+        it "should allow nesting patterns (stripes of checkers)" {
+            let pattern = StripePattern {
+                color_a: CheckersPattern {
+                    color_a: COLOR_RED.into(),
+                    color_b: COLOR_GREEN.into(),
+                    ..CheckersPattern::default()
+                }.into(),
+                color_b: COLOR_BLUE.into(),
+                ..StripePattern::default()
+            };
+
+            assert_eq!(pattern.color_at(&Point3::new(0.5, 0.5, 0.5)), COLOR_RED);
+            assert_eq!(pattern.color_at(&Point3::new(0.5, 1.5, 0.5)), COLOR_GREEN);
+            assert_eq!(pattern.color_at(&Point3::new(1.5, 0.5, 0.5)), COLOR_BLUE);
+            assert_eq!(pattern.color_at(&Point3::new(1.5, 1.5, 0.5)), COLOR_BLUE);
+        }
+    }
+}
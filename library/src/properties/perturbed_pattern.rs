@@ -0,0 +1,67 @@
+use super::{Color, FlatPattern, Noise, Pattern};
+use crate::{
+    lang::Float,
+    math::{Point3, Transform, Vector3},
+};
+
+// Jitters the point before passing it to the inner pattern, which makes regular patterns look natural;
+// for example, perturbed stripes become marble, and perturbed rings wood.
+//
+// Each coordinate is displaced by up to `scale`, in both directions, using a separate noise sample;
+// the samples are taken at distant points, so that the displacements are uncorrelated.
+//
+// Since the inner pattern is not passed through Material#lighting(), its transform is applied here,
+// after the perturbation.
+//
+#[derive(Debug, SmartDefault)]
+pub struct PerturbedPattern {
+    // The default is phony, for default purposes.
+    //
+    #[default(Box::new(FlatPattern::default()))]
+    pub pattern: Box<dyn Pattern>,
+    pub noise: Noise,
+    #[default(0.2)]
+    pub scale: Float,
+    pub transform: Transform,
+    #[default(None)]
+    pub previous_pattern: Option<Box<dyn Pattern>>,
+}
+
+// Arbitrary offsets, far enough from each other to sample unrelated noise areas.
+//
+const Y_SAMPLE_OFFSET: Float = 31.7;
+const Z_SAMPLE_OFFSET: Float = 67.3;
+
+impl PerturbedPattern {
+    fn displacement(&self, point: &Point3, sample_offset: Float) -> Float {
+        let sample_point = Point3 {
+            x: point.x + sample_offset,
+            y: point.y + sample_offset,
+            z: point.z + sample_offset,
+        };
+
+        (self.noise.value_at(&sample_point) * 2.0 - 1.0) * self.scale
+    }
+}
+
+impl Pattern for PerturbedPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn previous_pattern(&self) -> &Option<Box<dyn Pattern>> {
+        &self.previous_pattern
+    }
+
+    // point: In pattern space.
+    //
+    fn current_color_at(&self, point: &Point3) -> Color {
+        let displacement = Vector3 {
+            x: self.displacement(point, 0.0),
+            y: self.displacement(point, Y_SAMPLE_OFFSET),
+            z: self.displacement(point, Z_SAMPLE_OFFSET),
+        };
+
+        self.pattern.transformed_color_at(&(*point + &displacement))
+    }
+}
@@ -0,0 +1,57 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "PerturbedPattern" {
+        use crate::math::*;
+        use crate::properties::*;
+
+        it "should be the inner pattern, with zero scale" {
+            let pattern = PerturbedPattern {
+                pattern: Box::new(StripePattern::default()),
+                scale: 0.0,
+                ..PerturbedPattern::default()
+            };
+
+            assert_eq!(pattern.color_at(&Point3::new(0.5, 0, 0)), COLOR_WHITE);
+            assert_eq!(pattern.color_at(&Point3::new(1.5, 0, 0)), COLOR_BLACK);
+        }
+
+        it "should apply the inner pattern transform" {
+            let pattern = PerturbedPattern {
+                pattern: Box::new(StripePattern {
+                    transform: Matrix4::scaling(2, 1, 1).into(),
+                    ..StripePattern::default()
+                }),
+                scale: 0.0,
+                ..PerturbedPattern::default()
+            };
+
+            assert_eq!(pattern.color_at(&Point3::new(1.5, 0, 0)), COLOR_WHITE);
+        }
+
+        it "should displace the point within the scale" {
+            let pattern = PerturbedPattern {
+                pattern: Box::new(GradientPattern::default()),
+                ..PerturbedPattern::default()
+            };
+
+            // Away from the lattice, the displacement is not zero, so the colors differ.
+            //
+            let point = Point3::new(0.3, 0.6, 0.2);
+            assert_ne!(pattern.color_at(&point), GradientPattern::default().color_at(&point));
+
+            // Stripes with width 1, perturbed by at most 0.2, keep the color around their centers.
+            //
+            let pattern = PerturbedPattern {
+                pattern: Box::new(StripePattern::default()),
+                ..PerturbedPattern::default()
+            };
+
+            for i in 0..100 {
+                let point = Point3::new(0.5, i as f64 * 0.13, i as f64 * 0.07);
+
+                assert_eq!(pattern.color_at(&point), COLOR_WHITE);
+            }
+        }
+    }
+}
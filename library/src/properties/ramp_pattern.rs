@@ -0,0 +1,72 @@
+use super::{Color, ColorRamp, Pattern};
+use crate::{
+    lang::{math::sqrt, Float},
+    math::{Point3, Transform},
+};
+
+// Determines the ramp position of a point.
+//
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RampShape {
+    // Along x, like GradientPattern.
+    //
+    #[default]
+    Linear,
+    // Distance from the y axis, like RingPattern.
+    //
+    Radial,
+    // Distance from the origin.
+    //
+    Spherical,
+}
+
+impl RampShape {
+    fn position_at(&self, point: &Point3) -> Float {
+        match self {
+            RampShape::Linear => point.x,
+            RampShape::Radial => sqrt(point.x.powi(2) + point.z.powi(2)),
+            RampShape::Spherical => sqrt(point.x.powi(2) + point.y.powi(2) + point.z.powi(2)),
+        }
+    }
+}
+
+// Configurable gradient: the shape determines the position of each point, which is mapped to a color
+// by the ramp.
+//
+// When repeating, only the fractional part of the position is used, so the ramp (which should span
+// [0, 1]) repeats every unit, e.g. concentric bands for the radial shape.
+//
+#[derive(Debug, SmartDefault)]
+pub struct RampPattern {
+    pub ramp: ColorRamp,
+    pub shape: RampShape,
+    #[default(false)]
+    pub repeating: bool,
+    pub transform: Transform,
+    #[default(None)]
+    pub previous_pattern: Option<Box<dyn Pattern>>,
+}
+
+impl Pattern for RampPattern {
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn previous_pattern(&self) -> &Option<Box<dyn Pattern>> {
+        &self.previous_pattern
+    }
+
+    // point: In pattern space.
+    //
+    fn current_color_at(&self, point: &Point3) -> Color {
+        let position = self.shape.position_at(point);
+
+        let position = if self.repeating {
+            position - position.floor()
+        } else {
+            position
+        };
+
+        self.ramp.color_at(position)
+    }
+}
@@ -0,0 +1,41 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "RampPattern" {
+        use crate::math::*;
+        use crate::properties::*;
+
+        before {
+            #[allow(unused_variables)]
+            let ramp = ColorRamp::new(vec![(0.0, COLOR_BLACK), (1.0, COLOR_WHITE)], RampInterpolation::Linear);
+        }
+
+        it "should vary linearly along x, by default" {
+            let pattern = RampPattern { ramp, ..RampPattern::default() };
+
+            assert_eq!(pattern.color_at(&Point3::new(0.25, 5, -3)), Color::new(0.25, 0.25, 0.25));
+            assert_eq!(pattern.color_at(&Point3::new(0.75, 0, 0)), Color::new(0.75, 0.75, 0.75));
+            assert_eq!(pattern.color_at(&Point3::new(1.75, 0, 0)), COLOR_WHITE);
+        }
+
+        it "should vary by the distance from the y axis, when radial" {
+            let pattern = RampPattern { ramp, shape: RampShape::Radial, ..RampPattern::default() };
+
+            assert_eq!(pattern.color_at(&Point3::new(0.3, 7, 0.4)), Color::new(0.5, 0.5, 0.5));
+        }
+
+        it "should vary by the distance from the origin, when spherical" {
+            let pattern = RampPattern { ramp, shape: RampShape::Spherical, ..RampPattern::default() };
+
+            assert_eq!(pattern.color_at(&Point3::new(0.2, 0.4, 0.4)), Color::new(0.6, 0.6, 0.6));
+            assert_eq!(pattern.color_at(&Point3::new(0, 2, 0)), COLOR_WHITE);
+        }
+
+        it "should repeat every unit, when repeating" {
+            let pattern = RampPattern { ramp, repeating: true, ..RampPattern::default() };
+
+            assert_eq!(pattern.color_at(&Point3::new(1.25, 0, 0)), Color::new(0.25, 0.25, 0.25));
+            assert_eq!(pattern.color_at(&Point3::new(-0.25, 0, 0)), Color::new(0.75, 0.75, 0.75));
+        }
+    }
+}
@@ -1,4 +1,6 @@
-pub const REFRACTIVE_INDEX_DIAMOND: f64 = 2.417;
-pub const REFRACTIVE_INDEX_GLASS: f64 = 1.52;
-pub const REFRACTIVE_INDEX_VACUUM: f64 = 1.0;
-pub const REFRACTIVE_INDEX_WATER: f64 = 1.333;
+use crate::lang::Float;
+
+pub const REFRACTIVE_INDEX_DIAMOND: Float = 2.417;
+pub const REFRACTIVE_INDEX_GLASS: Float = 1.52;
+pub const REFRACTIVE_INDEX_VACUUM: Float = 1.0;
+pub const REFRACTIVE_INDEX_WATER: Float = 1.333;
@@ -1,20 +1,19 @@
-use super::{Color, Pattern, COLOR_BLACK, COLOR_WHITE};
-use crate::{lang::math::sqrt, lang::ApproximateFloat64Ops, math::Matrix};
+use super::{Color, Pattern, PatternSlot, COLOR_BLACK, COLOR_WHITE};
+use crate::{lang::math::sqrt, lang::ApproximateFloatOps, math::Transform};
 
 #[derive(Debug, SmartDefault)]
 pub struct RingPattern {
-    #[default(COLOR_WHITE)]
-    pub color_a: Color,
-    #[default(COLOR_BLACK)]
-    pub color_b: Color,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
+    #[default(_code = "COLOR_WHITE.into()")]
+    pub color_a: PatternSlot,
+    #[default(_code = "COLOR_BLACK.into()")]
+    pub color_b: PatternSlot,
+    pub transform: Transform,
     #[default(None)]
     pub previous_pattern: Option<Box<dyn Pattern>>,
 }
 
 impl Pattern for RingPattern {
-    fn transform(&self) -> &Matrix {
+    fn transform(&self) -> &Transform {
         &self.transform
     }
 
@@ -24,15 +23,15 @@ impl Pattern for RingPattern {
 
     // point: In pattern space.
     //
-    fn current_color_at(&self, point: &crate::math::Tuple) -> Color {
+    fn current_color_at(&self, point: &crate::math::Point3) -> Color {
         let approximated_root_floor = sqrt(point.x.powi(2) + point.z.powi(2))
             .approximate()
             .floor();
 
         if approximated_root_floor as u32 % 2 == 0 {
-            self.color_a
+            self.color_a.color_at(point)
         } else {
-            self.color_b
+            self.color_b.color_at(point)
         }
     }
 }
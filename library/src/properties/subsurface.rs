@@ -0,0 +1,103 @@
+use super::Color;
+use crate::lang::Float;
+
+// Translucency (subsurface scattering) parameters, for materials like skin, wax and marble.
+//
+// The model is the diffusion approximation (Jensen et al., 2001): the light entering the object is
+// considered to be diffusing, rather than following its (random) walk, which gives closed forms for the
+// light leaving the surface. Two terms are computed:
+//
+// - the light entering and leaving the lit side; it replaces the diffuse albedo of the material (see
+//   diffuse_reflectance());
+// - the light crossing the object, from the side facing the light to the shaded point; it's attenuated
+//   based on the thickness crossed (see transmittance() and World#translucent_color()).
+//
+// The coefficients are per channel, and in inverse world units; e.g. an absorption of 0.5 attenuates the
+// light to 1/e every 2 units.
+//
+#[derive(Clone, Copy, Debug, SmartDefault)]
+pub struct Subsurface {
+    // Reduced scattering coefficient (σs').
+    //
+    #[default(Color::new(1.0, 1.0, 1.0))]
+    pub scattering: Color,
+    // Absorption coefficient (σa).
+    //
+    #[default(Color::new(0.1, 0.1, 0.1))]
+    pub absorption: Color,
+}
+
+impl Subsurface {
+    // Reduced albedo (σs' / σt'): the fraction of the light that is scattered, rather than absorbed, at
+    // each interaction.
+    //
+    pub fn albedo(&self) -> Color {
+        self.per_channel(|scattering, absorption| {
+            let extinction = scattering + absorption;
+
+            if extinction > 0.0 {
+                scattering / extinction
+            } else {
+                0.0
+            }
+        })
+    }
+
+    // Effective transport coefficient (σtr = sqrt(3 σa σt')); it's the rate of the (exponential) decay
+    // of the diffused light with the distance.
+    //
+    pub fn effective_transport(&self) -> Color {
+        self.per_channel(|scattering, absorption| {
+            (3.0 * absorption * (absorption + scattering)).sqrt()
+        })
+    }
+
+    // Total diffuse reflectance (Rd) of a semi-infinite medium, from the dipole approximation.
+    //
+    // relative_index: Refractive index of the medium, relative to the outside one; it determines the
+    // internal reflections at the boundary.
+    //
+    pub fn diffuse_reflectance(&self, relative_index: Float) -> Color {
+        let eta = relative_index;
+
+        // Average diffuse Fresnel reflectance (empirical fit), and the derived boundary condition.
+        //
+        let fresnel_diffuse = -1.44 / eta.powi(2) + 0.71 / eta + 0.668 + 0.0636 * eta;
+        let boundary = (1.0 + fresnel_diffuse) / (1.0 - fresnel_diffuse);
+
+        let albedo = self.albedo();
+
+        let reflectance = |albedo: Float| {
+            let root = (3.0 * (1.0 - albedo)).sqrt();
+
+            albedo / 2.0 * (1.0 + (-4.0 / 3.0 * boundary * root).exp()) * (-root).exp()
+        };
+
+        Color {
+            r: reflectance(albedo.r),
+            g: reflectance(albedo.g),
+            b: reflectance(albedo.b),
+        }
+    }
+
+    // Fraction of the light crossing the given thickness; only the scattered light contributes.
+    //
+    pub fn transmittance(&self, thickness: Float) -> Color {
+        let albedo = self.albedo();
+        let effective_transport = self.effective_transport();
+
+        Color {
+            r: albedo.r * (-effective_transport.r * thickness).exp(),
+            g: albedo.g * (-effective_transport.g * thickness).exp(),
+            b: albedo.b * (-effective_transport.b * thickness).exp(),
+        }
+    }
+
+    fn per_channel(&self, f: impl Fn(Float, Float) -> Float) -> Color {
+        Color {
+            r: f(self.scattering.r, self.absorption.r),
+            g: f(self.scattering.g, self.absorption.g),
+            b: f(self.scattering.b, self.absorption.b),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Subsurface" {
+        use crate::properties::*;
+
+        it "should compute the albedo and the effective transport per channel" {
+            let subsurface = Subsurface {
+                scattering: Color::new(1, 3, 0),
+                absorption: Color::new(1, 1, 0),
+            };
+
+            assert_eq!(subsurface.albedo(), Color::new(0.5, 0.75, 0));
+            assert_eq!(subsurface.effective_transport(), Color::new(2.44949, 3.46410, 0));
+        }
+
+        it "should reflect all the light, without absorption" {
+            let subsurface = Subsurface {
+                absorption: Color::new(0, 0, 0),
+                ..Subsurface::default()
+            };
+
+            assert_eq!(subsurface.diffuse_reflectance(REFRACTIVE_INDEX_GLASS), Color::new(1, 1, 1));
+        }
+
+        it "should reflect less light, with more absorption" {
+            let subsurface = Subsurface {
+                absorption: Color::new(0.1, 0.5, 2.0),
+                ..Subsurface::default()
+            };
+
+            let reflectance = subsurface.diffuse_reflectance(REFRACTIVE_INDEX_WATER);
+
+            assert!(reflectance.r < 1.0);
+            assert!(reflectance.g < reflectance.r);
+            assert!(reflectance.b < reflectance.g);
+        }
+
+        it "should attenuate the transmitted light exponentially with the thickness" {
+            let subsurface = Subsurface::default();
+
+            assert_eq!(subsurface.transmittance(0.0), subsurface.albedo());
+            assert_eq!(subsurface.transmittance(2.0), Color::new(0.28814, 0.28814, 0.28814));
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use super::{
+    shape::{self, private::ShapeLocal},
+    BoundedShape, Bounds, Intersection, MeshFace, Ray, ShapeParent, TextureCoordinates,
+    TriangleMesh, WorldTransformsCache,
+};
+use crate::{
+    lang::Float,
+    math::{Normal3, Point3, Transform, Vector3},
+    properties::MaterialHandle,
+};
+
+// Maximum distance between the tessellated triangles and the actual surface, in object space.
+//
+const DEFAULT_TESSELLATION_TOLERANCE: Float = 1e-3;
+
+// Upper limit to the segments per direction, in order to avoid pathological patches blowing up memory.
+//
+const MAX_SEGMENTS: usize = 64;
+
+// Bicubic Bezier patch.
+//
+// The patch is tessellated on construction into an internal mesh; the resolution is chosen adaptively,
+// based on the curvature of the control net (see `segments()`), and the normals are computed analytically
+// at the vertices, so the mesh is smooth.
+// The (u, v) patch parameters are stored as mesh texture coordinates.
+//
+// Since the tessellation depends on the control points, these are not public, and the patch must be
+// created via `new()`.
+//
+#[derive(Debug, ShapeAccessors, SmartDefault)]
+pub struct BezierPatch {
+    #[default(_code = "shape::new_shape_id()")]
+    pub id: u32,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
+
+    // Row-major: control_points[v][u].
+    //
+    #[default([[Point3::origin(); 4]; 4])]
+    control_points: [[Point3; 4]; 4],
+    mesh: TriangleMesh,
+}
+
+impl BezierPatch {
+    pub fn new(control_points: [[Point3; 4]; 4]) -> Self {
+        Self::with_tolerance(control_points, DEFAULT_TESSELLATION_TOLERANCE)
+    }
+
+    pub fn with_tolerance(control_points: [[Point3; 4]; 4], tolerance: Float) -> Self {
+        let mut patch = BezierPatch {
+            control_points,
+            ..BezierPatch::default()
+        };
+
+        patch.mesh = patch.tessellate(tolerance);
+
+        patch
+    }
+
+    pub fn control_points(&self) -> &[[Point3; 4]; 4] {
+        &self.control_points
+    }
+
+    pub fn mesh(&self) -> &TriangleMesh {
+        &self.mesh
+    }
+
+    // In object space.
+    //
+    pub fn point_at(&self, u: Float, v: Float) -> Point3 {
+        let (bu, bv) = (Self::bernstein(u), Self::bernstein(v));
+
+        Point3::origin() + &self.weighted_sum(&bu, &bv)
+    }
+
+    // In object space. Degenerate points (e.g. corners collapsed to a single point) are handled by
+    // sampling the normal slightly inside the patch.
+    //
+    pub fn normal_at(&self, u: Float, v: Float) -> Normal3 {
+        const DEGENERATE_OFFSET: Float = 1e-3;
+
+        let normal = self.unnormalized_normal_at(u, v);
+
+        if normal.magnitude() > Float::EPSILON {
+            return normal.normalize().into();
+        }
+
+        let inner_u = u + DEGENERATE_OFFSET * (0.5 - u).signum();
+        let inner_v = v + DEGENERATE_OFFSET * (0.5 - v).signum();
+
+        self.unnormalized_normal_at(inner_u, inner_v)
+            .normalize()
+            .into()
+    }
+
+    fn unnormalized_normal_at(&self, u: Float, v: Float) -> Vector3 {
+        let (bu, bv) = (Self::bernstein(u), Self::bernstein(v));
+        let (dbu, dbv) = (Self::bernstein_derivative(u), Self::bernstein_derivative(v));
+
+        let du = self.weighted_sum(&dbu, &bv);
+        let dv = self.weighted_sum(&bu, &dbv);
+
+        dv.cross_product(&du)
+    }
+
+    // The control points are summed as vectors from the origin. The Bernstein weights sum to 1 (or to 0,
+    // for the derivatives), so the result is the position of a point (or a vector).
+    //
+    fn weighted_sum(&self, u_weights: &[Float; 4], v_weights: &[Float; 4]) -> Vector3 {
+        let mut result = Vector3::new(0, 0, 0);
+
+        for (row, v_weight) in self.control_points.iter().zip(v_weights.iter()) {
+            for (point, u_weight) in row.iter().zip(u_weights.iter()) {
+                result = result + &(point.to_vector() * (u_weight * v_weight));
+            }
+        }
+
+        result
+    }
+
+    fn bernstein(t: Float) -> [Float; 4] {
+        let s = 1.0 - t;
+
+        [s * s * s, 3.0 * t * s * s, 3.0 * t * t * s, t * t * t]
+    }
+
+    fn bernstein_derivative(t: Float) -> [Float; 4] {
+        let s = 1.0 - t;
+
+        [
+            -3.0 * s * s,
+            3.0 * s * s - 6.0 * t * s,
+            6.0 * t * s - 3.0 * t * t,
+            3.0 * t * t,
+        ]
+    }
+
+    // Number of uniform segments per direction required to stay within the tolerance.
+    //
+    // For a Bezier curve of degree d, subdivided into n uniform segments, the distance between the
+    // curve and the polyline is at most d(d-1)/8 * M / n², where M is the maximum magnitude of the
+    // second differences of the control points. For the patch, the errors of the two directions are
+    // summed, which gives 1.5 * M / n².
+    // Using the same segments for the whole patch avoids cracks between the triangles.
+    //
+    fn segments(&self, tolerance: Float) -> usize {
+        let mut max_second_difference: Float = 0.0;
+
+        for i in 0..4 {
+            for j in 0..2 {
+                let u_difference = (self.control_points[i][j] - &self.control_points[i][j + 1])
+                    - &(self.control_points[i][j + 1] - &self.control_points[i][j + 2]);
+                let v_difference = (self.control_points[j][i] - &self.control_points[j + 1][i])
+                    - &(self.control_points[j + 1][i] - &self.control_points[j + 2][i]);
+
+                max_second_difference = max_second_difference
+                    .max(u_difference.magnitude())
+                    .max(v_difference.magnitude());
+            }
+        }
+
+        let segments = (1.5 * max_second_difference / tolerance).sqrt().ceil() as usize;
+
+        segments.clamp(1, MAX_SEGMENTS)
+    }
+
+    fn tessellate(&self, tolerance: Float) -> TriangleMesh {
+        let segments = self.segments(tolerance);
+        let row_length = segments + 1;
+
+        let mut vertices = Vec::with_capacity(row_length.pow(2));
+        let mut normals = Vec::with_capacity(row_length.pow(2));
+        let mut uvs = Vec::with_capacity(row_length.pow(2));
+
+        for vi in 0..=segments {
+            for ui in 0..=segments {
+                let (u, v) = (
+                    ui as Float / segments as Float,
+                    vi as Float / segments as Float,
+                );
+
+                vertices.push(self.point_at(u, v));
+                normals.push(self.normal_at(u, v));
+                uvs.push((u, v));
+            }
+        }
+
+        let mut faces = Vec::with_capacity(2 * segments.pow(2));
+
+        for vi in 0..segments {
+            for ui in 0..segments {
+                let p1i = (vi * row_length + ui) as u32;
+                let p2i = p1i + 1;
+                let p3i = p2i + row_length as u32;
+                let p4i = p1i + row_length as u32;
+
+                for indexes in &[[p1i, p2i, p3i], [p1i, p3i, p4i]] {
+                    faces.push(MeshFace {
+                        vertices: *indexes,
+                        normals: Some(*indexes),
+                        uvs: Some(*indexes),
+                    });
+                }
+            }
+        }
+
+        TriangleMesh::new(vertices, normals, uvs, faces)
+    }
+}
+
+impl ShapeLocal for BezierPatch {
+    fn local_normal(&self, point: Point3, intersection: &Intersection) -> Normal3 {
+        self.mesh.local_normal(point, intersection)
+    }
+
+    fn texture_coordinates(&self, intersection: &Intersection) -> Option<TextureCoordinates> {
+        self.mesh.texture_coordinates(intersection)
+    }
+
+    // ray: In object space.
+    //
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        let start = intersections.len();
+
+        self.mesh.local_intersections_into(ray, intersections);
+
+        for intersection in intersections[start..].iter_mut() {
+            intersection.object = self;
+        }
+    }
+}
+
+impl BoundedShape for BezierPatch {
+    // The patch lies within the convex hull of the control points, but the mesh bounds are tighter.
+    //
+    fn local_bounds(&self) -> Bounds {
+        self.mesh.local_bounds()
+    }
+}
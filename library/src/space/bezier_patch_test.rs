@@ -0,0 +1,120 @@
+use demonstrate::demonstrate;
+
+use crate::math::Point3;
+
+// Control net on a regular grid; `height` returns the z of each control point.
+//
+pub fn control_points(height: fn(usize, usize) -> f64) -> [[Point3; 4]; 4] {
+    let mut control_points = [[Point3::new(0, 0, 0); 4]; 4];
+
+    for (v, row) in control_points.iter_mut().enumerate() {
+        for (u, point) in row.iter_mut().enumerate() {
+            *point = Point3::new(u as f64, v as f64, height(u, v));
+        }
+    }
+
+    control_points
+}
+
+demonstrate! {
+    describe "BezierPatch" {
+        use super::control_points;
+        use crate::lang::ApproximateFloatOps;
+        use crate::math::*;
+        use crate::space::{*, shape::private::ShapeLocal};
+
+        it "A flat patch is tessellated into two triangles" {
+            let patch = BezierPatch::new(control_points(|_, _| 0.0));
+
+            assert_eq!(patch.mesh().faces().len(), 2);
+        }
+
+        it "A curved patch is tessellated more finely when the tolerance is lower" {
+            let height = |u, v| if (u == 1 || u == 2) && (v == 1 || v == 2) { 2.0 } else { 0.0 };
+
+            let coarse_patch = BezierPatch::with_tolerance(control_points(height), 0.1);
+            let fine_patch = BezierPatch::with_tolerance(control_points(height), 0.001);
+
+            assert!(coarse_patch.mesh().faces().len() > 2);
+            assert!(fine_patch.mesh().faces().len() > coarse_patch.mesh().faces().len());
+        }
+
+        it "The patch interpolates the corner control points" {
+            let patch = BezierPatch::new(control_points(|u, v| (u * v) as f64));
+
+            assert_eq!(patch.point_at(0.0, 0.0), Point3::new(0, 0, 0));
+            assert_eq!(patch.point_at(1.0, 0.0), Point3::new(3, 0, 0));
+            assert_eq!(patch.point_at(0.0, 1.0), Point3::new(0, 3, 0));
+            assert_eq!(patch.point_at(1.0, 1.0), Point3::new(3, 3, 9));
+        }
+
+        it "The normal of a flat patch is perpendicular to it" {
+            let patch = BezierPatch::new(control_points(|_, _| 0.0));
+
+            assert_eq!(patch.normal_at(0.3, 0.6), Normal3::new(0, 0, -1));
+        }
+
+        it "A degenerate corner has a valid normal" {
+            let mut points = control_points(|_, _| 0.0);
+
+            // Collapse the first row into a single point.
+            //
+            for point in points[0].iter_mut() {
+                *point = Point3::new(1.5, 0, 0);
+            }
+
+            let patch = BezierPatch::new(points);
+
+            assert!(Vector3::from(patch.normal_at(0.0, 0.0)).magnitude().approximate_equals(1.0));
+        }
+
+        it "A ray strikes the patch within the tolerance" {
+            let height = |u, v| if (u == 1 || u == 2) && (v == 1 || v == 2) { 2.0 } else { 0.0 };
+            let patch = BezierPatch::new(control_points(height));
+            let ray = Ray::new((1.3, 1.7, 10.0), (0, 0, -1));
+
+            let intersections = patch.local_intersections(&ray);
+
+            // The control points are evenly spaced on x/y, so (u, v) = (x / 3, y / 3).
+            //
+            let expected_point = patch.point_at(1.3 / 3.0, 1.7 / 3.0);
+
+            assert_eq!(intersections.len(), 1);
+            assert_eq!(intersections[0].object.id(), patch.id());
+            assert!((ray.position(intersections[0].t).z - expected_point.z).abs() < 1e-3);
+        }
+
+        it "The normal is interpolated from the analytic ones" {
+            let height = |u, v| if (u == 1 || u == 2) && (v == 1 || v == 2) { 2.0 } else { 0.0 };
+            let patch = BezierPatch::new(control_points(height));
+            let ray = Ray::new((1.3, 1.7, 10.0), (0, 0, -1));
+
+            let intersections = patch.local_intersections(&ray);
+            let actual_normal = patch.local_normal(ray.position(intersections[0].t), &intersections[0]);
+            let expected_normal = patch.normal_at(1.3 / 3.0, 1.7 / 3.0);
+
+            assert!(actual_normal.normalize().dot_product(&Vector3::from(expected_normal)) > 0.999);
+        }
+
+        it "The texture coordinates are the patch parameters" {
+            let patch = BezierPatch::new(control_points(|_, _| 0.0));
+            let ray = Ray::new((1.5, 0.6, 10.0), (0, 0, -1));
+
+            let intersections = patch.local_intersections(&ray);
+            let texture_coordinates = patch.texture_coordinates(&intersections[0]).unwrap();
+
+            assert_float_absolute_eq!(texture_coordinates.uv.0, 0.5);
+            assert_float_absolute_eq!(texture_coordinates.uv.1, 0.2);
+            assert_eq!(texture_coordinates.tangent, Vector3::new(3, 0, 0));
+            assert_eq!(texture_coordinates.bitangent, Vector3::new(0, 3, 0));
+        }
+
+        it "Computes the bounds from the tessellated surface" {
+            let patch = BezierPatch::new(control_points(|u, v| (u + v) as f64));
+            let bounds = patch.local_bounds();
+
+            assert_eq!(bounds.min, Point3::new(0, 0, 0));
+            assert_eq!(bounds.max, Point3::new(3, 3, 6));
+        }
+    }
+}
@@ -1,15 +1,16 @@
+use crate::lang::Float;
 use std::sync::Arc;
 
-use crate::math::Tuple;
+use crate::math::Point3;
 
-use super::Shape;
+use super::{Ray, Shape};
 
 #[derive(Copy, Clone, Debug, SmartDefault)]
 pub struct Bounds {
-    #[default(Tuple::point(f64::INFINITY, f64::INFINITY, f64::INFINITY))]
-    pub min: Tuple,
-    #[default(Tuple::point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY))]
-    pub max: Tuple,
+    #[default(Point3::new(Float::INFINITY, Float::INFINITY, Float::INFINITY))]
+    pub min: Point3,
+    #[default(Point3::new(Float::NEG_INFINITY, Float::NEG_INFINITY, Float::NEG_INFINITY))]
+    pub max: Point3,
 }
 
 impl Bounds {
@@ -45,7 +46,7 @@ impl Bounds {
     //
     // It's a PITA not to have overloading, and it's not worth to use the trait workaround.
     //
-    pub fn update_from_tuple(reference: &mut Bounds, other: &Tuple) {
+    pub fn update_from_point(reference: &mut Bounds, other: &Point3) {
         // Interestingly, using seemingly optimized logic like the following, is slower:
         //
         // if other.x < reference.min.x {
@@ -62,4 +63,34 @@ impl Bounds {
         reference.max.y = reference.max.y.max(other.y);
         reference.max.z = reference.max.z.max(other.z);
     }
+
+    // Slab test, without creating the intersections; the box is considered intersected also when
+    // it's behind the ray origin, consistently with the Shape#intersections_into() contract.
+    //
+    pub fn intersected_by(&self, ray: &Ray) -> bool {
+        self.intersection_range(ray).is_some()
+    }
+
+    // Same as above, returning the (tmin, tmax) range of the ray inside the box.
+    //
+    pub fn intersection_range(&self, ray: &Ray) -> Option<(Float, Float)> {
+        let mut tmin = Float::NEG_INFINITY;
+        let mut tmax = Float::INFINITY;
+
+        for axis in 0..3 {
+            let inverse_direction = 1.0 / ray.direction[axis];
+
+            let t1 = (self.min[axis] - ray.origin[axis]) * inverse_direction;
+            let t2 = (self.max[axis] - ray.origin[axis]) * inverse_direction;
+
+            tmin = tmin.max(t1.min(t2));
+            tmax = tmax.min(t1.max(t2));
+
+            if tmin > tmax {
+                return None;
+            }
+        }
+
+        Some((tmin, tmax))
+    }
 }
@@ -3,7 +3,8 @@ use std::sync::Mutex;
 use super::{Ray, World};
 use crate::{
     interface::Image,
-    math::{Matrix, Tuple},
+    lang::Float,
+    math::{Matrix4, Point3, Transform},
     properties::COLOR_BLACK,
 };
 
@@ -14,33 +15,34 @@ const MAX_REFLECTIONS: u8 = 5;
 pub struct Camera {
     pub hsize: u16,
     pub vsize: u16,
-    pub half_width: f64,
-    pub half_height: f64,
-    pub field_of_view: f64,
-    pub transform: Matrix,
-    pub pixel_size: f64,
+    pub half_width: Float,
+    pub half_height: Float,
+    pub field_of_view: Float,
+    pub transform: Transform,
+    pub pixel_size: Float,
 }
 
 impl Camera {
-    pub fn new(hsize: u16, vsize: u16, field_of_view: f64) -> Self {
+    pub fn new<T: Into<f64>>(hsize: u16, vsize: u16, field_of_view: T) -> Self {
+        let field_of_view = field_of_view.into() as Float;
         let view_units = (field_of_view / 2.0).tan() * 2.0;
-        let max_dimension = hsize.max(vsize) as f64;
+        let max_dimension = hsize.max(vsize) as Float;
 
         let pixel_size = view_units / max_dimension;
 
-        let half_width = hsize as f64 * pixel_size / 2.0;
-        let half_height = vsize as f64 * pixel_size / 2.0;
+        let half_width = hsize as Float * pixel_size / 2.0;
+        let half_height = vsize as Float * pixel_size / 2.0;
 
         // Original formula
         //
         // let half_view = (field_of_view / 2.0).tan();
-        // let aspect = hsize as f64 / vsize as f64;
+        // let aspect = hsize as Float / vsize as Float;
         // let (half_width, half_height) = if aspect >= 1.0 {
         //     (half_view, half_view / aspect)
         // } else {
         //     (half_view * aspect, half_view)
         // };
-        // let pixel_size = (half_width * 2.0) / hsize as f64;
+        // let pixel_size = (half_width * 2.0) / hsize as Float;
 
         Camera {
             hsize,
@@ -48,7 +50,7 @@ impl Camera {
             half_width,
             half_height,
             field_of_view,
-            transform: Matrix::identity(4),
+            transform: Matrix4::identity().into(),
             pixel_size,
         }
     }
@@ -56,8 +58,8 @@ impl Camera {
     pub fn ray_for_pixel(&self, px: u16, py: u16) -> Ray {
         // Offset from the canvas edge to the pixel's center
         //
-        let x_offset = (px as f64 + 0.5) * self.pixel_size;
-        let y_offset = (py as f64 + 0.5) * self.pixel_size;
+        let x_offset = (px as Float + 0.5) * self.pixel_size;
+        let y_offset = (py as Float + 0.5) * self.pixel_size;
 
         let world_x = self.half_width - x_offset;
         let world_y = self.half_height - y_offset;
@@ -66,27 +68,33 @@ impl Camera {
 
         // The canvas's z is -1!!
         //
-        let pixel = &camera_inverse_transform * &Tuple::point(world_x, world_y, -1);
-        let origin = &camera_inverse_transform * &Tuple::point(0, 0, 0);
+        let pixel = camera_inverse_transform * &Point3::new(world_x, world_y, -1);
+        let origin = camera_inverse_transform * &Point3::new(0, 0, 0);
 
         let direction = (pixel - &origin).normalize();
 
-        Ray { origin, direction }
+        Ray::unbounded(origin, direction)
     }
 
     pub fn render<T: Image>(&self, world: &World) -> T {
+        world.commit();
+
         let mut pixels_buffer = vec![vec![COLOR_BLACK; self.hsize as usize]; self.vsize as usize];
         let pixels_buffer_mtx = Mutex::new(&mut pixels_buffer);
 
-        (0..self.vsize).into_par_iter().for_each(|y| {
-            for x in 0..self.hsize {
-                let ray = self.ray_for_pixel(x, y);
-                let color = world.color_at(&ray, MAX_REFLECTIONS);
-
-                let mut pixels_buffer = pixels_buffer_mtx.lock().unwrap();
-                pixels_buffer[y as usize][x as usize] = color;
-            }
-        });
+        // Each worker reuses its own intersections buffer; see World#intersections().
+        //
+        (0..self.vsize)
+            .into_par_iter()
+            .for_each_init(Vec::new, |intersections, y| {
+                for x in 0..self.hsize {
+                    let ray = self.ray_for_pixel(x, y);
+                    let color = world.color_at(&ray, MAX_REFLECTIONS, intersections);
+
+                    let mut pixels_buffer = pixels_buffer_mtx.lock().unwrap();
+                    pixels_buffer[y as usize][x as usize] = color;
+                }
+            });
 
         T::from_pixels(pixels_buffer, self.hsize, self.vsize)
     }
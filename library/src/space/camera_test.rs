@@ -8,7 +8,7 @@ demonstrate! {
         use crate::lang::math::sqrt;
         use crate::properties::*;
         use crate::space::*;
-        use std::f64::consts::PI;
+        use crate::lang::consts::PI;
 
         context "pixel size" {
             it "should be computed when width > height" {
@@ -38,8 +38,8 @@ demonstrate! {
 
                 let ray = camera.ray_for_pixel(100, 50);
 
-                assert_eq!(ray.origin, Tuple::point(0, 0, 0));
-                assert_eq!(ray.direction, Tuple::vector(0, 0, -1));
+                assert_eq!(ray.origin, Point3::new(0, 0, 0));
+                assert_eq!(ray.direction, Vector3::new(0, 0, -1));
             }
 
             it "through a corner of the canvas" {
@@ -47,18 +47,18 @@ demonstrate! {
 
                 let ray = camera.ray_for_pixel(0, 0);
 
-                assert_eq!(ray.origin, Tuple::point(0, 0, 0));
-                assert_eq!(ray.direction, Tuple::vector(0.66519, 0.33259, -0.66851));
+                assert_eq!(ray.origin, Point3::new(0, 0, 0));
+                assert_eq!(ray.direction, Vector3::new(0.66519, 0.33259, -0.66851));
             }
 
             it "when the camera is transformed" {
                 let mut camera = Camera::new(201, 101, PI / 2.0);
-                camera.transform = Matrix::rotation(Axis::Y, PI / 4.0) * &Matrix::translation(0, -2, 5);
+                camera.transform = (Matrix4::rotation(Axis::Y, PI / 4.0) * &Matrix4::translation(0, -2, 5)).into();
 
                 let ray = camera.ray_for_pixel(100, 50);
 
-                assert_eq!(ray.origin, Tuple::point(0, 2, -5));
-                assert_eq!(ray.direction, Tuple::vector(sqrt(2) / 2.0, 0, -sqrt(2) / 2.0));
+                assert_eq!(ray.origin, Point3::new(0, 2, -5));
+                assert_eq!(ray.direction, Vector3::new(sqrt(2) / 2.0, 0, -sqrt(2) / 2.0));
             }
         } // context "constructs a ray"
 
@@ -67,11 +67,11 @@ demonstrate! {
 
             let mut camera = Camera::new(11, 11, PI / 2.0);
 
-            camera.transform = Matrix::view_transform(
-                &Tuple::point(0, 0, -5),
-                &Tuple::point(0, 0, 0),
-                &Tuple::vector(0, 1, 0),
-            );
+            camera.transform = Matrix4::view_transform(
+                &Point3::new(0, 0, -5),
+                &Point3::new(0, 0, 0),
+                &Vector3::new(0, 1, 0),
+            ).into();
 
             let image: VirtualImage = camera.render(&world);
 
@@ -1,27 +1,30 @@
-use std::{mem, sync::Weak};
+use std::mem;
 
-use super::{shape, shape::private::ShapeLocal, BoundedShape, Bounds, Intersection, Ray, Shape};
+use super::{
+    shape, shape::private::ShapeLocal, BoundedShape, Bounds, Intersection, Ray, ShapeParent,
+    WorldTransformsCache,
+};
 use crate::{
-    lang::{math::sqrt, ApproximateFloat64Ops},
-    math::{Matrix, Tuple},
-    properties::Material,
+    lang::Float,
+    lang::{math::sqrt, ApproximateFloatOps},
+    math::{Normal3, Point3, Transform},
+    properties::MaterialHandle,
 };
 
 #[derive(Debug, ShapeAccessors, SmartDefault)]
 pub struct Cone {
     #[default(_code = "shape::new_shape_id()")]
     pub id: u32,
-    #[default(Weak::<Self>::new())]
-    pub parent: Weak<dyn Shape>,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
-    #[default(Material::default())]
-    pub material: Material,
-
-    #[default(f64::NEG_INFINITY)]
-    pub minimum: f64,
-    #[default(f64::INFINITY)]
-    pub maximum: f64,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
+
+    #[default(Float::NEG_INFINITY)]
+    pub minimum: Float,
+    #[default(Float::INFINITY)]
+    pub maximum: Float,
     pub closed: bool,
 }
 
@@ -42,6 +45,9 @@ impl Cone {
             intersections.push(Intersection {
                 t: t1,
                 uv: None,
+                face: None,
+                instance: None,
+                material: None,
                 object: self,
             });
         }
@@ -52,6 +58,9 @@ impl Cone {
             intersections.push(Intersection {
                 t: t2,
                 uv: None,
+                face: None,
+                instance: None,
+                material: None,
                 object: self,
             });
         }
@@ -59,26 +68,26 @@ impl Cone {
 
     // Check if the intersection at `t` is within a radius of 1 (the Cone radius) from the y axis.
     //
-    fn check_cap(ray: &Ray, t: f64, y: f64) -> bool {
+    fn check_cap(ray: &Ray, t: Float, y: Float) -> bool {
         let x = ray.origin.x + t * ray.direction.x;
         let z = ray.origin.z + t * ray.direction.z;
 
-        (x.powi(2) + z.powi(2)) <= y.abs()
+        (x.powi(2) + z.powi(2)).approximate_less_or_equal(y.abs())
     }
 }
 
 impl ShapeLocal for Cone {
     // point: In object space.
     //
-    fn local_normal(&self, point: Tuple, _intersection: &Intersection) -> Tuple {
+    fn local_normal(&self, point: Point3, _intersection: &Intersection) -> Normal3 {
         // Compute the square of the distance from the y axis.
         //
         let dist = point.x.powi(2) + point.z.powi(2);
 
         if dist < 1.0 && point.y.approximate_greater_or_equal(self.maximum) {
-            Tuple::vector(0, 1, 0)
+            Normal3::new(0, 1, 0)
         } else if dist < 1.0 && point.y.approximate_less_or_equal(self.minimum) {
-            Tuple::vector(0, -1, 0)
+            Normal3::new(0, -1, 0)
         } else {
             let mut y = sqrt(point.x.powi(2) + point.z.powi(2));
 
@@ -86,15 +95,17 @@ impl ShapeLocal for Cone {
                 y = -y;
             }
 
-            Tuple::vector(point.x, y, point.z)
+            Normal3::new(point.x, y, point.z)
         }
     }
 
     // ray: In object space.
     //
-    fn local_intersections(&self, ray: &super::Ray) -> Vec<Intersection> {
-        let mut intersections = Vec::with_capacity(4);
-
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &super::Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
         let a = ray.direction.x.powi(2) - ray.direction.y.powi(2) + ray.direction.z.powi(2);
 
         let b = 2.0 * ray.origin.x * ray.direction.x - 2.0 * ray.origin.y * ray.direction.y
@@ -106,13 +117,16 @@ impl ShapeLocal for Cone {
         //
         if a.approximate_equals(0.0) {
             if b.approximate_equals(0.0) {
-                return intersections;
+                return;
             } else {
                 let t = -c / (2.0 * b);
 
                 intersections.push(Intersection {
                     t,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object: self,
                 });
             }
@@ -120,9 +134,11 @@ impl ShapeLocal for Cone {
 
         let disc = b.powi(2) - 4.0 * a * c;
 
-        // Ray intersects the walls.
+        // Ray intersects the walls. Tangent rays can yield a slightly negative discriminant, due to
+        // rounding (especially with f32), so it's clamped.
         //
-        if disc >= 0.0 {
+        if disc.approximate_greater_or_equal(0.0) {
+            let disc = disc.max(0.0);
             let mut t0 = (-b - sqrt(disc)) / (2.0 * a);
             let mut t1 = (-b + sqrt(disc)) / (2.0 * a);
 
@@ -136,6 +152,9 @@ impl ShapeLocal for Cone {
                 intersections.push(Intersection {
                     t: t0,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object: self,
                 });
             }
@@ -146,22 +165,23 @@ impl ShapeLocal for Cone {
                 intersections.push(Intersection {
                     t: t1,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object: self,
                 });
             }
         }
 
-        self.intersect_caps(ray, &mut intersections);
-
-        intersections
+        self.intersect_caps(ray, intersections);
     }
 }
 
 impl BoundedShape for Cone {
     fn local_bounds(&self) -> Bounds {
         Bounds {
-            min: Tuple::point(-1, self.minimum, -1),
-            max: Tuple::point(1, self.maximum, 1),
+            min: Point3::new(-1, self.minimum, -1),
+            max: Point3::new(1, self.maximum, 1),
         }
     }
 }
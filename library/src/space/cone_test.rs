@@ -5,7 +5,7 @@ demonstrate! {
         use std::sync::Arc;
         use crate::math::*;
         use crate::lang::math::sqrt;
-        use crate::lang::ApproximateFloat64Ops;
+        use crate::lang::ApproximateFloatOps;
         use crate::space::{*, shape::private::ShapeLocal};
 
         before {
@@ -22,9 +22,9 @@ demonstrate! {
             ];
 
             for ((ox, oy, oz), (dx, dy, dz), t1, t2) in examples.iter() {
-                let origin    = Tuple::point(*ox, *oy, *oz);
-                let direction = Tuple::vector(*dx, *dy, *dz).normalize();
-                let ray = Ray { origin, direction };
+                let origin    = Point3::new(*ox, *oy, *oz);
+                let direction = Vector3::new(*dx, *dy, *dz).normalize();
+                let ray = Ray::unbounded(origin, direction);
 
                 let actual_intersections = &cone.local_intersections(&ray);
 
@@ -36,9 +36,9 @@ demonstrate! {
 
 
         it "Intersecting a cone with a ray parallel to one of its halves" {
-            let origin    = Tuple::point(0, 0, -1);
-            let direction = Tuple::vector(0, 1, 1).normalize();
-            let ray = Ray { origin, direction };
+            let origin    = Point3::new(0, 0, -1);
+            let direction = Vector3::new(0, 1, 1).normalize();
+            let ray = Ray::unbounded(origin, direction);
 
             let actual_intersections = cone.local_intersections(&ray);
 
@@ -63,9 +63,9 @@ demonstrate! {
 
 
             for ((ox, oy, oz), (dx, dy, dz), expected_count) in examples.iter() {
-                let origin    = Tuple::point(*ox, *oy, *oz);
-                let direction = Tuple::vector(*dx, *dy, *dz).normalize();
-                let ray = Ray { origin, direction };
+                let origin    = Point3::new(*ox, *oy, *oz);
+                let direction = Vector3::new(*dx, *dy, *dz).normalize();
+                let ray = Ray::unbounded(origin, direction);
 
                 let intersections = &cone.local_intersections(&ray);
 
@@ -85,10 +85,13 @@ demonstrate! {
                 let default_intersection = Intersection {
                     t: 0.0,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object: &Plane::default(),
                 };
-                let point = Tuple::point(*px, *py, *pz);
-                let expected_normal = Tuple::vector(*nx, *ny, *nz);
+                let point = Point3::new(*px, *py, *pz);
+                let expected_normal = Normal3::new(*nx, *ny, *nz);
 
                 assert_eq!(cone.local_normal(point, &default_intersection), expected_normal);
             }
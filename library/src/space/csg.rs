@@ -2,205 +2,375 @@ use std::sync::{Arc, Weak};
 
 use super::{
     shape::{self, private::ShapeLocal},
-    BoundedShape, Bounds, Intersection, Plane, Ray, Shape,
+    BoundedShape, Bounds, Intersection, Ray, Shape, ShapeParent, WorldTransformsCache,
+};
+use crate::{
+    lang::Float,
+    math::{Matrix4, Transform},
+    math::{Normal3, Point3},
+    properties::MaterialHandle,
 };
-use crate::{math::Matrix, math::Tuple, properties::Material};
-
-#[cfg(test)]
-use std::any::Any;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Operation {
     Difference,
     Intersection,
+    // Inside an odd number of children.
+    //
+    SymmetricDifference,
     Union,
 }
 
-#[derive(Clone, Copy)]
-pub enum ChildHit {
-    Left,
-    Right,
+// Material of the surfaces contributed by a child after the first (the base); in the typical case of a
+// difference, those are the faces cut by the cutters. It's chosen per child (see Csg#with_operands()).
+//
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CutMaterial {
+    // Each surface keeps the material of its own child.
+    //
+    Cutter,
+    // The surfaces take the material of the base, at the point where the ray is inside it.
+    //
+    Base,
+}
+
+// Inside state of the children (bit `i` for the child `i`), along with the number of children the ray is
+// inside of. The first 64 children are stored inline, which covers the typical CSGs without allocating
+// on each query; the state of the other ones is stored on the heap.
+//
+#[derive(Debug, Default)]
+pub(crate) struct ChildrenInside {
+    inline: u64,
+    overflow: Vec<u64>,
+    count: usize,
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+impl ChildrenInside {
+    pub(crate) fn new(children_count: usize) -> Self {
+        let overflow_children = children_count.saturating_sub(WORD_BITS);
+
+        ChildrenInside {
+            overflow: vec![0; overflow_children.div_ceil(WORD_BITS)],
+            ..ChildrenInside::default()
+        }
+    }
+
+    pub(crate) fn contains(&self, child: usize) -> bool {
+        let word = if child < WORD_BITS {
+            self.inline
+        } else {
+            self.overflow[child / WORD_BITS - 1]
+        };
+
+        word & Self::bit(child) != 0
+    }
+
+    pub(crate) fn toggle(&mut self, child: usize) {
+        if self.contains(child) {
+            self.count -= 1;
+        } else {
+            self.count += 1;
+        }
+
+        let word = if child < WORD_BITS {
+            &mut self.inline
+        } else {
+            &mut self.overflow[child / WORD_BITS - 1]
+        };
+
+        *word ^= Self::bit(child);
+    }
+
+    fn bit(child: usize) -> u64 {
+        1 << (child % WORD_BITS)
+    }
 }
 
-#[derive(Debug, SmartDefault)]
+// N-ary CSG; with two children, this is the classic binary one (left/right).
+//
+// The operations are generalized as follows:
+//
+// - Difference: inside the first child, and outside all the others;
+// - Intersection: inside all the children;
+// - SymmetricDifference: inside an odd number of children;
+// - Union: inside any child.
+//
+#[derive(Debug, ShapeAccessors, SmartDefault)]
+#[container]
 pub struct Csg {
     #[default(_code = "shape::new_shape_id()")]
     pub id: u32,
-    #[default(Weak::<Self>::new())]
-    pub parent: Weak<dyn Shape>,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    // When set, it's inherited by the descendants without a material (see
+    // Intersection#inherit_material()).
+    //
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
 
-    // The defaults below are phony, for default purposes.
+    // The default is phony, for default purposes.
     //
     #[default(Operation::Union)]
     pub operation: Operation,
+    // One per child; the base one is ignored.
+    //
+    #[default(vec![])]
+    pub cut_materials: Vec<CutMaterial>,
     // For ease, we follow the Group#children pattern, but this prevents modifications to the children.
-    // Structure: (left, right).
-    #[default((Arc::new(Plane::default()), Arc::new(Plane::default())))]
-    pub children: (Arc<dyn Shape>, Arc<dyn Shape>),
+    //
+    #[default(vec![])]
+    pub children: Vec<Arc<dyn Shape>>,
 }
 
 impl Csg {
-    // Sets the children's parent to the new Csg instance.
+    // Binary CSG; see `with_children()`.
     //
     pub fn new(
         operation: Operation,
-        mut left: Arc<dyn Shape>,
-        mut right: Arc<dyn Shape>,
-        transform: Matrix,
+        left: Arc<dyn Shape>,
+        right: Arc<dyn Shape>,
+        transform: Matrix4,
+    ) -> Arc<Csg> {
+        Self::with_children(operation, CutMaterial::Cutter, vec![left, right], transform)
+    }
+
+    // Sets the children's parent to the new Csg instance. The cut material applies to all the children;
+    // see `with_operands()` for choosing it per child.
+    //
+    pub fn with_children(
+        operation: Operation,
+        cut_material: CutMaterial,
+        children: Vec<Arc<dyn Shape>>,
+        transform: Matrix4,
     ) -> Arc<Csg> {
-        let mut csg = Arc::new(Csg {
+        let cut_materials = vec![cut_material; children.len()];
+
+        Self::build(operation, None, children, cut_materials, transform)
+    }
+
+    // Each child has its own cut material (the base one is ignored).
+    //
+    pub fn with_operands(
+        operation: Operation,
+        operands: Vec<(Arc<dyn Shape>, CutMaterial)>,
+        transform: Matrix4,
+    ) -> Arc<Csg> {
+        let (children, cut_materials) = operands.into_iter().unzip();
+
+        Self::build(operation, None, children, cut_materials, transform)
+    }
+
+    // See Group#with_material().
+    //
+    pub fn with_material(
+        operation: Operation,
+        cut_material: CutMaterial,
+        material: MaterialHandle,
+        children: Vec<Arc<dyn Shape>>,
+        transform: Matrix4,
+    ) -> Arc<Csg> {
+        let cut_materials = vec![cut_material; children.len()];
+
+        Self::build(
             operation,
+            Some(material),
+            children,
+            cut_materials,
             transform,
-            ..Csg::default()
-        });
-
-        // Children needs to be unchecked as well, otherwise shapes can't be nested.
+        )
+    }
 
-        let left_mut = unsafe { Arc::get_mut_unchecked(&mut left) };
-        let left_parent_ref = left_mut.parent_mut();
-        *left_parent_ref = Arc::downgrade(&(Arc::clone(&csg) as Arc<dyn Shape>));
+    fn build(
+        operation: Operation,
+        material: Option<MaterialHandle>,
+        children: Vec<Arc<dyn Shape>>,
+        cut_materials: Vec<CutMaterial>,
+        transform: Matrix4,
+    ) -> Arc<Csg> {
+        // See Group#new().
+        //
+        Arc::new_cyclic(|csg: &Weak<Csg>| {
+            for child in children.iter() {
+                child.set_parent(csg.clone());
+            }
 
-        let right_mut = unsafe { Arc::get_mut_unchecked(&mut right) };
-        let right_parent_ref = right_mut.parent_mut();
-        *right_parent_ref = Arc::downgrade(&(Arc::clone(&csg) as Arc<dyn Shape>));
+            Csg {
+                operation,
+                cut_materials,
+                transform: transform.into(),
+                material,
+                children,
+                ..Csg::default()
+            }
+        })
+    }
 
-        let csg_mut = unsafe { Arc::get_mut_unchecked(&mut csg) };
+    // An intersection is allowed when hitting the child changes the inside state of the CSG.
+    //
+    // inside: The children the ray is currently inside of.
+    //
+    pub(crate) fn intersection_allowed(&self, child_hit: usize, inside: &ChildrenInside) -> bool {
+        let (inside_base, inside_count) = (inside.contains(0), inside.count);
 
-        csg_mut.children = (left, right);
+        let (hit_inside_base, hit_inside_count) = if inside.contains(child_hit) {
+            (inside_base && child_hit != 0, inside_count - 1)
+        } else {
+            (inside_base || child_hit == 0, inside_count + 1)
+        };
 
-        csg
+        self.is_inside(inside_base, inside_count)
+            != self.is_inside(hit_inside_base, hit_inside_count)
     }
 
-    pub(crate) fn intersection_allowed(
-        &self,
-        child_hit: ChildHit,
-        inside_left: bool,
-        inside_right: bool,
-    ) -> bool {
+    // The operations depend only on the base, and on the number of children the ray is inside of.
+    //
+    fn is_inside(&self, inside_base: bool, inside_count: usize) -> bool {
         match self.operation {
-            Operation::Difference => match child_hit {
-                ChildHit::Left => !inside_right,
-                ChildHit::Right => inside_left,
-            },
-            Operation::Intersection => match child_hit {
-                ChildHit::Left => inside_right,
-                ChildHit::Right => inside_left,
-            },
-            Operation::Union => match child_hit {
-                ChildHit::Left => !inside_right,
-                ChildHit::Right => !inside_left,
-            },
+            Operation::Difference => inside_base && inside_count == 1,
+            Operation::Intersection => inside_count > 0 && inside_count == self.children.len(),
+            Operation::SymmetricDifference => inside_count % 2 == 1,
+            Operation::Union => inside_count > 0,
         }
     }
 
-    pub(crate) fn filter_intersections<'a>(
-        &self,
-        intersections: Vec<Intersection<'a>>,
-    ) -> Vec<Intersection<'a>> {
-        // begin outside of both children
+    fn child_hit(&self, intersection: &Intersection) -> usize {
+        self.children
+            .iter()
+            .position(|child| child.includes(intersection))
+            .expect("intersection not belonging to any child")
+    }
+
+    // Filters the (sorted) intersections in place, moving the allowed ones to the front; returns their
+    // number. Consecutive duplicates are skipped.
+    //
+    pub(crate) fn filter_intersections<'a>(&self, intersections: &mut [Intersection<'a>]) -> usize {
+        // begin outside of all the children
         //
-        let mut inside_left = false;
-        let mut inside_right = false;
+        let mut inside = ChildrenInside::new(self.children.len());
 
-        let mut result = Vec::with_capacity(intersections.len());
+        // The cut faces take the material of the closest base intersection before them (the base was
+        // entered there); if there is none, the ray started inside the base, so the first one is used.
+        //
+        let uses_base_material = self
+            .cut_materials
+            .iter()
+            .skip(1)
+            .any(|cut_material| *cut_material == CutMaterial::Base);
+
+        let mut base_material = if uses_base_material {
+            intersections
+                .iter()
+                .find(|intersection| self.child_hit(intersection) == 0)
+                .map(|intersection| intersection.material_handle())
+        } else {
+            None
+        };
 
-        let left_child = &self.children.0;
+        let mut previous = None;
+        let mut allowed_count = 0;
 
-        for intersection in intersections {
-            let child_hit = if left_child.includes(intersection.object) {
-                ChildHit::Left
-            } else {
-                ChildHit::Right
-            };
+        for i in 0..intersections.len() {
+            let intersection = &intersections[i];
+            let current = (
+                intersection.t,
+                intersection.object.id(),
+                intersection.instance.map(|instance| instance.id),
+            );
 
-            if self.intersection_allowed(child_hit, inside_left, inside_right) {
-                result.push(intersection);
+            if previous == Some(current) {
+                continue;
             }
 
-            // depending on which object was hit, toggle either inside_left or inside_right;
+            previous = Some(current);
 
-            match child_hit {
-                ChildHit::Left => inside_left = !inside_left,
-                ChildHit::Right => inside_right = !inside_right,
-            };
-        }
+            let child_hit = self.child_hit(&intersections[i]);
 
-        result
-    }
-}
-
-impl Shape for Csg {
-    fn id(&self) -> u32 {
-        self.id
-    }
+            if child_hit == 0 && uses_base_material {
+                base_material = Some(intersections[i].material_handle());
+            }
 
-    fn parent(&self) -> Option<Arc<dyn Shape>> {
-        Weak::upgrade(&self.parent)
-    }
+            if self.intersection_allowed(child_hit, &inside) {
+                if child_hit != 0 && self.cut_materials.get(child_hit) == Some(&CutMaterial::Base) {
+                    if let Some(base_material) = base_material {
+                        intersections[i].material = Some(base_material);
+                    }
+                }
 
-    fn parent_mut(&mut self) -> &mut Weak<dyn Shape> {
-        &mut self.parent
-    }
-
-    fn transform(&self) -> &Matrix {
-        &self.transform
-    }
+                intersections.swap(allowed_count, i);
+                allowed_count += 1;
+            }
 
-    fn transform_mut(&mut self) -> &mut Matrix {
-        &mut self.transform
-    }
+            inside.toggle(child_hit);
+        }
 
-    fn material(&self) -> &Material {
-        panic!()
+        allowed_count
     }
+}
 
-    fn material_mut(&mut self) -> &mut Material {
-        panic!()
+impl Shape for Csg {
+    fn includes(&self, intersection: &Intersection) -> bool {
+        self.children
+            .iter()
+            .any(|child| child.includes(intersection))
     }
 
-    fn includes(&self, object: &dyn Shape) -> bool {
-        self.children.0.includes(object) || self.children.1.includes(object)
-    }
+    fn commit(&self, parent_world_transform: Option<&Matrix4>) {
+        let world_transforms = self
+            .world_transforms
+            .update(self.transform.matrix(), parent_world_transform);
 
-    #[cfg(test)]
-    fn as_any(&self) -> &dyn Any {
-        self
+        for child in self.children.iter() {
+            child.commit(Some(&world_transforms.transform));
+        }
     }
 }
 
 impl ShapeLocal for Csg {
-    fn local_normal(&self, _point: Tuple, _intersection: &Intersection) -> Tuple {
+    fn local_normal(&self, _point: Point3, _intersection: &Intersection) -> Normal3 {
         panic!("local normal is not meaningful for Group")
     }
 
     // ray: In object space.
     //
-    fn local_intersections<'a>(&'a self, ray: &Ray) -> Vec<Intersection<'a>> {
-        // filter_intersections() locks the children, so we need to drop the mutex before then.
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        // The inside state is tracked from the start of the line, so the children are intersected
+        // without the ray interval, which is applied to the filtered intersections (see
+        // Shape#intersections_into()).
         //
-        let mut all_intersections = {
-            let (left_child, right_child) = &self.children;
+        let unbounded_ray = ray.with_interval(Float::NEG_INFINITY, Float::INFINITY);
+
+        let start = intersections.len();
 
-            let mut left_intersections = left_child.intersections(ray);
-            let right_intersections = right_child.intersections(ray);
+        for child in self.children.iter() {
+            child.intersections_into(&unbounded_ray, intersections);
+        }
 
-            left_intersections.extend(right_intersections);
+        // The material is inherited before filtering, so that the base material is the inherited one,
+        // if the base has none.
+        //
+        if let Some(material) = &self.material {
+            for intersection in intersections[start..].iter_mut() {
+                intersection.inherit_material(material);
+            }
+        }
 
-            left_intersections
-        };
+        Intersection::sort(&mut intersections[start..]);
 
-        all_intersections.sort();
-        all_intersections.dedup();
+        let allowed_count = self.filter_intersections(&mut intersections[start..]);
 
-        self.filter_intersections(all_intersections)
+        intersections.truncate(start + allowed_count);
     }
 }
 
 impl BoundedShape for Csg {
     fn local_bounds(&self) -> Bounds {
-        let children = vec![Arc::clone(&self.children.0), Arc::clone(&self.children.1)];
-        Bounds::compute_for_children(&children)
+        Bounds::compute_for_children(&self.children)
     }
 }
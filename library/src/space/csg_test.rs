@@ -4,6 +4,7 @@ demonstrate! {
     describe "Csg" {
         use std::sync::Arc;
         use crate::math::*;
+        use crate::properties::*;
         use crate::space::{*, shape::private::ShapeLocal, csg};
 
         // before {
@@ -17,48 +18,58 @@ demonstrate! {
                 csg::Operation::Union,
                 Arc::clone(&sphere1),
                 Arc::clone(&sphere2),
-                Matrix::identity(4)
+                Matrix4::identity()
             );
 
             assert_eq!(csg.operation, csg::Operation::Union);
-            assert_eq!(csg.children.0.id(), sphere1.id());
-            assert_eq!(csg.children.1.id(), sphere2.id());
+            assert_eq!(csg.children[0].id(), sphere1.id());
+            assert_eq!(csg.children[1].id(), sphere2.id());
             assert_eq!(sphere1.parent().unwrap().id(), csg.id());
             assert_eq!(sphere2.parent().unwrap().id(), csg.id());
         }
 
         it "Evaluating the rule for a CSG operation" {
             let examples = vec![
-                // op                          lhit                   inl     inr     result
-                (csg::Operation::Union,        csg::ChildHit::Left,   true,   true,   false),
-                (csg::Operation::Union,        csg::ChildHit::Left,   true,   false,  true),
-                (csg::Operation::Union,        csg::ChildHit::Left,   false,  true,   false),
-                (csg::Operation::Union,        csg::ChildHit::Left,   false,  false,  true),
-                (csg::Operation::Union,        csg::ChildHit::Right,  true,   true,   false),
-                (csg::Operation::Union,        csg::ChildHit::Right,  true,   false,  false),
-                (csg::Operation::Union,        csg::ChildHit::Right,  false,  true,   true),
-                (csg::Operation::Union,        csg::ChildHit::Right,  false,  false,  true),
-                (csg::Operation::Intersection, csg::ChildHit::Left,   true,   true,   true),
-                (csg::Operation::Intersection, csg::ChildHit::Left,   true,   false,  false),
-                (csg::Operation::Intersection, csg::ChildHit::Left,   false,  true,   true),
-                (csg::Operation::Intersection, csg::ChildHit::Left,   false,  false,  false),
-                (csg::Operation::Intersection, csg::ChildHit::Right,  true,   true,   true),
-                (csg::Operation::Intersection, csg::ChildHit::Right,  true,   false,  true),
-                (csg::Operation::Intersection, csg::ChildHit::Right,  false,  true,   false),
-                (csg::Operation::Intersection, csg::ChildHit::Right,  false,  false,  false),
-                (csg::Operation::Difference,   csg::ChildHit::Left,   true,   true,   false),
-                (csg::Operation::Difference,   csg::ChildHit::Left,   true,   false,  true),
-                (csg::Operation::Difference,   csg::ChildHit::Left,   false,  true,   false),
-                (csg::Operation::Difference,   csg::ChildHit::Left,   false,  false,  true),
-                (csg::Operation::Difference,   csg::ChildHit::Right,  true,   true,   true),
-                (csg::Operation::Difference,   csg::ChildHit::Right,  true,   false,  true),
-                (csg::Operation::Difference,   csg::ChildHit::Right,  false,  true,   false),
-                (csg::Operation::Difference,   csg::ChildHit::Right,  false,  false,  false),
+                // op                          hit inl     inr     result
+                (csg::Operation::Union,        0,  true,   true,   false),
+                (csg::Operation::Union,        0,  true,   false,  true),
+                (csg::Operation::Union,        0,  false,  true,   false),
+                (csg::Operation::Union,        0,  false,  false,  true),
+                (csg::Operation::Union,        1,  true,   true,   false),
+                (csg::Operation::Union,        1,  true,   false,  false),
+                (csg::Operation::Union,        1,  false,  true,   true),
+                (csg::Operation::Union,        1,  false,  false,  true),
+                (csg::Operation::Intersection, 0,  true,   true,   true),
+                (csg::Operation::Intersection, 0,  true,   false,  false),
+                (csg::Operation::Intersection, 0,  false,  true,   true),
+                (csg::Operation::Intersection, 0,  false,  false,  false),
+                (csg::Operation::Intersection, 1,  true,   true,   true),
+                (csg::Operation::Intersection, 1,  true,   false,  true),
+                (csg::Operation::Intersection, 1,  false,  true,   false),
+                (csg::Operation::Intersection, 1,  false,  false,  false),
+                (csg::Operation::Difference,   0,  true,   true,   false),
+                (csg::Operation::Difference,   0,  true,   false,  true),
+                (csg::Operation::Difference,   0,  false,  true,   false),
+                (csg::Operation::Difference,   0,  false,  false,  true),
+                (csg::Operation::Difference,   1,  true,   true,   true),
+                (csg::Operation::Difference,   1,  true,   false,  true),
+                (csg::Operation::Difference,   1,  false,  true,   false),
+                (csg::Operation::Difference,   1,  false,  false,  false),
             ];
 
             for (operation, child_hit, in_left, in_right, expected_result) in examples.into_iter() {
-                let csg = Arc::new(Csg { operation, ..Csg::default() });
-                let actual_result = csg.intersection_allowed(child_hit, in_left, in_right);
+                let children: Vec<Arc<dyn Shape>> = vec![Arc::new(Sphere::default()), Arc::new(Cube::default())];
+                let csg = Arc::new(Csg { operation, children, ..Csg::default() });
+                let mut inside = csg::ChildrenInside::new(2);
+
+                if in_left {
+                    inside.toggle(0);
+                }
+                if in_right {
+                    inside.toggle(1);
+                }
+
+                let actual_result = csg.intersection_allowed(child_hit, &inside);
 
                 assert_eq!(actual_result, expected_result);
             }
@@ -76,10 +87,10 @@ demonstrate! {
             let right: Arc<dyn Shape> = Arc::new(Cube::default());
 
             let intersections = vec![
-                Intersection { t: 1.0, uv: None, object: left.as_ref() },
-                Intersection { t: 2.0, uv: None, object: right.as_ref() },
-                Intersection { t: 3.0, uv: None, object: left.as_ref() },
-                Intersection { t: 4.0, uv: None, object: right.as_ref() },
+                Intersection { t: 1.0, uv: None, face: None, instance: None, material: None, object: left.as_ref() },
+                Intersection { t: 2.0, uv: None, face: None, instance: None, material: None, object: right.as_ref() },
+                Intersection { t: 3.0, uv: None, face: None, instance: None, material: None, object: left.as_ref() },
+                Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: right.as_ref() },
             ];
 
             for (operation, x0, x1) in examples.into_iter() {
@@ -87,12 +98,13 @@ demonstrate! {
                     operation,
                     Arc::clone(&left),
                     Arc::clone(&right),
-                    Matrix::identity(4)
+                    Matrix4::identity()
                 );
 
-                let result = csg.filter_intersections(intersections.clone());
+                let mut result = intersections.clone();
+                let allowed_count = csg.filter_intersections(&mut result);
 
-                assert_eq!(result.len(), 2);
+                assert_eq!(allowed_count, 2);
 
                 assert_eq!(result[0], intersections[x0]);
                 assert_eq!(result[1], intersections[x1]);
@@ -104,7 +116,7 @@ demonstrate! {
                 csg::Operation::Union,
                 Arc::new(Sphere::default()),
                 Arc::new(Cube::default()),
-                Matrix::identity(4),
+                Matrix4::identity(),
             );
 
             let ray = Ray::new((0, 2, -5), (0, 0, 1));
@@ -117,7 +129,7 @@ demonstrate! {
         it "A ray hits a CSG object" {
             let s1: Arc<dyn Shape> = Arc::new(Sphere::default());
             let s2: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(0.0, 0.0, 0.5),
+                transform: Matrix4::translation(0.0, 0.0, 0.5).into(),
                 ..Sphere::default()
             });
 
@@ -125,7 +137,7 @@ demonstrate! {
                 csg::Operation::Union,
                 Arc::clone(&s1),
                 Arc::clone(&s2),
-                Matrix::identity(4),
+                Matrix4::identity(),
             );
 
             let ray = Ray::new((0, 0, -5), (0, 0, 1));
@@ -138,5 +150,282 @@ demonstrate! {
             assert_eq!(intersections[1].t, 6.5);
             assert_eq!(intersections[1].object, s2.as_ref());
         }
+
+        it "Evaluating the rule for a symmetric difference" {
+            let examples = vec![
+                // hit inl     inr     result
+                (0,  true,   true,   true),
+                (0,  true,   false,  true),
+                (0,  false,  true,   true),
+                (0,  false,  false,  true),
+                (1,  true,   true,   true),
+                (1,  false,  false,  true),
+            ];
+
+            for (child_hit, in_left, in_right, expected_result) in examples.into_iter() {
+                let children: Vec<Arc<dyn Shape>> = vec![Arc::new(Sphere::default()), Arc::new(Cube::default())];
+                let csg = Csg { operation: csg::Operation::SymmetricDifference, children, ..Csg::default() };
+                let mut inside = csg::ChildrenInside::new(2);
+
+                if in_left {
+                    inside.toggle(0);
+                }
+                if in_right {
+                    inside.toggle(1);
+                }
+
+                let actual_result = csg.intersection_allowed(child_hit, &inside);
+
+                assert_eq!(actual_result, expected_result);
+            }
+        }
+
+        it "Evaluating the rule for an n-ary CSG operation" {
+            let examples = vec![
+                // op                          hit inside                  result
+                (csg::Operation::Union,        2,  vec![true, false, false],  false),
+                (csg::Operation::Union,        2,  vec![false, false, false], true),
+                (csg::Operation::Intersection, 0,  vec![false, true, true],   true),
+                (csg::Operation::Intersection, 0,  vec![false, true, false],  false),
+                (csg::Operation::Difference,   2,  vec![true, false, false],  true),
+                (csg::Operation::Difference,   2,  vec![true, true, false],   false),
+                (csg::Operation::Difference,   0,  vec![false, false, true],  false),
+            ];
+
+            for (operation, child_hit, inside, expected_result) in examples.into_iter() {
+                let children = inside.iter().map(|_| Arc::new(Sphere::default()) as Arc<dyn Shape>).collect();
+                let csg = Csg { operation, children, ..Csg::default() };
+                let mut inside_state = csg::ChildrenInside::new(inside.len());
+
+                for (child, _) in inside.iter().enumerate().filter(|(_, inside)| **inside) {
+                    inside_state.toggle(child);
+                }
+
+                let actual_result = csg.intersection_allowed(child_hit, &inside_state);
+
+                assert_eq!(actual_result, expected_result);
+            }
+        }
+
+        it "A ray hits an n-ary CSG object with more children than the inline state" {
+            let spheres = (0..100)
+                .map(|i| Arc::new(Sphere {
+                    transform: Matrix4::translation(0, 0, i).into(),
+                    ..Sphere::default()
+                }) as Arc<dyn Shape>)
+                .collect();
+            let csg = Csg::with_children(csg::Operation::Union, csg::CutMaterial::Cutter, spheres, Matrix4::identity());
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let ts = csg.intersections(&ray).iter().map(|intersection| intersection.t).collect::<Vec<_>>();
+
+            assert_eq!(ts, vec![4.0, 105.0]);
+
+            let mut inside = csg::ChildrenInside::new(100);
+
+            inside.toggle(0);
+            inside.toggle(99);
+
+            assert!(inside.contains(99));
+            assert!(!inside.contains(64));
+            assert!(!csg.intersection_allowed(64, &inside));
+        }
+
+        it "A ray hits an n-ary CSG object" {
+            let spheres = (0..3)
+                .map(|i| Arc::new(Sphere {
+                    transform: Matrix4::translation(0, 0, i).into(),
+                    ..Sphere::default()
+                }) as Arc<dyn Shape>)
+                .collect::<Vec<_>>();
+
+            let csg = Csg::with_children(csg::Operation::Union, csg::CutMaterial::Cutter, spheres.clone(), Matrix4::identity());
+
+            for sphere in spheres.iter() {
+                assert_eq!(sphere.parent().unwrap().id(), csg.id());
+            }
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = csg.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, 4.0);
+            assert_eq!(intersections[0].object, spheres[0].as_ref());
+            assert_eq!(intersections[1].t, 8.0);
+            assert_eq!(intersections[1].object, spheres[2].as_ref());
+
+            let csg = Csg::with_children(csg::Operation::Difference, csg::CutMaterial::Cutter, spheres.clone(), Matrix4::identity());
+
+            let intersections = csg.local_intersections(&ray);
+
+            // The base is cut by the second sphere only.
+            //
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, 4.0);
+            assert_eq!(intersections[1].t, 5.0);
+            assert_eq!(intersections[1].object, spheres[1].as_ref());
+        }
+
+        it "A ray hits a symmetric difference" {
+            let s1: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let s2: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0.0, 0.0, 0.5).into(),
+                ..Sphere::default()
+            });
+
+            let csg = Csg::new(csg::Operation::SymmetricDifference, s1, s2, Matrix4::identity());
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = csg.local_intersections(&ray);
+            let ts = intersections.iter().map(|intersection| intersection.t).collect::<Vec<_>>();
+
+            assert_eq!(ts, vec![4.0, 4.5, 6.0, 6.5]);
+        }
+
+        it "Cut faces take the base material, if requested" {
+            let examples = vec![
+                // cut material              ambient
+                (csg::CutMaterial::Cutter,  Material::default().ambient),
+                (csg::CutMaterial::Base,    0.5),
+            ];
+
+            for (cut_material, expected_ambient) in examples.into_iter() {
+                let base: Arc<dyn Shape> = Arc::new(Sphere {
+                    material: Material {
+                        ambient: 0.5,
+                        ..Material::default()
+                    }.into(),
+                    ..Sphere::default()
+                });
+                let cutter: Arc<dyn Shape> = Arc::new(Sphere {
+                    transform: Matrix4::translation(0.0, 0.0, -0.5).into(),
+                    ..Sphere::default()
+                });
+
+                let csg = Csg::with_children(csg::Operation::Difference, cut_material, vec![base, cutter], Matrix4::identity());
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+                let intersections = csg.local_intersections(&ray);
+
+                // Cut face, then base surface.
+                //
+                assert_eq!(intersections[0].t, 5.5);
+                assert_eq!(intersections[0].material().ambient, expected_ambient);
+                assert_eq!(intersections[1].t, 6.0);
+                assert_eq!(intersections[1].material().ambient, 0.5);
+            }
+        }
+
+        it "Each cutter can keep its own material, or take the base one" {
+            let base: Arc<dyn Shape> = Arc::new(Sphere {
+                material: Material { ambient: 0.5, ..Material::default() }.into(),
+                ..Sphere::default()
+            });
+            let front_cutter: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0.0, 0.0, -1.5).into(),
+                material: Material { ambient: 0.3, ..Material::default() }.into(),
+                ..Sphere::default()
+            });
+            let back_cutter: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0.0, 0.0, 1.5).into(),
+                material: Material { ambient: 0.2, ..Material::default() }.into(),
+                ..Sphere::default()
+            });
+
+            let csg = Csg::with_operands(
+                csg::Operation::Difference,
+                vec![
+                    (base, csg::CutMaterial::Cutter),
+                    (front_cutter, csg::CutMaterial::Cutter),
+                    (back_cutter, csg::CutMaterial::Base),
+                ],
+                Matrix4::identity(),
+            );
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = csg.local_intersections(&ray);
+            let ts_ambients = intersections.iter().map(|intersection| (intersection.t, intersection.material().ambient)).collect::<Vec<_>>();
+
+            // Front cut face (own material), then back cut face (base material).
+            //
+            assert_eq!(ts_ambients, vec![(4.5, 0.3), (5.5, 0.5)]);
+        }
+
+        it "Children without a material inherit the CSG one, also as base material" {
+            let base: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let cutter: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0.0, 0.0, -0.5).into(),
+                material: Material { ambient: 0.3, ..Material::default() }.into(),
+                ..Sphere::default()
+            });
+
+            let csg = Csg::with_material(
+                csg::Operation::Difference,
+                csg::CutMaterial::Base,
+                MaterialHandle::new(Material { ambient: 0.5, ..Material::default() }),
+                vec![base, cutter],
+                Matrix4::identity(),
+            );
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = csg.local_intersections(&ray);
+
+            assert_eq!(intersections[0].material().ambient, 0.5);
+            assert_eq!(intersections[1].material().ambient, 0.5);
+        }
+
+        it "A ray starting inside a CSG object is bounded after the filtering" {
+            let s1: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let s2: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0.0, 0.0, 0.5).into(),
+                ..Sphere::default()
+            });
+
+            let csg = Csg::new(
+                csg::Operation::Union,
+                Arc::clone(&s1),
+                Arc::clone(&s2),
+                Matrix4::identity(),
+            );
+
+            // Starting inside s1 (and s2); the s1 exit is inside s2, so it must be filtered out, which
+            // requires the intersections behind the origin.
+            //
+            let ray = Ray::new((0, 0, 0), (0, 0, 1)).with_interval(0.0, 10.0);
+
+            let intersections = csg.intersections(&ray);
+
+            assert_eq!(intersections.len(), 1);
+            assert_eq!(intersections[0].t, 1.5);
+            assert_eq!(intersections[0].object, s2.as_ref());
+        }
+
+        it "A CSG object filters only the intersections it appends to the buffer" {
+            let s1: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let s2: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0.0, 0.0, 0.5).into(),
+                ..Sphere::default()
+            });
+
+            let csg = Csg::new(
+                csg::Operation::Union,
+                Arc::clone(&s1),
+                Arc::clone(&s2),
+                Matrix4::identity(),
+            );
+
+            let other = Sphere::default();
+            let mut intersections = vec![Intersection { t: 100.0, uv: None, face: None, instance: None, material: None, object: &other }];
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            csg.intersections_into(&ray, &mut intersections);
+
+            assert_eq!(intersections.len(), 3);
+            assert_eq!(intersections[0].t, 100.0);
+            assert_eq!(intersections[1].t, 4.0);
+            assert_eq!(intersections[2].t, 6.5);
+        }
     }
 }
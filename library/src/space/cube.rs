@@ -1,32 +1,34 @@
-use std::sync::Weak;
-
 use super::{
     shape::{self, private::ShapeLocal},
-    BoundedShape, Bounds, Intersection, Ray, Shape,
+    BoundedShape, Bounds, Intersection, Ray, Shape, ShapeParent, WorldTransformsCache,
+};
+use crate::{
+    lang::Float,
+    math::{Normal3, Point3, Transform},
+    properties::MaterialHandle,
 };
-use crate::{math::Matrix, math::Tuple, properties::Material};
 
 #[derive(Debug, ShapeAccessors, SmartDefault)]
 pub struct Cube {
     #[default(_code = "shape::new_shape_id()")]
     pub id: u32,
-    #[default(Weak::<Self>::new())]
-    pub parent: Weak<dyn Shape>,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
-    #[default(Material::default())]
-    pub material: Material,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
 }
 
 impl Cube {
     // Passing the object as parameter rather than modeling this as associated method, it allows to
     // use this logic on any Shape.
     //
-    pub fn generalized_intersections<'a>(
+    pub fn generalized_intersections_into<'a>(
         object: &'a dyn Shape,
         bounds: &Bounds,
         transformed_ray: &Ray,
-    ) -> Vec<Intersection<'a>> {
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
         let (xtmin, xtmax) = Self::check_axis(
             transformed_ray.origin.x,
             transformed_ray.direction.x,
@@ -46,7 +48,7 @@ impl Cube {
         // Optimized version, as suggested in the practice section.
         //
         if tmin > tmax {
-            return vec![];
+            return;
         }
 
         let (ztmin, ztmax) = Self::check_axis(
@@ -59,21 +61,25 @@ impl Cube {
         tmin = tmin.max(ztmin);
         tmax = tmax.min(ztmax);
 
-        if tmin > tmax {
-            vec![]
-        } else {
-            vec![
+        if tmin <= tmax {
+            intersections.extend([
                 Intersection {
                     t: tmin,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object,
                 },
                 Intersection {
                     t: tmax,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object,
                 },
-            ]
+            ]);
         }
     }
 }
@@ -81,7 +87,7 @@ impl Cube {
 impl ShapeLocal for Cube {
     // point: In object space.
     //
-    fn local_normal(&self, point: Tuple, _intersection: &Intersection) -> Tuple {
+    fn local_normal(&self, point: Point3, _intersection: &Intersection) -> Normal3 {
         let x_abs = point.x.abs();
         let y_abs = point.y.abs();
         let z_abs = point.z.abs();
@@ -96,9 +102,9 @@ impl ShapeLocal for Cube {
         };
 
         if max_dimension_abs > z_abs {
-            Tuple::vector(current_normal.0, current_normal.1, current_normal.2)
+            Normal3::new(current_normal.0, current_normal.1, current_normal.2)
         } else {
-            Tuple::vector(0.0, 0.0, point.z)
+            Normal3::new(0.0, 0.0, point.z)
         }
 
         // Original algorithm
@@ -106,28 +112,37 @@ impl ShapeLocal for Cube {
         // let max_dimension_abs = x_abs.max(y_abs).max(z_abs);
 
         // return if max_dimension_abs == x_abs {
-        //     Tuple::vector(point.x, 0.0, 0.0)
+        //     Normal3::new(point.x, 0.0, 0.0)
         // } else if max_dimension_abs == y_abs {
-        //     Tuple::vector(0.0, point.y, 0.0)
+        //     Normal3::new(0.0, point.y, 0.0)
         // } else {
-        //     Tuple::vector(0.0, 0.0, point.z)
+        //     Normal3::new(0.0, 0.0, point.z)
         // };
     }
 
     // ray: In object space.
     //
-    fn local_intersections(&self, ray: &Ray) -> Vec<Intersection> {
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
         let bounds = Bounds {
-            min: Tuple::point(-1, -1, -1),
-            max: Tuple::point(1, 1, 1),
+            min: Point3::new(-1, -1, -1),
+            max: Point3::new(1, 1, 1),
         };
 
-        Self::generalized_intersections(self, &bounds, ray)
+        Self::generalized_intersections_into(self, &bounds, ray, intersections)
     }
 }
 
 impl Cube {
-    fn check_axis(origin: f64, direction: f64, minimum: f64, maximum: f64) -> (f64, f64) {
+    fn check_axis(
+        origin: Float,
+        direction: Float,
+        minimum: Float,
+        maximum: Float,
+    ) -> (Float, Float) {
         let tmin_numerator = minimum - origin;
         let tmax_numerator = maximum - origin;
 
@@ -145,8 +160,8 @@ impl Cube {
 impl BoundedShape for Cube {
     fn local_bounds(&self) -> Bounds {
         Bounds {
-            min: Tuple::point(-1, -1, -1),
-            max: Tuple::point(1, 1, 1),
+            min: Point3::new(-1, -1, -1),
+            max: Point3::new(1, 1, 1),
         }
     }
 }
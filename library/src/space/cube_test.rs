@@ -47,10 +47,7 @@ demonstrate! {
             ];
 
             for ((ox, oy, oz), (dx, dy, dz)) in examples.iter() {
-                let ray = Ray {
-                    origin: Tuple::point(*ox, *oy, *oz),
-                    direction: Tuple::vector(*dx, *dy, *dz),
-                };
+                let ray = Ray::unbounded(Point3::new(*ox, *oy, *oz), Vector3::new(*dx, *dy, *dz));
 
                 assert_eq!(Arc::clone(&cube).local_intersections(&ray), vec![]);
             }
@@ -77,10 +74,13 @@ demonstrate! {
                 let default_intersection = Intersection {
                     t: 0.0,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object: &Plane::default(),
                 };
-                let point = Tuple::point(*px, *py, *pz);
-                let expected_normal = Tuple::vector(*nx, *ny, *nz);
+                let point = Point3::new(*px, *py, *pz);
+                let expected_normal = Normal3::new(*nx, *ny, *nz);
 
                 assert_eq!(cube.local_normal(point, &default_intersection), expected_normal);
             }
@@ -0,0 +1,267 @@
+use super::{
+    shape::{self, private::ShapeLocal},
+    BoundedShape, Bounds, Intersection, Ray, ShapeParent, WorldTransformsCache,
+};
+use crate::{
+    lang::Float,
+    math::{Normal3, Point3, Transform, Vector3},
+    properties::MaterialHandle,
+};
+
+// Maximum distance between the flattened axis and the actual curve, relative to the maximum radius.
+//
+const FLATTENING_TOLERANCE_FACTOR: Float = 0.05;
+
+const MAX_SEGMENTS: usize = 128;
+
+// Minimum marching step, relative to the maximum radius; it determines the thinnest detail (e.g. the
+// tip of a tapered curve) that is found.
+//
+const MIN_STEP_FACTOR: Float = 1e-3;
+
+// The distance function is not exact for tapered segments, so the steps are slightly shortened.
+//
+const STEP_FACTOR: Float = 0.9;
+
+const MAX_MARCHING_STEPS: usize = 1024;
+
+const ROOT_REFINEMENT_STEPS: usize = 32;
+
+#[derive(Debug)]
+struct CurveSegment {
+    start: Point3,
+    end: Point3,
+    start_radius: Float,
+    end_radius: Float,
+}
+
+// Tube swept along a cubic Bezier curve, with the width linearly varying from start to end; it's meant
+// for hair, grass, cables and so on.
+//
+// The axis is flattened on construction into a polyline (fine enough to be indistinguishable from the
+// curve), which is then intersected directly, by marching the ray against the distance from the tube
+// surface; once the surface is crossed, the root is refined via bisection.
+//
+// Since the flattening depends on the control points/widths, these are not public, and the curve must be
+// created via `new()`.
+//
+#[derive(Debug, ShapeAccessors, SmartDefault)]
+pub struct Curve {
+    #[default(_code = "shape::new_shape_id()")]
+    pub id: u32,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
+
+    #[default([Point3::origin(); 4])]
+    control_points: [Point3; 4],
+    widths: (Float, Float),
+    segments: Vec<CurveSegment>,
+}
+
+impl Curve {
+    pub fn new(control_points: [Point3; 4], start_width: Float, end_width: Float) -> Self {
+        let mut curve = Curve {
+            control_points,
+            widths: (start_width, end_width),
+            ..Curve::default()
+        };
+
+        curve.segments = curve.flatten();
+
+        curve
+    }
+
+    pub fn control_points(&self) -> &[Point3; 4] {
+        &self.control_points
+    }
+
+    pub fn widths(&self) -> (Float, Float) {
+        self.widths
+    }
+
+    // In object space.
+    //
+    pub fn point_at(&self, u: Float) -> Point3 {
+        let s = 1.0 - u;
+        let weights = [s * s * s, 3.0 * u * s * s, 3.0 * u * u * s, u * u * u];
+
+        // The weights sum to 1, so the (vector) sum is the position of a point.
+        //
+        let position = self
+            .control_points
+            .iter()
+            .zip(weights.iter())
+            .fold(Vector3::new(0, 0, 0), |result, (point, weight)| {
+                result + &(point.to_vector() * *weight)
+            });
+
+        Point3::origin() + &position
+    }
+
+    fn radius_at(&self, u: Float) -> Float {
+        (self.widths.0 + (self.widths.1 - self.widths.0) * u) / 2.0
+    }
+
+    fn max_radius(&self) -> Float {
+        self.widths.0.max(self.widths.1) / 2.0
+    }
+
+    // See BezierPatch#segments() for the error bound.
+    //
+    fn flatten(&self) -> Vec<CurveSegment> {
+        let max_second_difference = (0..2)
+            .map(|i| {
+                let difference = (self.control_points[i] - &self.control_points[i + 1])
+                    - &(self.control_points[i + 1] - &self.control_points[i + 2]);
+                difference.magnitude()
+            })
+            .fold(0.0, Float::max);
+
+        let tolerance = self.max_radius() * FLATTENING_TOLERANCE_FACTOR;
+        let segments_count = ((0.75 * max_second_difference / tolerance).sqrt().ceil() as usize)
+            .clamp(1, MAX_SEGMENTS);
+
+        (0..segments_count)
+            .map(|i| {
+                let start_u = i as Float / segments_count as Float;
+                let end_u = (i + 1) as Float / segments_count as Float;
+
+                CurveSegment {
+                    start: self.point_at(start_u),
+                    end: self.point_at(end_u),
+                    start_radius: self.radius_at(start_u),
+                    end_radius: self.radius_at(end_u),
+                }
+            })
+            .collect()
+    }
+
+    // Returns the closest point on the axis, and the radius at that point.
+    //
+    fn closest_axis_point(&self, point: &Point3) -> (Point3, Float) {
+        let mut closest = (Point3::origin(), 0.0);
+        let mut closest_distance = Float::INFINITY;
+
+        for segment in self.segments.iter() {
+            let axis = segment.end - &segment.start;
+            let axis_length_squared = axis.dot_product(&axis);
+
+            let s = if axis_length_squared > 0.0 {
+                ((*point - &segment.start).dot_product(&axis) / axis_length_squared).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            let axis_point = segment.start + &(axis * s);
+            let radius = segment.start_radius + (segment.end_radius - segment.start_radius) * s;
+            let distance = (*point - &axis_point).magnitude() - radius;
+
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest = (axis_point, radius);
+            }
+        }
+
+        closest
+    }
+
+    // Signed (approximate) distance from the surface; negative inside the tube.
+    //
+    fn distance(&self, point: &Point3) -> Float {
+        let (axis_point, radius) = self.closest_axis_point(point);
+
+        (*point - &axis_point).magnitude() - radius
+    }
+
+    fn refine_root(&self, ray: &Ray, mut t_a: Float, mut t_b: Float, distance_a: Float) -> Float {
+        for _ in 0..ROOT_REFINEMENT_STEPS {
+            let t_middle = (t_a + t_b) / 2.0;
+
+            if self.distance(&ray.position(t_middle)).signum() == distance_a.signum() {
+                t_a = t_middle;
+            } else {
+                t_b = t_middle;
+            }
+        }
+
+        (t_a + t_b) / 2.0
+    }
+}
+
+impl ShapeLocal for Curve {
+    // point: In object space.
+    //
+    fn local_normal(&self, point: Point3, _intersection: &Intersection) -> Normal3 {
+        let (axis_point, _) = self.closest_axis_point(&point);
+
+        (point - &axis_point).into()
+    }
+
+    // ray: In object space.
+    //
+    // The marching is performed in `t` units, which are converted from/to the distances via the ray
+    // direction length (which is not necessarily normalized, in object space).
+    //
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        let (mut t, t_end) = match self.local_bounds().intersection_range(ray) {
+            Some(range) => range,
+            None => return,
+        };
+
+        let direction_length = ray.direction.magnitude();
+        let min_step = self.max_radius() * MIN_STEP_FACTOR / direction_length;
+
+        let mut distance = self.distance(&ray.position(t));
+
+        for _ in 0..MAX_MARCHING_STEPS {
+            if t >= t_end {
+                break;
+            }
+
+            let step = (distance.abs() * STEP_FACTOR / direction_length).max(min_step);
+            let next_t = (t + step).min(t_end);
+            let next_distance = self.distance(&ray.position(next_t));
+
+            if next_distance.signum() != distance.signum() {
+                intersections.push(Intersection {
+                    t: self.refine_root(ray, t, next_t, distance),
+                    uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
+                    object: self,
+                });
+            }
+
+            t = next_t;
+            distance = next_distance;
+        }
+    }
+}
+
+impl BoundedShape for Curve {
+    // The curve lies within the convex hull of the control points, so their bounds, extended by the
+    // radius, enclose the tube.
+    //
+    fn local_bounds(&self) -> Bounds {
+        let mut bounds = Bounds::default();
+
+        for point in self.control_points.iter() {
+            Bounds::update_from_point(&mut bounds, point);
+        }
+
+        let radius = self.max_radius();
+
+        bounds.min = bounds.min - &Vector3::new(radius, radius, radius);
+        bounds.max = bounds.max + &Vector3::new(radius, radius, radius);
+
+        bounds
+    }
+}
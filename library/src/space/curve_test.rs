@@ -0,0 +1,132 @@
+use demonstrate::demonstrate;
+
+use crate::math::Point3;
+
+// Straight curve along the x axis, from -2 to 2.
+//
+pub fn straight_control_points() -> [Point3; 4] {
+    [
+        Point3::new(-2, 0, 0),
+        Point3::new(-1, 0, 0),
+        Point3::new(1, 0, 0),
+        Point3::new(2, 0, 0),
+    ]
+}
+
+demonstrate! {
+    describe "Curve" {
+        use super::straight_control_points;
+        use crate::lang::ApproximateFloatOps;
+        use crate::math::*;
+        use crate::space::{*, shape::private::ShapeLocal};
+        use std::sync::Arc;
+
+        it "The curve interpolates the end control points" {
+            let curve = Curve::new(straight_control_points(), 1.0, 1.0);
+
+            assert_eq!(curve.point_at(0.0), Point3::new(-2, 0, 0));
+            assert_eq!(curve.point_at(1.0), Point3::new(2, 0, 0));
+        }
+
+        it "A ray strikes the tube on both sides" {
+            let curve = Curve::new(straight_control_points(), 1.0, 1.0);
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = curve.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert!(intersections[0].t.approximate_equals(4.5));
+            assert!(intersections[1].t.approximate_equals(5.5));
+        }
+
+        it "A ray with a non-normalized direction strikes the tube" {
+            let curve = Curve::new(straight_control_points(), 1.0, 1.0);
+            let ray = Ray::new((0, 0, -5), (0, 0, 2));
+
+            let intersections = curve.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert!(intersections[0].t.approximate_equals(2.25));
+            assert!(intersections[1].t.approximate_equals(2.75));
+        }
+
+        it "A ray misses the tube" {
+            let curve = Curve::new(straight_control_points(), 1.0, 1.0);
+
+            for (origin, direction) in &[((0.0, 0.6, -5.0), (0, 0, 1)), ((3.0, 0.0, -5.0), (0, 0, 1))] {
+                let ray = Ray::new(*origin, *direction);
+
+                assert_eq!(curve.local_intersections(&ray).len(), 0);
+            }
+        }
+
+        it "The width varies along the curve" {
+            let curve = Curve::new(straight_control_points(), 2.0, 0.0);
+
+            // At x = 0, u = 0.5, so the radius is 0.5.
+            //
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+            let intersections = curve.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert!(intersections[0].t.approximate_equals(4.5));
+
+            // Close to the tip, the tube is thinner.
+            //
+            let ray = Ray::new((1.9, 0.2, -5.0), (0, 0, 1));
+
+            assert_eq!(curve.local_intersections(&ray).len(), 0);
+        }
+
+        it "The normal points away from the axis" {
+            let curve = Curve::new(straight_control_points(), 1.0, 1.0);
+            let ray = Ray::new((0.5, 0.0, -5.0), (0, 0, 1));
+
+            let intersections = curve.local_intersections(&ray);
+            let normal = curve.local_normal(ray.position(intersections[0].t), &intersections[0]);
+
+            assert_eq!(normal.normalize(), Normal3::new(0, 0, -1));
+        }
+
+        it "A ray strikes a bent curve" {
+            let control_points = [
+                Point3::new(-2, 0, 0),
+                Point3::new(-1, 2, 0),
+                Point3::new(1, 2, 0),
+                Point3::new(2, 0, 0),
+            ];
+            let curve = Curve::new(control_points, 0.2, 0.2);
+
+            // The curve apex is at u = 0.5.
+            //
+            let apex = curve.point_at(0.5);
+            let ray = Ray::new((0.0, apex.y, -5.0), (0, 0, 1));
+
+            let intersections = curve.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert!((intersections[0].t - 4.9).abs() < 1e-2);
+        }
+
+        it "Computes the bounds, including the width" {
+            let curve = Curve::new(straight_control_points(), 0.5, 1.0);
+            let bounds = curve.local_bounds();
+
+            assert_eq!(bounds.min, Point3::new(-2.5, -0.5, -0.5));
+            assert_eq!(bounds.max, Point3::new(2.5, 0.5, 0.5));
+        }
+
+        it "A curve inside a group" {
+            let curve: Arc<dyn Shape> = Arc::new(Curve::new(straight_control_points(), 1.0, 1.0));
+            let group = Group::new(Matrix4::translation(0, 0, 1), vec![curve]);
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = group.intersections(&ray);
+            let intersection_state = ray.intersection_state(&intersections[0], &intersections);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersection_state.point, Point3::new(0, 0, 0.5));
+            assert_eq!(intersection_state.normalv, Normal3::new(0, 0, -1));
+        }
+    }
+}
@@ -4,7 +4,7 @@ demonstrate! {
     describe "Cylinder" {
         use std::sync::Arc;
         use crate::math::*;
-        use crate::lang::ApproximateFloat64Ops;
+        use crate::lang::{ApproximateFloatOps, Float};
         use crate::space::{*, shape::private::ShapeLocal};
 
         before {
@@ -15,6 +15,9 @@ demonstrate! {
             let default_intersection = Intersection {
                 t: 0.0,
                 uv: None,
+                face: None,
+                instance: None,
+                material: None,
                 object: &Plane::default(),
             };
         }
@@ -28,9 +31,9 @@ demonstrate! {
             ];
 
             for ((ox, oy, oz), (dx, dy, dz)) in examples.iter() {
-                let origin    = Tuple::point(*ox, *oy, *oz);
-                let direction = Tuple::vector(*dx, *dy, *dz).normalize();
-                let ray = Ray { origin, direction };
+                let origin    = Point3::new(*ox, *oy, *oz);
+                let direction = Vector3::new(*dx, *dy, *dz).normalize();
+                let ray = Ray::unbounded(origin, direction);
 
                 assert_eq!(cylinder.local_intersections(&ray), vec![]);
             }
@@ -45,9 +48,9 @@ demonstrate! {
             ];
 
             for ((ox, oy, oz), (dx, dy, dz), t1, t2) in examples.iter() {
-                let origin    = Tuple::point(*ox, *oy, *oz);
-                let direction = Tuple::vector(*dx, *dy, *dz).normalize();
-                let ray = Ray { origin, direction };
+                let origin    = Point3::new(*ox, *oy, *oz);
+                let direction = Vector3::new(*dx, *dy, *dz).normalize();
+                let ray = Ray::unbounded(origin, direction);
 
                 let actual_intersections = &cylinder.local_intersections(&ray);
 
@@ -66,16 +69,16 @@ demonstrate! {
             ];
 
             for ((px, py, pz), (nx, ny, nz)) in examples.iter() {
-                let point = Tuple::point(*px, *py, *pz);
-                let expected_normal = Tuple::vector(*nx, *ny, *nz);
+                let point = Point3::new(*px, *py, *pz);
+                let expected_normal = Normal3::new(*nx, *ny, *nz);
 
                 assert_eq!(cylinder.local_normal(point, &default_intersection), expected_normal);
             }
         }
 
         it "The default minimum and maximum for a cylinder" {
-            assert_eq!(cylinder.minimum, f64::NEG_INFINITY);
-            assert_eq!(cylinder.maximum, f64::INFINITY);
+            assert_eq!(cylinder.minimum, Float::NEG_INFINITY);
+            assert_eq!(cylinder.maximum, Float::INFINITY);
         }
 
         it "Intersecting a constrained cylinder" {
@@ -96,9 +99,9 @@ demonstrate! {
             ];
 
             for ((ox, oy, oz), (dx, dy, dz), expected_count) in examples.iter() {
-                let origin    = Tuple::point(*ox, *oy, *oz);
-                let direction = Tuple::vector(*dx, *dy, *dz).normalize();
-                let ray = Ray { origin, direction };
+                let origin    = Point3::new(*ox, *oy, *oz);
+                let direction = Vector3::new(*dx, *dy, *dz).normalize();
+                let ray = Ray::unbounded(origin, direction);
 
                 let actual_intersections = &cylinder.local_intersections(&ray);
 
@@ -128,9 +131,9 @@ demonstrate! {
             });
 
             for ((ox, oy, oz), (dx, dy, dz), expected_count) in examples.iter() {
-                let origin    = Tuple::point(*ox, *oy, *oz);
-                let direction = Tuple::vector(*dx, *dy, *dz).normalize();
-                let ray = Ray { origin, direction };
+                let origin    = Point3::new(*ox, *oy, *oz);
+                let direction = Vector3::new(*dx, *dy, *dz).normalize();
+                let ray = Ray::unbounded(origin, direction);
 
                 let actual_intersections = &cylinder.local_intersections(&ray);
 
@@ -157,8 +160,8 @@ demonstrate! {
             ];
 
             for ((px, py, pz), (nx, ny, nz)) in examples.iter() {
-                let point = Tuple::point(*px, *py, *pz);
-                let expected_normal = Tuple::vector(*nx, *ny, *nz);
+                let point = Point3::new(*px, *py, *pz);
+                let expected_normal = Normal3::new(*nx, *ny, *nz);
 
                 assert_eq!(cylinder.local_normal(point, &default_intersection), expected_normal);
             }
@@ -0,0 +1,38 @@
+use crate::{lang::Float, properties::Color};
+
+// Global exponential fog (haze): the color reaching the eye is blended towards the fog color, based on
+// the distance traveled; the rays that don't hit anything take the fog color.
+//
+// The fog is not lit (its color is constant); for lit volumes, see Medium.
+//
+#[derive(Clone, Copy, Debug)]
+pub struct Fog {
+    pub color: Color,
+    // Per world unit; at distance d, the original color is weighted by exp(-density * d).
+    //
+    pub density: Float,
+}
+
+impl Fog {
+    pub fn new(color: Color, density: Float) -> Self {
+        Self { color, density }
+    }
+
+    // distance: Infinite for the rays that don't hit anything.
+    //
+    pub fn apply(&self, color: Color, distance: Float) -> Color {
+        // The exponent would be NaN for a zero density (0 * ∞).
+        //
+        if distance.is_infinite() {
+            return if self.density > 0.0 {
+                self.color
+            } else {
+                color
+            };
+        }
+
+        let transmittance = (-self.density * distance).exp();
+
+        color * transmittance + &(self.color * (1.0 - transmittance))
+    }
+}
@@ -2,28 +2,33 @@ use std::sync::{Arc, Weak};
 
 use super::{
     shape::{self, private::ShapeLocal},
-    BoundedShape, Bounds, Cube, Intersection, Ray, Shape,
+    BoundedShape, Bounds, Intersection, Ray, Shape, ShapeParent, WorldTransformsCache,
+};
+use crate::{
+    math::{Matrix4, Transform},
+    math::{Normal3, Point3},
+    properties::MaterialHandle,
 };
-use crate::{math::Matrix, math::Tuple, properties::Material};
-
-#[cfg(test)]
-use std::any::Any;
 
-// For nested groups, an optimization is to reduce the transformations by pushing them to the children,
-// although it requires some modification. Since in this project there is a maximum of one level, the
-// optimization doesn't apply.
+// For nested groups, the transformations are flattened by Shape#commit(), which caches the combined
+// world transforms of each shape.
 //
 // Creating a struct with a single Mutex doesn't simplify things, since parent and children are not
 // accessed together (at least, currently, directly and in the same context).
 //
-#[derive(Debug, SmartDefault)]
+#[derive(Debug, ShapeAccessors, SmartDefault)]
+#[container]
 pub struct Group {
     #[default(_code = "shape::new_shape_id()")]
     pub id: u32,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
-    #[default(Weak::<Self>::new())]
-    pub parent: Weak<dyn Shape>,
+    pub transform: Transform,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    // When set, it's inherited by the descendants without a material (see
+    // Intersection#inherit_material()).
+    //
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
 
     // This is tricky. Wrapping the vector with the mutex will cause contention, but wrapping the shape
     // will require all the Shape methods to be converted to functions taking Arc<Mutex<dyn shape>>;
@@ -36,105 +41,150 @@ pub struct Group {
 impl Group {
     // In the book, this is `add_child()`.
     //
-    // In order to create a bidirectional tree, interior mutability is required. Since the intent is
-    // _not_ to have mutexes, unsafe code is used.
+    // The group is created via Arc::new_cyclic(), which provides the (not yet upgradable) Weak reference
+    // before the group is constructed, so that the children parent can be set without mutable access to
+    // the Arc.
     //
-    // Although an Arc can be borrowed as mutable, it's still not possible, because:
+    // Since the children can't be changed after construction, mutable trees are modeled via SceneGraph,
+    // which builds the groups once the structure is final.
     //
-    // - if we get the group mutable reference before the children parent set cycle, the children will
-    //   require an immutable borrow (in order to clone the Arc)
-    // - if we get the mutable reference after the children cycle, there are now multiple Arc clones,
-    //   so the mutable reference will fail
+    pub fn new(transform: Matrix4, children: Vec<Arc<dyn Shape>>) -> Arc<Group> {
+        Self::build(transform, None, children)
+    }
+
+    // Group whose material is inherited by the descendants without one (e.g. the triangles of an
+    // imported model). Since the children reference the group, the material can't be assigned after
+    // construction.
     //
-    // Group mutability is required in order to add the children.
+    pub fn with_material(
+        transform: Matrix4,
+        material: MaterialHandle,
+        children: Vec<Arc<dyn Shape>>,
+    ) -> Arc<Group> {
+        Self::build(transform, Some(material), children)
+    }
+
+    fn build(
+        transform: Matrix4,
+        material: Option<MaterialHandle>,
+        children: Vec<Arc<dyn Shape>>,
+    ) -> Arc<Group> {
+        Arc::new_cyclic(|group: &Weak<Group>| {
+            for child in children.iter() {
+                child.set_parent(group.clone());
+            }
+
+            Group {
+                transform: transform.into(),
+                material,
+                children,
+                ..Group::default()
+            }
+        })
+    }
+}
+
+impl Group {
+    // ray: In object space.
     //
-    // There are no trivial/clean solutions to this problem; see https://users.rust-lang.org/t/is-it-possible-to-safely-build-a-read-only-thread-safe-bidirectional-tree/52759.
+    // The box intersections are checked against the ray interval, so that the groups entirely behind
+    // the origin, or beyond the closest hit found so far, are skipped.
     //
-    pub fn new(transform: Matrix, mut children: Vec<Arc<dyn Shape>>) -> Arc<Group> {
-        let mut group = Arc::new(Group {
-            transform,
-            ..Group::default()
-        });
-
-        for child in children.iter_mut() {
-            // Children needs to be unchecked as well, otherwise shapes can't be nested.
-            //
-            let child_parent_ref = unsafe { Arc::get_mut_unchecked(child) }.parent_mut();
-            let parent_ref = Arc::clone(&group) as Arc<dyn Shape>;
-            *child_parent_ref = Arc::downgrade(&parent_ref);
+    fn bounds_hit(&self, ray: &Ray) -> bool {
+        match self.local_bounds().intersection_range(ray) {
+            Some((box_enter, box_exit)) => box_enter < ray.t_max && box_exit >= ray.t_min,
+            None => false,
         }
-
-        let group_mut = unsafe { Arc::get_mut_unchecked(&mut group) };
-        group_mut.children = children;
-
-        group
     }
 }
 
 impl Shape for Group {
-    fn id(&self) -> u32 {
-        self.id
-    }
+    // Narrows the interval at each hit, so that the following children are searched only for closer
+    // intersections.
+    //
+    fn closest_hit<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Option<Intersection<'a>> {
+        let mut transformed_ray = ray.inverse_transform(&self.transform);
+
+        if !self.bounds_hit(&transformed_ray) {
+            return None;
+        }
 
-    fn parent(&self) -> Option<Arc<dyn Shape>> {
-        Weak::upgrade(&self.parent)
-    }
+        let mut closest_hit = None;
 
-    fn parent_mut(&mut self) -> &mut Weak<dyn Shape> {
-        &mut self.parent
-    }
+        for child in self.children.iter() {
+            if let Some(hit) = child.closest_hit(&transformed_ray, intersections) {
+                transformed_ray.t_max = hit.t;
+                closest_hit = Some(hit);
+            }
+        }
 
-    fn transform(&self) -> &Matrix {
-        &self.transform
-    }
+        if let (Some(hit), Some(material)) = (&mut closest_hit, &self.material) {
+            hit.inherit_material(material);
+        }
 
-    fn transform_mut(&mut self) -> &mut Matrix {
-        &mut self.transform
+        closest_hit
     }
 
-    fn material(&self) -> &Material {
-        panic!()
-    }
+    fn any_hit<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) -> bool {
+        let transformed_ray = ray.inverse_transform(&self.transform);
 
-    fn material_mut(&mut self) -> &mut Material {
-        panic!()
+        self.bounds_hit(&transformed_ray)
+            && self
+                .children
+                .iter()
+                .any(|child| child.any_hit(&transformed_ray, intersections))
     }
 
-    fn includes(&self, object: &dyn Shape) -> bool {
-        self.children.iter().any(|child| child.includes(object))
+    fn includes(&self, intersection: &Intersection) -> bool {
+        self.children
+            .iter()
+            .any(|child| child.includes(intersection))
     }
 
-    #[cfg(test)]
-    fn as_any(&self) -> &dyn Any {
-        self
+    fn commit(&self, parent_world_transform: Option<&Matrix4>) {
+        let world_transforms = self
+            .world_transforms
+            .update(self.transform.matrix(), parent_world_transform);
+
+        for child in self.children.iter() {
+            child.commit(Some(&world_transforms.transform));
+        }
     }
 }
 
 impl ShapeLocal for Group {
-    fn local_normal(&self, _point: Tuple, _intersection: &Intersection) -> Tuple {
+    fn local_normal(&self, _point: Point3, _intersection: &Intersection) -> Normal3 {
         panic!("local normal is not meaningful for Group")
     }
 
     // ray: In object space.
     //
-    fn local_intersections(&self, ray: &Ray) -> Vec<Intersection> {
-        let local_bounds = self.local_bounds();
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        if !self.bounds_hit(ray) {
+            return;
+        }
 
-        let box_intersections = Cube::generalized_intersections(self, &local_bounds, ray);
+        let start = intersections.len();
 
-        if box_intersections.is_empty() {
-            return vec![];
+        for child in self.children.iter() {
+            child.intersections_into(ray, intersections);
         }
 
-        let mut intersections = self
-            .children
-            .iter()
-            .flat_map(|child| child.intersections(ray))
-            .collect::<Vec<_>>();
-
-        intersections.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        if let Some(material) = &self.material {
+            for intersection in intersections[start..].iter_mut() {
+                intersection.inherit_material(material);
+            }
+        }
 
-        intersections
+        Intersection::sort(&mut intersections[start..]);
     }
 }
 
@@ -2,14 +2,16 @@ use demonstrate::demonstrate;
 
 demonstrate! {
     describe "Group" {
+        use crate::lang::Float;
         use crate::math::*;
+        use crate::properties::*;
         use crate::space::{*, shape::private::ShapeLocal};
         use std::sync::{Arc};
 
         it "Creating a new group" {
             let group: Arc<Group> = Arc::new(Group::default());
 
-            assert_eq!(*group.transform(), Matrix::identity(4));
+            assert_eq!(*group.transform(), Matrix4::identity());
             assert_eq!((*group).children.len(), 0);
         }
 
@@ -18,7 +20,7 @@ demonstrate! {
             let shape_id = shape.id();
 
             let group = Group::new(
-                Matrix::identity(4),
+                Matrix4::identity(),
                 vec![shape],
             );
 
@@ -41,11 +43,11 @@ demonstrate! {
                 ..Sphere::default()
             });
             let sphere2: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(0, 0, -3),
+                transform: Matrix4::translation(0, 0, -3).into(),
                 ..Sphere::default()
             });
             let sphere3: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(5, 0, 0),
+                transform: Matrix4::translation(5, 0, 0).into(),
                 ..Sphere::default()
             });
 
@@ -53,7 +55,7 @@ demonstrate! {
             let sphere2_id = sphere2.id();
 
             let group = Group::new(
-                Matrix::identity(4),
+                Matrix4::identity(),
                 vec![sphere1, sphere2, sphere3],
             );
 
@@ -71,12 +73,12 @@ demonstrate! {
 
         it "Intersecting a transformed group" {
             let sphere: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(5, 0, 0),
+                transform: Matrix4::translation(5, 0, 0).into(),
                 ..Sphere::default()
             });
 
             let group = Group::new(
-                Matrix::scaling(2, 2, 2),
+                Matrix4::scaling(2, 2, 2),
                 vec![sphere],
             );
 
@@ -86,5 +88,107 @@ demonstrate! {
 
             assert_eq!(actual_intersections.len(), 2);
         }
+
+        it "Intersections outside of the ray interval are pruned" {
+            let sphere: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let group = Group::new(Matrix4::identity(), vec![sphere]);
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1)).with_interval(5.0, 10.0);
+
+            let actual_intersections = group.intersections(&ray);
+
+            assert_eq!(actual_intersections.len(), 1);
+            assert_eq!(actual_intersections[0].t, 6.0);
+        }
+
+        it "The closest hit is found across the children" {
+            let sphere1: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let sphere2: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0, 0, -3).into(),
+                ..Sphere::default()
+            });
+
+            let sphere2_id = sphere2.id();
+
+            let group = Group::new(Matrix4::identity(), vec![sphere1, sphere2]);
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1)).with_interval(0.0, Float::INFINITY);
+
+            let hit = group.closest_hit(&ray, &mut vec![]).unwrap();
+
+            assert_eq!(hit.t, 1.0);
+            assert_eq!(hit.object.id(), sphere2_id);
+        }
+
+        it "A group beyond the ray interval is not hit" {
+            let sphere: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let group = Group::new(Matrix4::translation(0, 0, 10), vec![sphere]);
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1)).with_interval(0.0, 10.0);
+
+            assert!(group.closest_hit(&ray, &mut vec![]).is_none());
+            assert!(!group.any_hit(&ray, &mut vec![]));
+            assert!(group.any_hit(&ray.with_interval(0.0, 20.0), &mut vec![]));
+        }
+
+        it "Children without a material inherit the group one" {
+            let own_material_sphere: Arc<dyn Shape> = Arc::new(Sphere {
+                material: Material { ambient: 0.3, ..Material::default() }.into(),
+                ..Sphere::default()
+            });
+            let inheriting_sphere: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(0, 0, -3).into(),
+                ..Sphere::default()
+            });
+
+            let group = Group::with_material(
+                Matrix4::identity(),
+                MaterialHandle::new(Material { ambient: 0.5, ..Material::default() }),
+                vec![own_material_sphere, inheriting_sphere],
+            );
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let actual_ambients = group.local_intersections(&ray).iter().map(|intersection| intersection.material().ambient).collect::<Vec<_>>();
+
+            assert_eq!(actual_ambients, vec![0.5, 0.5, 0.3, 0.3]);
+
+            let hit = group.closest_hit(&ray.with_interval(0.0, Float::INFINITY), &mut vec![]).unwrap();
+
+            assert_eq!(hit.material().ambient, 0.5);
+            assert_eq!(group.material().ambient, 0.5);
+        }
+
+        it "The material of the closest container is inherited" {
+            let sphere: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let inner_group: Arc<dyn Shape> = Group::with_material(
+                Matrix4::identity(),
+                MaterialHandle::new(Material { ambient: 0.3, ..Material::default() }),
+                vec![sphere],
+            );
+            let outer_group = Group::with_material(
+                Matrix4::identity(),
+                MaterialHandle::new(Material { ambient: 0.5, ..Material::default() }),
+                vec![inner_group],
+            );
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = outer_group.intersections(&ray);
+
+            assert_eq!(intersections[0].material().ambient, 0.3);
+        }
+
+        it "Children of a group without a material use the default one" {
+            let sphere: Arc<dyn Shape> = Arc::new(Sphere::default());
+            let group = Group::new(Matrix4::identity(), vec![sphere]);
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = group.intersections(&ray);
+
+            assert_eq!(intersections[0].material().ambient, Material::default().ambient);
+            assert_eq!(group.material().ambient, Material::default().ambient);
+        }
     }
 }
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use super::{
+    shape::{self, private::ShapeLocal},
+    BoundedShape, Bounds, Intersection, Ray, Shape, ShapeParent, WorldTransformsCache,
+};
+use crate::{
+    math::{Matrix4, Transform},
+    math::{Normal3, Point3},
+    properties::MaterialHandle,
+};
+
+// Places a shared geometry in the scene, with its own transform and (optionally) material.
+//
+// Unlike a Group, the geometry is not modified (its parent is not set), so the same geometry can be
+// referenced by any number of instances. Since the geometry doesn't know about the instances, the
+// intersections store the instance, which is then used in order to compute normals and shading (see
+// Intersection#normal()).
+//
+// Instances nested inside instanced geometries are not supported, since an intersection stores only
+// one instance.
+//
+#[derive(Debug, ShapeAccessors, SmartDefault)]
+#[container]
+#[material_override]
+pub struct Instance {
+    #[default(_code = "shape::new_shape_id()")]
+    pub id: u32,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    // When set, it overrides the materials of the geometry.
+    //
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
+
+    // The default is phony, for default purposes.
+    //
+    #[default(Arc::new(super::Sphere::default()))]
+    pub geometry: Arc<dyn Shape>,
+}
+
+impl Instance {
+    pub fn new(geometry: &Arc<dyn Shape>, transform: Matrix4) -> Self {
+        Instance {
+            transform: transform.into(),
+            geometry: Arc::clone(geometry),
+            ..Instance::default()
+        }
+    }
+
+    // Marks a geometry intersection as belonging to this instance, with the instance material, if any.
+    //
+    fn tag_intersection<'a>(&'a self, intersection: &mut Intersection<'a>) {
+        intersection.instance = Some(self);
+
+        if let Some(material) = &self.material {
+            intersection.material = Some(material);
+        }
+    }
+}
+
+impl Shape for Instance {
+    fn material_handle(&self) -> &MaterialHandle {
+        self.material
+            .as_ref()
+            .unwrap_or_else(|| self.geometry.material_handle())
+    }
+
+    fn closest_hit<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Option<Intersection<'a>> {
+        let transformed_ray = ray.inverse_transform(&self.transform);
+
+        let mut hit = self.geometry.closest_hit(&transformed_ray, intersections)?;
+        self.tag_intersection(&mut hit);
+
+        Some(hit)
+    }
+
+    fn any_hit<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) -> bool {
+        let transformed_ray = ray.inverse_transform(&self.transform);
+
+        self.geometry.any_hit(&transformed_ray, intersections)
+    }
+
+    // The geometry may be shared by other instances, so the intersection must have been tagged by this
+    // one (nested instances are not supported).
+    //
+    fn includes(&self, intersection: &Intersection) -> bool {
+        intersection.instance.map(|instance| instance.id) == Some(self.id)
+    }
+
+    // The geometry is committed as root, since the instance transforms are applied separately (see
+    // Intersection#normal()).
+    //
+    fn commit(&self, parent_world_transform: Option<&Matrix4>) {
+        self.world_transforms
+            .update(self.transform.matrix(), parent_world_transform);
+        self.geometry.commit(None);
+    }
+}
+
+impl ShapeLocal for Instance {
+    fn local_normal(&self, _point: Point3, _intersection: &Intersection) -> Normal3 {
+        panic!("local normal is not meaningful for Instance")
+    }
+
+    // ray: In object space.
+    //
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        let start = intersections.len();
+
+        self.geometry.intersections_into(ray, intersections);
+
+        for intersection in intersections[start..].iter_mut() {
+            self.tag_intersection(intersection);
+        }
+    }
+}
+
+impl BoundedShape for Instance {
+    fn local_bounds(&self) -> Bounds {
+        self.geometry.bounds()
+    }
+}
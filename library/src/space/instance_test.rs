@@ -0,0 +1,207 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Instance" {
+        use std::sync::Arc;
+        use crate::math::*;
+        use crate::properties::*;
+        use crate::space::{*, csg};
+
+        before {
+            #[allow(unused_variables)]
+            let geometry: Arc<dyn Shape> = Arc::new(Sphere::default());
+        }
+
+        it "Instances share the geometry" {
+            let instance1 = Instance::new(&geometry, Matrix4::translation(-2, 0, 0));
+            let instance2 = Instance::new(&geometry, Matrix4::translation(2, 0, 0));
+
+            assert_eq!(Arc::strong_count(&geometry), 3);
+            assert_eq!(instance1.geometry.id(), instance2.geometry.id());
+            assert!(geometry.parent().is_none());
+        }
+
+        it "Intersecting a transformed instance" {
+            let instance = Instance::new(&geometry, Matrix4::translation(5, 0, 0));
+            let ray = Ray::new((5, 0, -5), (0, 0, 1));
+
+            let intersections = instance.intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, 4.0);
+            assert_eq!(intersections[0].object.id(), geometry.id());
+            assert_eq!(intersections[0].instance.unwrap().id(), instance.id());
+        }
+
+        it "Computing the normal on a transformed instance" {
+            let instance = Instance::new(&geometry, Matrix4::scaling(2, 2, 2).translate(5, 0, 0));
+            let ray = Ray::new((5, 0, -5), (0, 0, 1));
+
+            let intersections = instance.intersections(&ray);
+            let intersection_state = ray.intersection_state(&intersections[0], &intersections);
+
+            assert_eq!(intersection_state.point, Point3::new(5, 0, -2));
+            assert_eq!(intersection_state.normalv, Normal3::new(0, 0, -1));
+        }
+
+        it "An instance inside a group uses the group transform" {
+            let instance: Arc<dyn Shape> = Arc::new(Instance::new(&geometry, Matrix4::translation(5, 0, 0)));
+            let group = Group::new(Matrix4::scaling(2, 2, 2), vec![instance]);
+            let ray = Ray::new((10, 0, -10), (0, 0, 1));
+
+            let intersections = group.intersections(&ray);
+            let intersection_state = ray.intersection_state(&intersections[0], &intersections);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersection_state.point, Point3::new(10, 0, -2));
+            assert_eq!(intersection_state.normalv, Normal3::new(0, 0, -1));
+        }
+
+        it "An instance inside a committed group" {
+            let instance: Arc<dyn Shape> = Arc::new(Instance::new(&geometry, Matrix4::translation(5, 0, 0)));
+            let group = Group::new(Matrix4::scaling(2, 2, 2), vec![instance]);
+            let ray = Ray::new((10, 0, -10), (0, 0, 1));
+
+            group.commit(None);
+
+            let intersections = group.intersections(&ray);
+            let intersection_state = ray.intersection_state(&intersections[0], &intersections);
+
+            assert!(geometry.world_transforms().get().is_some());
+            assert_eq!(intersection_state.point, Point3::new(10, 0, -2));
+            assert_eq!(intersection_state.normalv, Normal3::new(0, 0, -1));
+        }
+
+        it "The material override is used for shading" {
+            let instance = Instance {
+                material: Some(MaterialHandle::new(Material {
+                    refractive_index: 1.5,
+                    ..Material::default()
+                })),
+                ..Instance::new(&geometry, Matrix4::identity())
+            };
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = instance.intersections(&ray);
+            let intersection_state = ray.intersection_state(&intersections[0], &intersections);
+
+            assert_eq!(intersection_state.material.refractive_index, 1.5);
+            assert_eq!(intersection_state.n2, 1.5);
+        }
+
+        it "Without override, the geometry material is used" {
+            let instance = Instance::new(&geometry, Matrix4::identity());
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = instance.intersections(&ray);
+
+            assert_eq!(intersections[0].material().refractive_index, geometry.material().refractive_index);
+        }
+
+        it "The material override can be modified through the instance" {
+            let mut instance = Instance {
+                material: Material::default().into(),
+                ..Instance::new(&geometry, Matrix4::identity())
+            };
+
+            instance.material_mut().ambient = 0.5;
+
+            assert_eq!(instance.material().ambient, 0.5);
+            assert_eq!(geometry.material().ambient, 0.1);
+        }
+
+        #[should_panic]
+        it "Without override, modifying the material through the instance panics" {
+            let mut instance = Instance::new(&geometry, Matrix4::identity());
+
+            instance.material_mut();
+        }
+
+        it "Instances of the same geometry are distinct containers, when refracting" {
+            let glass = Arc::new(Sphere {
+                material: Material {
+                    transparency: 1.0,
+                    refractive_index: 1.5,
+                    ..Material::default()
+                }.into(),
+                ..Sphere::default()
+            }) as Arc<dyn Shape>;
+            let outer = Instance::new(&glass, Matrix4::scaling(2, 2, 2));
+            let inner = Instance::new(&glass, Matrix4::identity());
+            let ray = Ray::new((0, 0, -4), (0, 0, 1));
+
+            let mut intersections = outer.intersections(&ray);
+            intersections.extend(inner.intersections(&ray));
+            intersections.sort();
+
+            // Entering the inner instance, while inside the outer one.
+            //
+            let (n1, n2) = Ray::refraction_indexes(&intersections[1], &intersections);
+
+            assert_eq!((n1, n2), (1.5, 1.5));
+
+            // Exiting the inner instance, while still inside the outer one.
+            //
+            let (n1, n2) = Ray::refraction_indexes(&intersections[2], &intersections);
+
+            assert_eq!((n1, n2), (1.5, 1.5));
+
+            // Exiting the outer instance.
+            //
+            let (n1, n2) = Ray::refraction_indexes(&intersections[3], &intersections);
+
+            assert_eq!((n1, n2), (1.5, REFRACTIVE_INDEX_VACUUM));
+        }
+
+        it "The closest hit of an instance carries the instance" {
+            let instance = Instance::new(&geometry, Matrix4::translation(5, 0, 0));
+            let ray = Ray::new((5, 0, -5), (0, 0, 1)).with_interval(0.0, 100.0);
+
+            let hit = instance.closest_hit(&ray, &mut vec![]).unwrap();
+
+            assert_eq!(hit.t, 4.0);
+            assert_eq!(hit.instance.unwrap().id(), instance.id());
+            assert!(instance.any_hit(&ray, &mut vec![]));
+            assert!(!instance.any_hit(&ray.with_interval(0.0, 3.0), &mut vec![]));
+        }
+
+        it "The intersections of different instances of the same geometry are distinct" {
+            let instance1 = Instance::new(&geometry, Matrix4::identity());
+            let instance2 = Instance::new(&geometry, Matrix4::identity());
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections1 = instance1.intersections(&ray);
+            let intersections2 = instance2.intersections(&ray);
+
+            assert_eq!(intersections1[0], instance1.intersections(&ray)[0]);
+            assert_ne!(intersections1[0], intersections2[0]);
+
+            let world = World {
+                objects: vec![Arc::new(instance1), Arc::new(instance2)],
+                ..World::default()
+            };
+
+            let mut intersections = vec![];
+            world.intersections(&ray, &mut intersections);
+
+            assert_eq!(intersections.len(), 4);
+        }
+
+        it "A CSG distinguishes the children instancing the same geometry" {
+            let children: Vec<Arc<dyn Shape>> = vec![
+                Arc::new(Instance::new(&geometry, Matrix4::identity())),
+                Arc::new(Instance::new(&geometry, Matrix4::translation(0.0, 0.0, 0.5))),
+            ];
+            let csg = Csg::with_children(csg::Operation::Difference, csg::CutMaterial::Cutter, children, Matrix4::identity());
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = csg.intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, 4.0);
+            assert_eq!(intersections[0].instance.unwrap().id(), csg.children[0].id());
+            assert_eq!(intersections[1].t, 4.5);
+            assert_eq!(intersections[1].instance.unwrap().id(), csg.children[1].id());
+        }
+    }
+}
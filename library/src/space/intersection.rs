@@ -1,29 +1,116 @@
-use std::{cmp::Ordering, fmt::Debug};
+use std::{cmp::Ordering, fmt::Debug, sync::Arc};
 
-use crate::space::Shape;
+use super::Instance;
+use crate::{
+    lang::Float,
+    math::{Normal3, Point3, Vector3},
+    properties::{BumpMap, Material, MaterialHandle},
+    space::Shape,
+};
 
 // Setting NaN values for `t` is invalid; it will cause undefined behavior/panic when sorting.
-// `uv` is used only by triangles.
+// `uv` is used only by triangles (and triangle meshes); `face` is the index of the hit face, and it's
+// used only by triangle meshes.
+// `instance` is set when the object is part of an instanced geometry, and `material` when a container
+// overrides the object material (instances), or the object inherits it (groups and CSGs); both are
+// required at shading time, since the object alone doesn't carry this information.
 // The `object` default is not meaningful, but it's required in order to allow type defaulting.
 //
 // In the book, `intersection()` and `intersection_with_uv` are instantiations of this type.
 //
 #[derive(Clone, Debug)]
 pub struct Intersection<'a> {
-    pub t: f64,
-    pub uv: Option<(f64, f64)>,
+    pub t: Float,
+    pub uv: Option<(Float, Float)>,
+    pub face: Option<usize>,
+    pub instance: Option<&'a Instance>,
+    pub material: Option<&'a MaterialHandle>,
     pub object: &'a dyn Shape,
 }
 
+impl<'a> Intersection<'a> {
+    // Sorts the intersections by `t`. The sort is unstable, since the stable one allocates.
+    //
+    pub(crate) fn sort(intersections: &mut [Intersection]) {
+        intersections.sort_unstable();
+    }
+
+    // The material to use for shading the intersection.
+    //
+    pub fn material_handle(&self) -> &'a MaterialHandle {
+        self.material
+            .unwrap_or_else(|| self.object.material_handle())
+    }
+
+    // Current material of the handle (see MaterialHandle#load()).
+    //
+    pub fn material(&self) -> Arc<Material> {
+        self.material_handle().load()
+    }
+
+    // Assigns the material of a container, if the object has no material of its own, and it hasn't been
+    // assigned one already; since the containers tag the intersections of their children before being
+    // tagged by their own parents, the closest container with a material wins.
+    //
+    pub(crate) fn inherit_material(&mut self, material: &'a MaterialHandle) {
+        if self.material.is_none() && self.object.own_material().is_none() {
+            self.material = Some(material);
+        }
+    }
+
+    // Instances of the same geometry share the objects, so they must be compared as well.
+    //
+    pub fn same_object(&self, other: &Intersection) -> bool {
+        self.object.id() == other.object.id()
+            && self.instance.map(|instance| instance.id)
+                == other.instance.map(|instance| instance.id)
+    }
+
+    // Same as Shape#normal(), but also handles instanced objects.
+    //
+    pub fn normal(&self, world_point: &Point3) -> Normal3 {
+        if let Some(instance) = self.instance {
+            let geometry_point = instance.world_to_object(world_point);
+            let geometry_normal = self.object.normal(&geometry_point, self);
+            instance.normal_to_world(&geometry_normal)
+        } else {
+            self.object.normal(world_point, self)
+        }
+    }
+
+    // Same as Shape#bumped_normal(), but also handles instanced objects.
+    //
+    pub fn bumped_normal(&self, world_point: &Point3, bump_map: &BumpMap) -> Normal3 {
+        if let Some(instance) = self.instance {
+            let geometry_point = instance.world_to_object(world_point);
+            let geometry_normal = self.object.bumped_normal(&geometry_point, self, bump_map);
+            instance.normal_to_world(&geometry_normal)
+        } else {
+            self.object.bumped_normal(world_point, self, bump_map)
+        }
+    }
+
+    // Same as Shape#world_to_object(), but also handles instanced objects.
+    //
+    pub fn world_to_object(&self, world_point: &Point3) -> Point3 {
+        if let Some(instance) = self.instance {
+            let geometry_point = instance.world_to_object(world_point);
+            self.object.world_to_object(&geometry_point)
+        } else {
+            self.object.world_to_object(world_point)
+        }
+    }
+}
+
 impl<'a> Eq for Intersection<'a> {}
 
-// Important: this implementation is intended for exact matches; in addition to `object.id`, it compares
-// `t` exactly. The use case for it is to match a hit in a collection of intersections, from whom the
+// Important: this implementation is intended for exact matches; in addition to the object (and instance),
+// it compares `t` exactly. The use case for it is to match a hit in a collection of intersections, from whom the
 // given intersection was extracted.
 //
 impl<'a> PartialEq for Intersection<'a> {
     fn eq(&self, other: &Self) -> bool {
-        self.t == other.t && self.object.id() == other.object.id()
+        self.t == other.t && self.same_object(other)
     }
 }
 
@@ -38,3 +125,13 @@ impl<'a> PartialOrd for Intersection<'a> {
         self.t.partial_cmp(&other.t)
     }
 }
+
+// Texture coordinates of a hit, along with the object-space derivatives of the surface position with
+// respect to them (dP/du and dP/dv), which define the tangent frame of the normal maps.
+//
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextureCoordinates {
+    pub uv: (Float, Float),
+    pub tangent: Vector3,
+    pub bitangent: Vector3,
+}
@@ -1,26 +1,46 @@
-use crate::{math::Tuple, space::Shape};
+use std::sync::Arc;
+
+use super::{Instance, PointLight};
+use crate::{
+    lang::Float,
+    math::{Normal3, Point3, Vector3},
+    properties::{Color, Material, SurfaceProperties},
+    space::Shape,
+};
 
 #[derive(Debug)]
 pub struct IntersectionState<'a> {
-    pub t: f64,
+    pub t: Float,
     pub object: &'a dyn Shape,
-    pub point: Tuple,
-    pub over_point: Tuple,
-    pub under_point: Tuple,
-    pub eyev: Tuple,
-    pub normalv: Tuple,
-    pub reflectv: Tuple,
-    pub n1: f64,
-    pub n2: f64,
+    pub instance: Option<&'a Instance>,
+    // The material used for shading, which is not necessarily the object's (see Intersection).
+    //
+    pub material: Arc<Material>,
+    // Material values at the point, with the pattern and the maps applied; they're computed once, since
+    // they're used by all the shading stages.
+    //
+    pub surface: SurfaceProperties,
+    pub point: Point3,
+    pub over_point: Point3,
+    pub under_point: Point3,
+    pub eyev: Vector3,
+    pub normalv: Normal3,
+    pub reflectv: Vector3,
+    pub n1: Float,
+    pub n2: Float,
     pub inside: bool,
 }
 
 // Intended to match two exactly equal intersection states - FP error is not considered.
+// The material is not compared, as it can't be (and it's determined by object/instance, anyway).
 //
 impl<'a> PartialEq for IntersectionState<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.t == other.t
             && self.object.eq(other.object)
+            && self.instance.map(|instance| instance.id)
+                == other.instance.map(|instance| instance.id)
+            && self.surface == other.surface
             && self.point == other.point
             && self.over_point == other.over_point
             && self.under_point == other.under_point
@@ -34,8 +54,43 @@ impl<'a> PartialEq for IntersectionState<'a> {
 }
 
 impl<'a> IntersectionState<'a> {
-    pub fn schlick(&self) -> f64 {
-        let mut cos = self.eyev.dot_product(&self.normalv);
+    // Same as Shape#lighting(), but also handles instanced objects and material overrides.
+    //
+    pub fn lighting(&self, light: &PointLight, in_shadow: bool) -> Color {
+        self.material.surface_lighting(
+            light,
+            &self.surface,
+            &self.point,
+            &self.eyev,
+            &self.normalv,
+            self.relative_index(),
+            in_shadow,
+        )
+    }
+
+    // Same as Material#translucency(); the light leaving the object is reduced by the reflectance of the
+    // boundary, based on the refraction indexes.
+    //
+    pub fn translucency(&self, light: &PointLight, thickness: Float, light_cos: Float) -> Color {
+        let translucency = self
+            .material
+            .translucency(light, &self.surface, thickness, light_cos);
+
+        translucency * (1.0 - self.schlick())
+    }
+
+    // Refractive index of the material, relative to the medium on the other side of the surface.
+    //
+    fn relative_index(&self) -> Float {
+        if self.inside {
+            self.n1 / self.n2
+        } else {
+            self.n2 / self.n1
+        }
+    }
+
+    pub fn schlick(&self) -> Float {
+        let mut cos = self.normalv.dot_product(&self.eyev);
 
         if self.n1 > self.n2 {
             let n_ratio = self.n1 / self.n2;
@@ -4,6 +4,7 @@ demonstrate! {
     describe "IntersectionState" {
         use std::sync::Arc;
         use crate::lang::math::sqrt;
+        use crate::math::*;
         use crate::properties::*;
         use crate::space::*;
 
@@ -14,7 +15,7 @@ demonstrate! {
                         transparency: 1.0,
                         refractive_index: 1.5,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Sphere::default()
                 });
             }
@@ -22,8 +23,8 @@ demonstrate! {
             it "should be computed under total internal reflection" {
                 let ray = Ray::new((0.0, 0.0, sqrt(2) / 2.0), (0, 1, 0));
                 let intersections = [
-                    Intersection { t: -sqrt(2) / 2.0, uv: None, object: glass_sphere.as_ref() },
-                    Intersection { t: sqrt(2) / 2.0, uv: None, object: glass_sphere.as_ref() },
+                    Intersection { t: -sqrt(2) / 2.0, uv: None, face: None, instance: None, material: None, object: glass_sphere.as_ref() },
+                    Intersection { t: sqrt(2) / 2.0, uv: None, face: None, instance: None, material: None, object: glass_sphere.as_ref() },
                 ];
                 let intersection_state = ray.intersection_state(&intersections[1], &intersections);
 
@@ -35,8 +36,8 @@ demonstrate! {
             it "should be computed with a perpendicular viewing angle" {
                 let ray = Ray::new((0, 0, 0), (0, 1, 0));
                 let intersections = [
-                    Intersection { t: -1.0, uv: None, object: glass_sphere.as_ref() },
-                    Intersection { t: 1.0, uv: None, object: glass_sphere.as_ref() },
+                    Intersection { t: -1.0, uv: None, face: None, instance: None, material: None, object: glass_sphere.as_ref() },
+                    Intersection { t: 1.0, uv: None, face: None, instance: None, material: None, object: glass_sphere.as_ref() },
                 ];
                 let intersection_state = ray.intersection_state(&intersections[1], &intersections);
 
@@ -48,7 +49,7 @@ demonstrate! {
             it "should be computed with small angle and n2 > n1" {
                 let ray = Ray::new((0.0, 0.99, -2.0), (0, 0, 1));
                 let intersections = [
-                    Intersection { t: 1.8589, uv: None, object: glass_sphere.as_ref() },
+                    Intersection { t: 1.8589, uv: None, face: None, instance: None, material: None, object: glass_sphere.as_ref() },
                 ];
                 let intersection_state = ray.intersection_state(&intersections[0], &intersections);
 
@@ -57,5 +58,39 @@ demonstrate! {
                 assert_float_absolute_eq!(intersection_state.schlick(), expected_reflectance);
             }
         } // context "Schlick approximation"
+
+        context "lighting" {
+            it "should compute the subsurface reflectance relative to the outside medium" {
+                let subsurface_material = |refractive_index| Material {
+                    subsurface: Some(Subsurface::default()),
+                    refractive_index,
+                    ..Material::default()
+                };
+                let water: Arc<dyn Shape> = Arc::new(Sphere {
+                    transform: Matrix4::scaling(2, 2, 2).into(),
+                    material: Material { transparency: 1.0, refractive_index: 1.5, ..Material::default() }.into(),
+                    ..Sphere::default()
+                });
+                let wax: Arc<dyn Shape> = Arc::new(Sphere { material: subsurface_material(1.5).into(), ..Sphere::default() });
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+                let intersections = [
+                    Intersection { t: 3.0, uv: None, face: None, instance: None, material: None, object: water.as_ref() },
+                    Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: wax.as_ref() },
+                    Intersection { t: 6.0, uv: None, face: None, instance: None, material: None, object: wax.as_ref() },
+                    Intersection { t: 7.0, uv: None, face: None, instance: None, material: None, object: water.as_ref() },
+                ];
+                let light = PointLight::new((0, 0, -10), (1, 1, 1));
+
+                let intersection_state = ray.intersection_state(&intersections[1], &intersections);
+
+                // Same index on both sides.
+                //
+                let matching_sphere = Sphere { material: subsurface_material(1.0).into(), ..Sphere::default() };
+                let expected_result = matching_sphere.lighting(&light, &intersection_state.point, &intersection_state.eyev, &intersection_state.normalv, false);
+
+                assert_eq!(intersection_state.lighting(&light, false), expected_result);
+                assert_ne!(wax.lighting(&light, &intersection_state.point, &intersection_state.eyev, &intersection_state.normalv, false), expected_result);
+            }
+        } // context "lighting"
     }
 }
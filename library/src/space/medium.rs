@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use super::{Intersection, Ray, Shape};
+use crate::{
+    lang::{consts::PI, Float},
+    properties::{Color, COLOR_WHITE},
+};
+
+// Homogeneous participating medium (e.g. smoke, dusty air), filling the volume of a closed shape (e.g. a
+// Cube or a Sphere); the light is scattered inside the volume once (single scattering), which, combined
+// with the shadows of the objects, gives the visible light shafts (god rays).
+//
+// The shape is only used for its volume, so it must not be part of the World objects. Its intersections
+// are paired in order as (enter, exit) intervals, so it must be closed, and its surfaces must not overlap
+// (e.g. a Cube, a Sphere, or a closed mesh); the children of a group, if overlapping, are mispaired.
+// Separate media can overlap instead (see World#media_color()).
+//
+#[derive(Debug, SmartDefault)]
+pub struct Medium {
+    // The default is phony, for default purposes.
+    //
+    #[default(Arc::new(super::Cube::default()))]
+    pub shape: Arc<dyn Shape>,
+    // Fraction of the light scattered (rather than absorbed), per channel; it tints the medium.
+    //
+    #[default(COLOR_WHITE)]
+    pub color: Color,
+    // Extinction coefficient, per world unit.
+    //
+    #[default(0.5)]
+    pub density: Float,
+    // Henyey-Greenstein asymmetry, in (-1, 1); positive values scatter forward, which makes the light
+    // shafts brighter when looking towards the light.
+    //
+    #[default(0.0)]
+    pub anisotropy: Float,
+    // Number of (equally spaced) light samples taken along each ray interval inside the volume.
+    //
+    #[default(16)]
+    pub samples: u32,
+}
+
+impl Medium {
+    pub fn new(shape: Arc<dyn Shape>) -> Self {
+        Medium {
+            shape,
+            ..Medium::default()
+        }
+    }
+
+    // Intervals of `t` where the ray is inside the volume, clipped to [t_min, t_max], in order. An odd
+    // trailing intersection (e.g. a grazing hit) is ignored.
+    //
+    // The shape intersections are computed in the buffer (which is cleared first), in order not to
+    // allocate; the intervals are read from it, so they must be consumed before reusing it.
+    //
+    pub fn intervals<'a, 'b>(
+        &'a self,
+        ray: &Ray,
+        t_min: Float,
+        t_max: Float,
+        intersections: &'b mut Vec<Intersection<'a>>,
+    ) -> impl DoubleEndedIterator<Item = (Float, Float)> + 'b {
+        intersections.clear();
+
+        self.shape.intersections_into(
+            &ray.with_interval(Float::NEG_INFINITY, Float::INFINITY),
+            intersections,
+        );
+
+        Intersection::sort(intersections);
+
+        intersections
+            .chunks_exact(2)
+            .map(move |bounds| (bounds[0].t.max(t_min), bounds[1].t.min(t_max)))
+            .filter(|(start, end)| start < end)
+    }
+
+    // Whether the ray is inside the volume for any part of [t_min, t_max].
+    //
+    pub fn overlaps<'a>(
+        &'a self,
+        ray: &Ray,
+        t_min: Float,
+        t_max: Float,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> bool {
+        self.intervals(ray, t_min, t_max, intersections)
+            .next()
+            .is_some()
+    }
+
+    // Fraction of the light crossing the volume, along the ray, between t_min and t_max.
+    //
+    pub fn transmittance<'a>(
+        &'a self,
+        ray: &Ray,
+        t_min: Float,
+        t_max: Float,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Float {
+        let length = self
+            .intervals(ray, t_min, t_max, intersections)
+            .map(|(start, end)| end - start)
+            .sum::<Float>()
+            * ray.direction.magnitude();
+
+        (-self.density * length).exp()
+    }
+
+    // Henyey-Greenstein phase function; its integral over the sphere is 1.
+    //
+    // cos: Cosine of the angle between the light propagation direction and the scattered one.
+    //
+    pub fn phase(&self, cos: Float) -> Float {
+        let g = self.anisotropy;
+        let denominator = (1.0 + g * g - 2.0 * g * cos).powf(1.5);
+
+        (1.0 - g * g) / (4.0 * PI * denominator)
+    }
+}
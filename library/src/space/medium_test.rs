@@ -0,0 +1,52 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Medium" {
+        use crate::space::*;
+        use std::sync::Arc;
+
+        before {
+            #[allow(unused_variables)]
+            let medium = Medium::new(Arc::new(Sphere::default()));
+        }
+
+        it "should compute the intervals inside the volume, within the bounds" {
+            let mut intersections = vec![];
+            let mut intervals = |ray: &Ray, t_min, t_max| medium.intervals(ray, t_min, t_max, &mut intersections).collect::<Vec<_>>();
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            assert_eq!(intervals(&ray, 0.0, 10.0), vec![(4.0, 6.0)]);
+            assert_eq!(intervals(&ray, 0.0, 5.0), vec![(4.0, 5.0)]);
+            assert_eq!(intervals(&ray, 7.0, 10.0), vec![]);
+
+            let inside_ray = Ray::new((0, 0, 0), (0, 0, 1));
+
+            assert_eq!(intervals(&inside_ray, 0.0, 10.0), vec![(0.0, 1.0)]);
+
+            let missing_ray = Ray::new((0, 2, -5), (0, 0, 1));
+
+            assert_eq!(intervals(&missing_ray, 0.0, 10.0), vec![]);
+        }
+
+        it "should compute the transmittance in world units" {
+            let ray = Ray::new((0, 0, -5), (0, 0, 2));
+
+            assert_float_absolute_eq!(medium.transmittance(&ray, 0.0, 10.0, &mut vec![]), 0.36788, 1e-5);
+            assert_float_absolute_eq!(medium.transmittance(&ray, 0.0, 2.5, &mut vec![]), 0.60653, 1e-5);
+        }
+
+        it "should compute the phase function" {
+            assert_float_absolute_eq!(medium.phase(1.0), 0.07958, 1e-5);
+            assert_float_absolute_eq!(medium.phase(-1.0), 0.07958, 1e-5);
+
+            let forward_medium = Medium {
+                anisotropy: 0.5,
+                ..Medium::default()
+            };
+
+            assert_float_absolute_eq!(forward_medium.phase(1.0), 0.47746, 1e-5);
+            assert_float_absolute_eq!(forward_medium.phase(-1.0), 0.01768, 1e-5);
+        }
+    }
+}
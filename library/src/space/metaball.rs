@@ -0,0 +1,220 @@
+use super::{
+    shape, shape::private::ShapeLocal, BoundedShape, Bounds, Intersection, Ray, ShapeParent,
+    WorldTransformsCache,
+};
+use crate::{
+    lang::math::sqrt,
+    lang::Float,
+    math::{Normal3, Point3, Transform, Vector3},
+    properties::MaterialHandle,
+};
+
+// Number of samples of the field, between two consecutive influence boundaries along the ray; within
+// such span, the field is a polynomial of degree 6, so a handful of samples is enough to separate the
+// roots, in non-pathological cases.
+//
+const SAMPLES_PER_SPAN: usize = 16;
+
+const ROOT_REFINEMENT_STEPS: usize = 32;
+
+#[derive(Clone, Copy, Debug, SmartDefault)]
+pub struct MetaballCenter {
+    #[default(Point3::origin())]
+    pub position: Point3,
+    // Distance beyond which the center has no influence.
+    //
+    #[default(1.0)]
+    pub radius: Float,
+    // Negative weights subtract from the field.
+    //
+    #[default(1.0)]
+    pub weight: Float,
+}
+
+impl MetaballCenter {
+    // Uses the (1 - r²/R²)³ falloff, which smoothly decays to 0 at the influence radius, and is
+    // polynomial, which makes the gradient cheap.
+    //
+    fn field(&self, point: &Point3) -> Float {
+        let distance_squared = Self::distance_squared(point, &self.position);
+        let radius_squared = self.radius.powi(2);
+
+        if distance_squared >= radius_squared {
+            0.0
+        } else {
+            self.weight * (1.0 - distance_squared / radius_squared).powi(3)
+        }
+    }
+
+    fn gradient(&self, point: &Point3) -> Vector3 {
+        let offset = *point - &self.position;
+        let radius_squared = self.radius.powi(2);
+        let falloff = 1.0 - offset.dot_product(&offset) / radius_squared;
+
+        if falloff <= 0.0 {
+            Vector3::new(0, 0, 0)
+        } else {
+            offset * (-6.0 * self.weight * falloff.powi(2) / radius_squared)
+        }
+    }
+
+    fn distance_squared(point: &Point3, position: &Point3) -> Float {
+        let offset = *point - position;
+
+        offset.dot_product(&offset)
+    }
+
+    // Returns the (t1, t2) span where the ray is inside the influence sphere, if any.
+    //
+    fn influence_span(&self, ray: &Ray) -> Option<(Float, Float)> {
+        let center_to_ray = ray.origin - &self.position;
+
+        let a = ray.direction.dot_product(&ray.direction);
+        let b = 2.0 * ray.direction.dot_product(&center_to_ray);
+        let c = center_to_ray.dot_product(&center_to_ray) - self.radius.powi(2);
+
+        let discriminant = b.powi(2) - 4.0 * a * c;
+
+        if discriminant <= 0.0 {
+            None
+        } else {
+            let t1 = (-b - sqrt(discriminant)) / (2.0 * a);
+            let t2 = (-b + sqrt(discriminant)) / (2.0 * a);
+
+            Some((t1, t2))
+        }
+    }
+}
+
+// Blobby object: the surface is where the sum of the centers fields equals the threshold; the inside is
+// where it's greater.
+//
+// Intersections are found by root finding along the ray, limited to the spans within the influence
+// spheres (outside of them, the field is 0, so with a positive threshold there's no surface).
+//
+#[derive(Debug, ShapeAccessors, SmartDefault)]
+pub struct Metaball {
+    #[default(_code = "shape::new_shape_id()")]
+    pub id: u32,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
+
+    pub centers: Vec<MetaballCenter>,
+    #[default(0.5)]
+    pub threshold: Float,
+}
+
+impl Metaball {
+    // Field value, relative to the threshold; positive inside.
+    //
+    fn potential(&self, point: &Point3) -> Float {
+        let field = self
+            .centers
+            .iter()
+            .map(|center| center.field(point))
+            .sum::<Float>();
+
+        field - self.threshold
+    }
+
+    fn refine_root(&self, ray: &Ray, mut t_a: Float, mut t_b: Float, potential_a: Float) -> Float {
+        for _ in 0..ROOT_REFINEMENT_STEPS {
+            let t_middle = (t_a + t_b) / 2.0;
+
+            if self.potential(&ray.position(t_middle)).signum() == potential_a.signum() {
+                t_a = t_middle;
+            } else {
+                t_b = t_middle;
+            }
+        }
+
+        (t_a + t_b) / 2.0
+    }
+}
+
+impl ShapeLocal for Metaball {
+    // point: In object space.
+    //
+    // The field decreases going outwards, so the normal is the opposite of the gradient.
+    //
+    fn local_normal(&self, point: Point3, _intersection: &Intersection) -> Normal3 {
+        let gradient = self
+            .centers
+            .iter()
+            .fold(Vector3::new(0, 0, 0), |gradient, center| {
+                gradient + &center.gradient(&point)
+            });
+
+        (-gradient).into()
+    }
+
+    // ray: In object space.
+    //
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        // The boundaries of the influence spheres split the ray into spans where the field is smooth.
+
+        let mut boundaries = self
+            .centers
+            .iter()
+            .filter_map(|center| center.influence_span(ray))
+            .flat_map(|(t1, t2)| [t1, t2])
+            .collect::<Vec<_>>();
+
+        boundaries.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for span in boundaries.windows(2) {
+            let (span_start, span_end) = (span[0], span[1]);
+            let step = (span_end - span_start) / SAMPLES_PER_SPAN as Float;
+
+            if step <= 0.0 {
+                continue;
+            }
+
+            let mut t = span_start;
+            let mut potential = self.potential(&ray.position(t));
+
+            for i in 1..=SAMPLES_PER_SPAN {
+                let next_t = span_start + step * i as Float;
+                let next_potential = self.potential(&ray.position(next_t));
+
+                if next_potential.signum() != potential.signum() {
+                    intersections.push(Intersection {
+                        t: self.refine_root(ray, t, next_t, potential),
+                        uv: None,
+                        face: None,
+                        instance: None,
+                        material: None,
+                        object: self,
+                    });
+                }
+
+                t = next_t;
+                potential = next_potential;
+            }
+        }
+    }
+}
+
+impl BoundedShape for Metaball {
+    // The surface is enclosed by the union of the influence spheres.
+    //
+    fn local_bounds(&self) -> Bounds {
+        let mut bounds = Bounds::default();
+
+        for center in self.centers.iter() {
+            let radius = Vector3::new(center.radius, center.radius, center.radius);
+
+            Bounds::update_from_point(&mut bounds, &(center.position - &radius));
+            Bounds::update_from_point(&mut bounds, &(center.position + &radius));
+        }
+
+        bounds
+    }
+}
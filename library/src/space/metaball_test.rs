@@ -0,0 +1,125 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "Metaball" {
+        use crate::lang::{math::sqrt, ApproximateFloatOps};
+        use crate::math::*;
+        use crate::space::{*, shape::private::ShapeLocal};
+
+        // With a single center of weight 1 and radius 1, the surface is at (1 - r²)³ = threshold; with a
+        // threshold of 0.125, r = √0.5.
+
+        it "A ray strikes a single center at the threshold distance" {
+            let metaball = Metaball {
+                centers: vec![MetaballCenter::default()],
+                threshold: 0.125,
+                ..Metaball::default()
+            };
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = metaball.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert!(intersections[0].t.approximate_equals(5.0 - sqrt(0.5)));
+            assert!(intersections[1].t.approximate_equals(5.0 + sqrt(0.5)));
+        }
+
+        it "A ray misses the surface, while crossing the influence sphere" {
+            let metaball = Metaball {
+                centers: vec![MetaballCenter::default()],
+                threshold: 0.125,
+                ..Metaball::default()
+            };
+            let ray = Ray::new((0.0, 0.8, -5.0), (0, 0, 1));
+
+            assert_eq!(metaball.local_intersections(&ray).len(), 0);
+        }
+
+        it "Close centers blend together" {
+            let centers = vec![
+                MetaballCenter {
+                    position: Point3::new(-0.6, 0.0, 0.0),
+                    ..MetaballCenter::default()
+                },
+                MetaballCenter {
+                    position: Point3::new(0.6, 0.0, 0.0),
+                    ..MetaballCenter::default()
+                },
+            ];
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            // Each center alone doesn't reach the origin: (1 - 0.36)³ ≈ 0.262 < 0.4.
+            //
+            let single = Metaball {
+                centers: vec![centers[0]],
+                threshold: 0.4,
+                ..Metaball::default()
+            };
+
+            assert_eq!(single.local_intersections(&ray).len(), 0);
+
+            let blended = Metaball {
+                centers,
+                threshold: 0.4,
+                ..Metaball::default()
+            };
+
+            let intersections = blended.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+        }
+
+        it "A negative weight carves the surface" {
+            let metaball = Metaball {
+                centers: vec![
+                    MetaballCenter {
+                        radius: 2.0,
+                        ..MetaballCenter::default()
+                    },
+                    MetaballCenter {
+                        weight: -1.0,
+                        radius: 0.5,
+                        ..MetaballCenter::default()
+                    },
+                ],
+                threshold: 0.125,
+                ..Metaball::default()
+            };
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            assert_eq!(metaball.local_intersections(&ray).len(), 4);
+        }
+
+        it "The normal is the opposite of the field gradient" {
+            let metaball = Metaball {
+                centers: vec![MetaballCenter::default()],
+                threshold: 0.125,
+                ..Metaball::default()
+            };
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            let intersections = metaball.local_intersections(&ray);
+            let normal = metaball.local_normal(ray.position(intersections[0].t), &intersections[0]);
+
+            assert_eq!(normal.normalize(), Normal3::new(0, 0, -1));
+        }
+
+        it "Computes the bounds from the influence spheres" {
+            let metaball = Metaball {
+                centers: vec![
+                    MetaballCenter::default(),
+                    MetaballCenter {
+                        position: Point3::new(2, 0, 0),
+                        radius: 2.0,
+                        ..MetaballCenter::default()
+                    },
+                ],
+                ..Metaball::default()
+            };
+            let bounds = metaball.local_bounds();
+
+            assert_eq!(bounds.min, Point3::new(-1, -2, -2));
+            assert_eq!(bounds.max, Point3::new(4, 2, 2));
+        }
+    }
+}
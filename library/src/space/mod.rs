@@ -1,39 +1,58 @@
+mod bezier_patch;
 mod bounded_shape;
 mod bounds;
 mod camera;
 mod cone;
 pub mod csg;
 mod cube;
+mod curve;
 mod cylinder;
+mod fog;
 mod group;
+mod instance;
 mod intersection;
 mod intersection_state;
+mod medium;
+mod metaball;
 mod plane;
 mod point_light;
 mod ray;
+mod scene_graph;
 mod shape;
 mod sphere;
 mod triangle;
+mod triangle_mesh;
 mod world;
 
+pub use bezier_patch::BezierPatch;
 pub use bounded_shape::BoundedShape;
 pub use bounds::Bounds;
 pub use camera::Camera;
 pub use cone::Cone;
 pub use csg::Csg;
 pub use cube::Cube;
+pub use curve::Curve;
 pub use cylinder::Cylinder;
+pub use fog::Fog;
 pub use group::Group;
-pub use intersection::Intersection;
+pub use instance::Instance;
+pub use intersection::{Intersection, TextureCoordinates};
 pub use intersection_state::IntersectionState;
+pub use medium::Medium;
+pub use metaball::{Metaball, MetaballCenter};
 pub use plane::Plane;
 pub use point_light::PointLight;
 pub use ray::Ray;
-pub use shape::Shape;
+pub use scene_graph::{NodeId, SceneGraph, SceneGraphError, SceneNode};
+pub use shape::{Shape, ShapeAccessors, ShapeParent, WorldTransforms, WorldTransformsCache};
 pub use sphere::Sphere;
 pub use triangle::Triangle;
+pub use triangle_mesh::{MeshFace, TriangleMesh};
 pub use world::World;
 
+#[cfg(test)]
+mod bezier_patch_test;
+
 #[cfg(test)]
 mod camera_test;
 
@@ -46,24 +65,39 @@ mod cube_test;
 #[cfg(test)]
 mod cone_test;
 
+#[cfg(test)]
+mod curve_test;
+
 #[cfg(test)]
 mod cylinder_test;
 
 #[cfg(test)]
 mod group_test;
 
+#[cfg(test)]
+mod instance_test;
+
 #[cfg(test)]
 mod intersection_state_test;
 
 #[cfg(test)]
 mod intersection_test;
 
+#[cfg(test)]
+mod medium_test;
+
+#[cfg(test)]
+mod metaball_test;
+
 #[cfg(test)]
 mod plane_test;
 
 #[cfg(test)]
 mod ray_test;
 
+#[cfg(test)]
+mod scene_graph_test;
+
 #[cfg(test)]
 mod shape_test;
 
@@ -73,5 +107,8 @@ mod sphere_test;
 #[cfg(test)]
 mod triangle_test;
 
+#[cfg(test)]
+mod triangle_mesh_test;
+
 #[cfg(test)]
 mod world_test;
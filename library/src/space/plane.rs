@@ -1,41 +1,48 @@
-use std::sync::Weak;
-
 use super::{
     shape::{self, private::ShapeLocal},
-    BoundedShape, Bounds, Intersection, Ray, Shape,
+    BoundedShape, Bounds, Intersection, Ray, ShapeParent, WorldTransformsCache,
+};
+use crate::{
+    lang::ApproximateFloatOps,
+    lang::Float,
+    math::{Normal3, Point3, Transform},
+    properties::MaterialHandle,
 };
-use crate::{lang::ApproximateFloat64Ops, math::Matrix, math::Tuple, properties::Material};
 
 #[derive(Debug, ShapeAccessors, SmartDefault)]
 pub struct Plane {
     #[default(_code = "shape::new_shape_id()")]
     pub id: u32,
-    #[default(Weak::<Self>::new())]
-    pub parent: Weak<dyn Shape>,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
-    #[default(Material::default())]
-    pub material: Material,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
 }
 
 impl ShapeLocal for Plane {
-    fn local_normal(&self, _point: Tuple, _intersection: &Intersection) -> Tuple {
-        Tuple::vector(0, 1, 0)
+    fn local_normal(&self, _point: Point3, _intersection: &Intersection) -> Normal3 {
+        Normal3::new(0, 1, 0)
     }
 
     // ray: In object space.
     //
-    fn local_intersections(&self, ray: &Ray) -> Vec<Intersection> {
-        if ray.direction.y.within_epsilon() {
-            vec![]
-        } else {
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        if !ray.direction.y.within_epsilon() {
             let t = -ray.origin.y / ray.direction.y;
 
-            vec![Intersection {
+            intersections.push(Intersection {
                 t,
                 uv: None,
+                face: None,
+                instance: None,
+                material: None,
                 object: self,
-            }]
+            });
         }
     }
 }
@@ -43,8 +50,8 @@ impl ShapeLocal for Plane {
 impl BoundedShape for Plane {
     fn local_bounds(&self) -> Bounds {
         Bounds {
-            min: Tuple::point(f64::NEG_INFINITY, 0, f64::NEG_INFINITY),
-            max: Tuple::point(f64::INFINITY, 0, f64::INFINITY),
+            min: Point3::new(Float::NEG_INFINITY, 0, Float::NEG_INFINITY),
+            max: Point3::new(Float::INFINITY, 0, Float::INFINITY),
         }
     }
 }
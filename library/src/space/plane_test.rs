@@ -14,14 +14,17 @@ demonstrate! {
             let default_intersection = Intersection {
                 t: 0.0,
                 uv: None,
+                face: None,
+                instance: None,
+                material: None,
                 object: &Plane::default(),
             };
 
-            let expected_normal = Tuple::vector(0, 1, 0);
+            let expected_normal = Normal3::new(0, 1, 0);
 
-            assert_eq!(plane.local_normal(Tuple::point(0, 0, 0), &default_intersection), expected_normal);
-            assert_eq!(plane.local_normal(Tuple::point(10, 0, -10), &default_intersection), expected_normal);
-            assert_eq!(plane.local_normal(Tuple::point(-5, 0, 150), &default_intersection), expected_normal);
+            assert_eq!(plane.local_normal(Point3::new(0, 0, 0), &default_intersection), expected_normal);
+            assert_eq!(plane.local_normal(Point3::new(10, 0, -10), &default_intersection), expected_normal);
+            assert_eq!(plane.local_normal(Point3::new(-5, 0, 150), &default_intersection), expected_normal);
         }
 
         context "intersections" {
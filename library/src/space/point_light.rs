@@ -1,14 +1,14 @@
-use crate::{math::Tuple, properties::Color};
+use crate::{math::Point3, properties::Color};
 
 pub struct PointLight {
-    pub position: Tuple,
+    pub position: Point3,
     pub intensity: Color,
 }
 
 impl PointLight {
     pub fn new(position: (i32, i32, i32), intensity: (i32, i32, i32)) -> Self {
         Self {
-            position: Tuple::point(position.0, position.1, position.2),
+            position: Point3::new(position.0, position.1, position.2),
             intensity: Color::new(intensity.0, intensity.1, intensity.2),
         }
     }
@@ -1,53 +1,102 @@
 use super::{Intersection, IntersectionState};
 use crate::{
-    math::{Matrix, Tuple, EPSILON},
+    lang::Float,
+    math::{Matrix4, Point3, Transform, Vector3},
     properties::REFRACTIVE_INDEX_VACUUM,
-    space::Shape,
 };
 
-#[derive(PartialEq, Debug)]
+// Lower bound of the self-intersection offset scale (see Ray#hit_offset()); it prevents the offset
+// from vanishing when the hit is at the world origin.
+//
+const MIN_OFFSET_SCALE: Float = 1e-3;
+
+// Relative rounding error bound of the hit point coordinates (see Ray#hit_offset()), in ulps; it
+// covers the (accumulated) error of the intersection algorithms.
+//
+const POINT_ERROR_FACTOR: Float = 16.0 * Float::EPSILON;
+
+// Relative rounding error of the distance travelled by the ray (see Ray#hit_offset()).
+//
+const DISTANCE_ERROR_FACTOR: Float = 16.0 * Float::EPSILON;
+
+// The [t_min, t_max) interval bounds the valid intersections; shapes use it to prune the search (see
+// Shape#intersections_into()). Since the direction is not normalized, `t` is preserved by the (affine)
+// transformations, so the interval applies unchanged in object space.
+//
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Ray {
-    pub origin: Tuple,
-    pub direction: Tuple,
+    pub origin: Point3,
+    pub direction: Vector3,
+    pub t_min: Float,
+    pub t_max: Float,
 }
 
 impl Ray {
     pub fn new<T: Into<f64>, U: Into<f64>>(origin: (T, T, T), direction: (U, U, U)) -> Self {
+        Self::unbounded(
+            Point3::new(origin.0, origin.1, origin.2),
+            Vector3::new(direction.0, direction.1, direction.2),
+        )
+    }
+
+    // Interval over the whole line, as in the book.
+    //
+    pub fn unbounded(origin: Point3, direction: Vector3) -> Self {
         Ray {
-            origin: Tuple::point(origin.0, origin.1, origin.2),
-            direction: Tuple::vector(direction.0, direction.1, direction.2),
+            origin,
+            direction,
+            t_min: Float::NEG_INFINITY,
+            t_max: Float::INFINITY,
+        }
+    }
+
+    pub fn with_interval(&self, t_min: Float, t_max: Float) -> Self {
+        Self {
+            t_min,
+            t_max,
+            ..*self
         }
     }
 
-    pub fn position<T: Into<f64>>(&self, t: T) -> Tuple {
-        self.origin + &(self.direction * t.into())
+    pub fn contains(&self, t: Float) -> bool {
+        self.t_min <= t && t < self.t_max
+    }
+
+    pub fn position<T: Into<f64>>(&self, t: T) -> Point3 {
+        self.origin + &(self.direction * t.into() as Float)
     }
 
     pub fn translate<T: Into<f64>>(&self, x: T, y: T, z: T) -> Self {
         Self {
-            origin: self.origin.translate(x, y, z),
+            origin: Matrix4::translation(x, y, z) * &self.origin,
             direction: self.direction,
+            ..*self
         }
     }
 
     pub fn scale<T: Into<f64> + Copy>(&self, x: T, y: T, z: T) -> Self {
         Self {
-            origin: self.origin.scale(x, y, z),
-            direction: self.direction.scale(x, y, z),
+            origin: Matrix4::scaling(x, y, z) * &self.origin,
+            direction: Matrix4::scaling(x, y, z) * &self.direction,
+            ..*self
         }
     }
 
-    pub fn inverse_transform(&self, transform: &Matrix) -> Self {
+    pub fn inverse_transform(&self, transform: &Transform) -> Self {
         let inverse_transform = transform.inverse();
 
         Self {
-            origin: &inverse_transform * &self.origin,
-            direction: &inverse_transform * &self.direction,
+            origin: inverse_transform * &self.origin,
+            direction: inverse_transform * &self.direction,
+            ..*self
         }
     }
 
     // In the book, this is split between `intersection(t, s)` and `prepare_computations(i, r)`.
     //
+    // With a bump map, `normalv` is the perturbed (shading) normal, used by lighting, reflection and
+    // refraction.
+    //
     pub fn intersection_state<'a>(
         &self,
         intersection: &'a Intersection,
@@ -55,21 +104,35 @@ impl Ray {
     ) -> IntersectionState<'a> {
         let point = self.position(intersection.t);
         let eyev = -self.direction;
-        let mut normalv = intersection.object.normal(&point, intersection);
-        let inside = if normalv.dot_product(&eyev) >= 0.0 {
+        let material = intersection.material();
+        let surface = material.surface_at(&intersection.world_to_object(&point));
+        let mut geometric_normalv = intersection.normal(&point);
+        let mut normalv = match &material.bump_map {
+            Some(bump_map) => intersection.bumped_normal(&point, bump_map),
+            None => geometric_normalv,
+        };
+        let inside = if geometric_normalv.dot_product(&eyev) >= 0.0 {
             false
         } else {
+            geometric_normalv = -geometric_normalv;
             normalv = -normalv;
             true
         };
-        let over_point = point + &(normalv * EPSILON);
-        let under_point = point - &(normalv * EPSILON);
+        // The points are offset along the geometric normal, since the bumped one doesn't reflect the
+        // actual surface, and could cause self-intersections.
+        //
+        let offset = self.hit_offset(&point, intersection.t);
+        let over_point = point + &(geometric_normalv * offset);
+        let under_point = point - &(geometric_normalv * offset);
         let reflectv = self.direction.reflect(&normalv);
         let (n1, n2) = Ray::refraction_indexes(intersection, intersections);
 
         IntersectionState {
             t: intersection.t,
             object: intersection.object,
+            instance: intersection.instance,
+            material,
+            surface,
             point,
             over_point,
             under_point,
@@ -82,52 +145,76 @@ impl Ray {
         }
     }
 
-    // In the book, this is part of `prepare_computations(i, r)`.
+    // Distance by which a hit point is offset, in order to avoid self-intersections.
     //
-    pub fn refraction_indexes(hit: &Intersection, intersections: &[Intersection]) -> (f64, f64) {
-        let mut containers = Vec::<&dyn Shape>::new();
-        let mut comps = (None, None);
-
-        for intersection in intersections.iter() {
-            if intersection == hit {
-                let container_last = containers.last();
-
-                if let Some(object) = container_last {
-                    comps.0 = Some(object.material().refractive_index);
-                } else {
-                    comps.0 = Some(REFRACTIVE_INDEX_VACUUM);
-                }
-            }
-
-            if let Some(pos) = containers
-                .iter()
-                .position(|object| object.id() == intersection.object.id())
-            {
-                containers.remove(pos);
-            } else {
-                containers.push(intersection.object);
-            }
-
-            if intersection == hit {
-                let container_last = containers.last();
-
-                if let Some(object) = container_last {
-                    comps.1 = Some(object.material().refractive_index);
-                } else {
-                    comps.1 = Some(REFRACTIVE_INDEX_VACUUM);
-                }
-
-                break;
-            }
-        }
+    // The rounding error of the hit point is relative to the magnitude of its coordinates, so a fixed
+    // offset causes acne on large scenes, and light leaks on tiny ones; the offset is therefore a few
+    // ulps of the largest (absolute) coordinate of the point, rather than a multiple of EPSILON, which
+    // would push the points off the surface far from the world origin. The error of `t` adds up, but
+    // it's relative to the distance travelled, and similarly bounded by a few ulps.
+    //
+    pub(crate) fn hit_offset(&self, point: &Point3, t: Float) -> Float {
+        let point_scale = [point.x, point.y, point.z]
+            .iter()
+            .fold(MIN_OFFSET_SCALE, |scale, coordinate| {
+                scale.max(coordinate.abs())
+            });
+        let distance_error = DISTANCE_ERROR_FACTOR * t.abs() * self.direction.magnitude();
+
+        POINT_ERROR_FACTOR * point_scale + distance_error
+    }
 
-        // In many tests, we don't care about refraction, so we pass no intersections and get bogus
-        // values.
-        //
+    // In the book, this is part of `prepare_computations(i, r)`.
+    //
+    // In many tests, we don't care about refraction, so we pass no intersections and get bogus values.
+    //
+    pub fn refraction_indexes(
+        hit: &Intersection,
+        intersections: &[Intersection],
+    ) -> (Float, Float) {
         if intersections.is_empty() {
-            (REFRACTIVE_INDEX_VACUUM, REFRACTIVE_INDEX_VACUUM)
-        } else {
-            (comps.0.unwrap(), comps.1.unwrap())
+            return (REFRACTIVE_INDEX_VACUUM, REFRACTIVE_INDEX_VACUUM);
         }
+
+        let hit_i = intersections
+            .iter()
+            .position(|intersection| intersection == hit)
+            .expect("hit not found in the intersections");
+
+        let refractive_index = |container: Option<&Intersection>| {
+            container.map_or(REFRACTIVE_INDEX_VACUUM, |container| {
+                container.material().refractive_index
+            })
+        };
+
+        (
+            refractive_index(Self::last_container(&intersections[..hit_i])),
+            refractive_index(Self::last_container(&intersections[..=hit_i])),
+        )
+    }
+
+    // The containers are the objects entered and not exited yet; the book keeps them in a list, and uses
+    // the last one (the most recently entered).
+    //
+    // In order to avoid allocating the list, the intersections are scanned backwards: the last container
+    // is the first intersection that is the last one of its object, with the object crossed an odd number
+    // of times. This is quadratic, but the lists are short.
+    //
+    fn last_container<'a, 'b>(
+        intersections: &'b [Intersection<'a>],
+    ) -> Option<&'b Intersection<'a>> {
+        intersections
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(i, candidate)| {
+                let same_container = |other: &&Intersection| candidate.same_object(other);
+
+                !intersections[i + 1..]
+                    .iter()
+                    .any(|other| same_container(&other))
+                    && intersections.iter().filter(same_container).count() % 2 == 1
+            })
+            .map(|(_, container)| container)
     }
 }
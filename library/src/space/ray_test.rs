@@ -10,27 +10,30 @@ demonstrate! {
         it "should compute a position at t" {
             let ray = Ray::new((2, 3, 4), (1, 0, 0));
 
-            assert_eq!(ray.position(0), Tuple::point(2, 3, 4));
-            assert_eq!(ray.position(1), Tuple::point(3, 3, 4));
-            assert_eq!(ray.position(-1), Tuple::point(1, 3, 4));
-            assert_eq!(ray.position(2.5), Tuple::point(4.5, 3, 4));
+            assert_eq!(ray.position(0), Point3::new(2, 3, 4));
+            assert_eq!(ray.position(1), Point3::new(3, 3, 4));
+            assert_eq!(ray.position(-1), Point3::new(1, 3, 4));
+            assert_eq!(ray.position(2.5), Point3::new(4.5, 3, 4));
         }
 
         context "intersection state" {
             context "should be computed from an intersection and an object" {
                 it "with the ray outside the object" {
                     let ray = Ray::new((0, 0, -5), (0, 0, 1));
-                    let intersection = Intersection { t: 4.0, uv: None, object: &Sphere::default() };
+                    let intersection = Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: &Sphere::default() };
 
                     let expected_intersection_state = IntersectionState {
                         t: intersection.t.clone(),
                         object: intersection.object,
-                        point: Tuple::point(0, 0, -1),
-                        over_point: Tuple::point(0, 0, -1),
-                        under_point: Tuple::point(0, 0, -0.9999),
-                        eyev: Tuple::vector(0, 0, -1),
-                        normalv: Tuple::vector(0, 0, -1),
-                        reflectv: Tuple::vector(0, 0, -1),
+                        instance: None,
+                        material: intersection.object.material(),
+                        surface: intersection.object.material().surface_at(&Point3::new(0, 0, -1)),
+                        point: Point3::new(0, 0, -1),
+                        over_point: Point3::new(0, 0, -1.0 - EPSILON),
+                        under_point: Point3::new(0, 0, -1.0 + EPSILON),
+                        eyev: Vector3::new(0, 0, -1),
+                        normalv: Normal3::new(0, 0, -1),
+                        reflectv: Vector3::new(0, 0, -1),
                         n1: REFRACTIVE_INDEX_VACUUM,
                         n2: REFRACTIVE_INDEX_VACUUM,
                         inside: false,
@@ -43,17 +46,20 @@ demonstrate! {
 
                 it "with the ray inside the object" {
                     let ray = Ray::new((0, 0, 0), (0, 0, 1));
-                    let intersection = Intersection { t: 1.0, uv: None, object: &Sphere::default() };
+                    let intersection = Intersection { t: 1.0, uv: None, face: None, instance: None, material: None, object: &Sphere::default() };
 
                     let expected_intersection_state = IntersectionState {
                         t: intersection.t,
                         object: intersection.object,
-                        point: Tuple::point(0, 0, 1),
-                        over_point: Tuple::point(0, 0, 0.9999),
-                        under_point: Tuple::point(0, 0, 1),
-                        eyev: Tuple::vector(0, 0, -1),
-                        normalv: Tuple::vector(0, 0, -1),
-                        reflectv: Tuple::vector(0, 0, -1),
+                        instance: None,
+                        material: intersection.object.material(),
+                        surface: intersection.object.material().surface_at(&Point3::new(0, 0, 1)),
+                        point: Point3::new(0, 0, 1),
+                        over_point: Point3::new(0, 0, 1.0 - EPSILON),
+                        under_point: Point3::new(0, 0, 1.0 + EPSILON),
+                        eyev: Vector3::new(0, 0, -1),
+                        normalv: Normal3::new(0, 0, -1),
+                        reflectv: Vector3::new(0, 0, -1),
                         n1: REFRACTIVE_INDEX_VACUUM,
                         n2: REFRACTIVE_INDEX_VACUUM,
                         inside: true,
@@ -64,13 +70,103 @@ demonstrate! {
                     assert_eq!(actual_intersection_state, expected_intersection_state);
                 }
 
+                it "with the offset scaled up, on large scenes" {
+                    let unit_sphere = Sphere::default();
+                    let unit_ray = Ray::new((0.0, 0.0, -1.1), (0, 0, 1));
+                    let unit_intersection = Intersection { t: 0.1, uv: None, face: None, instance: None, material: None, object: &unit_sphere };
+                    let sphere = Sphere {
+                        transform: Matrix4::scaling(1000, 1000, 1000).into(),
+                        ..Sphere::default()
+                    };
+                    let ray = Ray::new((0, 0, -1100), (0, 0, 1));
+                    let intersection = Intersection { t: 100.0, uv: None, face: None, instance: None, material: None, object: &sphere };
+
+                    let unit_intersection_state = unit_ray.intersection_state(&unit_intersection, &[]);
+                    let intersection_state = ray.intersection_state(&intersection, &[]);
+
+                    let unit_offset = unit_intersection_state.under_point.z - unit_intersection_state.point.z;
+
+                    assert_float_relative_eq!(intersection_state.point.z - intersection_state.over_point.z, 1000.0 * unit_offset, 1e-2);
+                    assert_float_relative_eq!(intersection_state.under_point.z - intersection_state.point.z, 1000.0 * unit_offset, 1e-2);
+                }
+
+                it "with the offset not scaled up by a distant ray origin" {
+                    let sphere = Sphere::default();
+                    let ray = Ray::new((0, 0, -10000), (0, 0, 1));
+                    let intersection = Intersection { t: 9999.0, uv: None, face: None, instance: None, material: None, object: &sphere };
+
+                    let intersection_state = ray.intersection_state(&intersection, &[]);
+
+                    assert!(intersection_state.point.z - intersection_state.over_point.z < 100.0 * EPSILON);
+                    assert!(intersection_state.under_point.z - intersection_state.point.z < 100.0 * EPSILON);
+                }
+
+                it "with the offset scaled down, on tiny scenes" {
+                    let unit_sphere = Sphere::default();
+                    let unit_ray = Ray::new((0, 0, -5), (0, 0, 1));
+                    let unit_intersection = Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: &unit_sphere };
+                    let sphere = Sphere {
+                        transform: Matrix4::scaling(0.001, 0.001, 0.001).into(),
+                        ..Sphere::default()
+                    };
+                    let ray = Ray::new((0.0, 0.0, -0.005), (0, 0, 1));
+                    let intersection = Intersection { t: 0.004, uv: None, face: None, instance: None, material: None, object: &sphere };
+
+                    let unit_intersection_state = unit_ray.intersection_state(&unit_intersection, &[]);
+                    let intersection_state = ray.intersection_state(&intersection, &[]);
+
+                    let unit_offset = unit_intersection_state.under_point.z - unit_intersection_state.point.z;
+
+                    assert_float_relative_eq!(intersection_state.point.z - intersection_state.over_point.z, 0.001 * unit_offset, 1e-2);
+                    assert_float_relative_eq!(intersection_state.under_point.z - intersection_state.point.z, 0.001 * unit_offset, 1e-2);
+                }
+
+                it "with the offset not pushing the points off the surface, far from the world origin" {
+                    let sphere = Sphere {
+                        transform: Matrix4::translation(10000, 0, 0).into(),
+                        ..Sphere::default()
+                    };
+                    let ray = Ray::new((10000, 0, -5), (0, 0, 1));
+                    let intersection = Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: &sphere };
+
+                    let intersection_state = ray.intersection_state(&intersection, &[]);
+
+                    assert!(intersection_state.over_point.z < intersection_state.point.z);
+                    assert!(intersection_state.under_point.z > intersection_state.point.z);
+                    assert!(intersection_state.point.z - intersection_state.over_point.z < 0.1);
+                    assert!(intersection_state.under_point.z - intersection_state.point.z < 0.1);
+                }
+
+                it "with the normal perturbed by the bump map, and the points offset by the geometric one" {
+                    let height_pattern = RampPattern {
+                        ramp: ColorRamp::new(vec![(-10.0, COLOR_BLACK), (10.0, COLOR_WHITE)], RampInterpolation::Linear),
+                        ..RampPattern::default()
+                    };
+                    let object = Plane {
+                        material: Material {
+                            bump_map: Some(BumpMap::Height { pattern: Box::new(height_pattern), strength: 20.0 }),
+                            ..Material::default()
+                        }.into(),
+                        ..Plane::default()
+                    };
+                    let ray = Ray::new((0.3, 1.0, 0.2), (0, -1, 0));
+                    let intersection = Intersection { t: 1.0, uv: None, face: None, instance: None, material: None, object: &object };
+
+                    let intersection_state = ray.intersection_state(&intersection, &[]);
+
+                    assert_eq!(intersection_state.normalv, Normal3::new(-1, 1, 0).normalize());
+                    assert_eq!(intersection_state.reflectv, Vector3::new(-1, 0, 0));
+                    assert_eq!(intersection_state.over_point, Point3::new(0.3, EPSILON, 0.2));
+                    assert!(!intersection_state.inside);
+                }
+
                 it "with reflection" {
                     let object = Plane::default();
                     let ray = Ray::new((0, 1, -1), (0.0, -sqrt(2) / 2.0, sqrt(2) / 2.0));
-                    let intersection = Intersection { t: sqrt(2), uv: None, object: &object };
+                    let intersection = Intersection { t: sqrt(2), uv: None, face: None, instance: None, material: None, object: &object };
 
                     let actual_intersection_state = ray.intersection_state(&intersection, &[]);
-                    let expected_reflectv = Tuple::vector(0.0, sqrt(2) / 2.0, sqrt(2) / 2.0);
+                    let expected_reflectv = Vector3::new(0.0, sqrt(2) / 2.0, sqrt(2) / 2.0);
 
                     assert_eq!(actual_intersection_state.reflectv, expected_reflectv);
                 }
@@ -94,5 +190,32 @@ demonstrate! {
                 assert_eq!(ray.scale(2, 3, 4), expected_ray);
             }
         } // context "transformations"
+
+        context "interval" {
+            it "should be unbounded by default" {
+                let ray = Ray::new((0, 0, 0), (0, 0, 1));
+
+                assert!(ray.contains(-1e9));
+                assert!(ray.contains(1e9));
+            }
+
+            it "should include t_min, and exclude t_max" {
+                let ray = Ray::new((0, 0, 0), (0, 0, 1)).with_interval(1.0, 2.0);
+
+                assert!(!ray.contains(0.5));
+                assert!(ray.contains(1.0));
+                assert!(ray.contains(1.5));
+                assert!(!ray.contains(2.0));
+            }
+
+            it "should be preserved by the transformations" {
+                let ray = Ray::new((1, 2, 3), (0, 1, 0)).with_interval(1.0, 2.0);
+
+                let transformed_ray = ray.scale(2, 3, 4).translate(3, 4, 5);
+
+                assert_eq!(transformed_ray.t_min, 1.0);
+                assert_eq!(transformed_ray.t_max, 2.0);
+            }
+        } // context "interval"
     }
 }
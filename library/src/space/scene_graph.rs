@@ -0,0 +1,249 @@
+use std::{error::Error, fmt, sync::Arc};
+
+use super::{
+    csg::{CutMaterial, Operation},
+    Csg, Group, Shape,
+};
+use crate::math::Matrix4;
+
+// Handle to a node of a SceneGraph; it stays valid (and is never reused) after other nodes are removed.
+//
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NodeId(usize);
+
+pub enum SceneNode {
+    // Leaf shape; it can't have children in the graph. It's shared with the built trees, so that the
+    // graph can be built again; it can be modified in place (via Arc::get_mut()) only once the
+    // previously built trees have been dropped, otherwise the content must be replaced.
+    // Containers with children (groups, CSGs) can't be leaves (see SceneGraphError); their content must
+    // be added as graph nodes.
+    //
+    Shape(Arc<dyn Shape>),
+    Group {
+        transform: Matrix4,
+    },
+    // The children order is meaningful; the first child is the base (see Csg). The cut material applies
+    // to all the children.
+    //
+    Csg {
+        operation: Operation,
+        cut_material: CutMaterial,
+        transform: Matrix4,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SceneGraphError {
+    // The leaf shape is a container with children; the children reference it (as parent), so its world
+    // transforms, and the ones of its descendants, can't be invalidated on build.
+    //
+    ContainerLeaf(NodeId),
+}
+
+impl fmt::Display for SceneGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SceneGraphError::ContainerLeaf(node_id) => write!(
+                f,
+                "the leaf shape of node {:?} is a container with children; add its content as graph nodes",
+                node_id
+            ),
+        }
+    }
+}
+
+impl Error for SceneGraphError {}
+
+struct Node {
+    content: SceneNode,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+// Mutable scene tree, stored in a flat arena, where the parent/children relationships are node handles.
+//
+// The shapes tree (Group/Csg with parent references) is immutable once built, so the graph is the
+// source of truth for the structure, which is edited here (adding, removing and reparenting nodes);
+// `build()` converts it to a shapes tree, and can be invoked any number of times. The editing cycle is:
+//
+// 1. drop the previously built trees (e.g. clear the World objects);
+// 2. edit the graph;
+// 3. build it, and set the result as World objects;
+// 4. commit the World (see World#commit()); this is done anyway by Camera#render().
+//
+// The leaf shapes are shared with the built trees; since their world transforms change when the tree
+// changes, they're invalidated on build, which requires the previously built trees to be dropped.
+//
+// Invalid operations (using removed nodes, adding children to shapes, creating cycles) are programming
+// errors, so they panic.
+//
+#[derive(Default)]
+pub struct SceneGraph {
+    nodes: Vec<Option<Node>>,
+}
+
+impl SceneGraph {
+    // parent: If None, the node is added as root.
+    //
+    pub fn add(&mut self, parent: Option<NodeId>, content: SceneNode) -> NodeId {
+        let node_id = NodeId(self.nodes.len());
+
+        self.nodes.push(Some(Node {
+            content,
+            parent: None,
+            children: vec![],
+        }));
+
+        if let Some(parent) = parent {
+            self.attach(node_id, parent);
+        }
+
+        node_id
+    }
+
+    // Removes the node, along with its descendants.
+    //
+    pub fn remove(&mut self, node_id: NodeId) {
+        self.detach(node_id);
+
+        let mut pending = vec![node_id];
+
+        while let Some(current_id) = pending.pop() {
+            let node = self.nodes[current_id.0]
+                .take()
+                .expect("the node has been removed");
+
+            pending.extend(node.children);
+        }
+    }
+
+    // Moves the node (along with its descendants) under the new parent, as last child; if the new parent
+    // is None, the node becomes a root.
+    //
+    pub fn reparent(&mut self, node_id: NodeId, new_parent: Option<NodeId>) {
+        if let Some(new_parent) = new_parent {
+            let mut ancestor = Some(new_parent);
+
+            while let Some(ancestor_id) = ancestor {
+                if ancestor_id == node_id {
+                    panic!("a node can't be moved under itself or its descendants");
+                }
+
+                ancestor = self.node(ancestor_id).parent;
+            }
+        }
+
+        self.detach(node_id);
+
+        if let Some(new_parent) = new_parent {
+            self.attach(node_id, new_parent);
+        }
+    }
+
+    pub fn parent(&self, node_id: NodeId) -> Option<NodeId> {
+        self.node(node_id).parent
+    }
+
+    pub fn children(&self, node_id: NodeId) -> &[NodeId] {
+        &self.node(node_id).children
+    }
+
+    pub fn content(&self, node_id: NodeId) -> &SceneNode {
+        &self.node(node_id).content
+    }
+
+    pub fn content_mut(&mut self, node_id: NodeId) -> &mut SceneNode {
+        &mut self.node_mut(node_id).content
+    }
+
+    pub fn roots(&self) -> Vec<NodeId> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| match node {
+                Some(node) if node.parent.is_none() => Some(NodeId(i)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Converts the roots (and their descendants) to shapes, typically for use as World objects.
+    //
+    // Returns an error if a leaf shape is a container with children, which are detected by their
+    // (weak) references to the parent. Panics if a leaf shape is still referenced by a previously built
+    // tree (see the editing cycle).
+    //
+    pub fn build(&mut self) -> Result<Vec<Arc<dyn Shape>>, SceneGraphError> {
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            if let Some(Node {
+                content: SceneNode::Shape(shape),
+                ..
+            }) = node
+            {
+                if Arc::weak_count(shape) > 0 {
+                    return Err(SceneGraphError::ContainerLeaf(NodeId(i)));
+                }
+
+                Arc::get_mut(shape)
+                    .expect("the previously built trees must be dropped before building again")
+                    .world_transforms_mut()
+                    .invalidate();
+            }
+        }
+
+        Ok(self
+            .roots()
+            .into_iter()
+            .map(|root_id| self.build_node(root_id))
+            .collect())
+    }
+
+    fn build_node(&self, node_id: NodeId) -> Arc<dyn Shape> {
+        let node = self.node(node_id);
+
+        let children = node
+            .children
+            .iter()
+            .map(|child_id| self.build_node(*child_id))
+            .collect::<Vec<_>>();
+
+        match &node.content {
+            SceneNode::Shape(shape) => Arc::clone(shape),
+            SceneNode::Group { transform } => Group::new(*transform, children),
+            SceneNode::Csg {
+                operation,
+                cut_material,
+                transform,
+            } => Csg::with_children(*operation, *cut_material, children, *transform),
+        }
+    }
+
+    fn attach(&mut self, node_id: NodeId, parent: NodeId) {
+        if let SceneNode::Shape(_) = self.node(parent).content {
+            panic!("only groups and CSGs can have children");
+        }
+
+        self.node_mut(parent).children.push(node_id);
+        self.node_mut(node_id).parent = Some(parent);
+    }
+
+    fn detach(&mut self, node_id: NodeId) {
+        if let Some(parent) = self.node_mut(node_id).parent.take() {
+            self.node_mut(parent)
+                .children
+                .retain(|child_id| *child_id != node_id);
+        }
+    }
+
+    fn node(&self, node_id: NodeId) -> &Node {
+        self.nodes[node_id.0]
+            .as_ref()
+            .expect("the node has been removed")
+    }
+
+    fn node_mut(&mut self, node_id: NodeId) -> &mut Node {
+        self.nodes[node_id.0]
+            .as_mut()
+            .expect("the node has been removed")
+    }
+}
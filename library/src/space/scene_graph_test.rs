@@ -0,0 +1,184 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "SceneGraph" {
+        use crate::math::*;
+        use crate::space::{*, csg};
+        use std::sync::Arc;
+
+        it "Adding nodes" {
+            let mut graph = SceneGraph::default();
+
+            let group = graph.add(None, SceneNode::Group { transform: Matrix4::identity() });
+            let sphere = graph.add(Some(group), SceneNode::Shape(Arc::new(Sphere::default())));
+
+            assert_eq!(graph.roots(), vec![group]);
+            assert_eq!(graph.children(group), &[sphere]);
+            assert_eq!(graph.parent(sphere), Some(group));
+        }
+
+        it "Removing a node removes its descendants" {
+            let mut graph = SceneGraph::default();
+
+            let outer = graph.add(None, SceneNode::Group { transform: Matrix4::identity() });
+            let inner = graph.add(Some(outer), SceneNode::Group { transform: Matrix4::identity() });
+            graph.add(Some(inner), SceneNode::Shape(Arc::new(Sphere::default())));
+            let cube = graph.add(Some(outer), SceneNode::Shape(Arc::new(Cube::default())));
+
+            graph.remove(inner);
+
+            assert_eq!(graph.children(outer), &[cube]);
+            assert_eq!(graph.roots(), vec![outer]);
+        }
+
+        it "Reparenting a node" {
+            let mut graph = SceneGraph::default();
+
+            let group1 = graph.add(None, SceneNode::Group { transform: Matrix4::identity() });
+            let group2 = graph.add(None, SceneNode::Group { transform: Matrix4::identity() });
+            let sphere = graph.add(Some(group1), SceneNode::Shape(Arc::new(Sphere::default())));
+
+            graph.reparent(sphere, Some(group2));
+
+            assert_eq!(graph.children(group1).len(), 0);
+            assert_eq!(graph.children(group2), &[sphere]);
+            assert_eq!(graph.parent(sphere), Some(group2));
+
+            graph.reparent(sphere, None);
+
+            assert_eq!(graph.roots(), vec![group1, group2, sphere]);
+        }
+
+        #[should_panic]
+        it "Reparenting a node under a descendant panics" {
+            let mut graph = SceneGraph::default();
+
+            let outer = graph.add(None, SceneNode::Group { transform: Matrix4::identity() });
+            let inner = graph.add(Some(outer), SceneNode::Group { transform: Matrix4::identity() });
+
+            graph.reparent(outer, Some(inner));
+        }
+
+        #[should_panic]
+        it "Adding a child to a shape panics" {
+            let mut graph = SceneGraph::default();
+
+            let sphere = graph.add(None, SceneNode::Shape(Arc::new(Sphere::default())));
+
+            graph.add(Some(sphere), SceneNode::Shape(Arc::new(Cube::default())));
+        }
+
+        it "Building the shapes tree sets the parents" {
+            let mut graph = SceneGraph::default();
+
+            let group = graph.add(None, SceneNode::Group { transform: Matrix4::translation(5, 0, 0) });
+            let csg = graph.add(
+                Some(group),
+                SceneNode::Csg {
+                    operation: csg::Operation::Difference,
+                    cut_material: csg::CutMaterial::Cutter,
+                    transform: Matrix4::identity(),
+                },
+            );
+            graph.add(Some(csg), SceneNode::Shape(Arc::new(Sphere::default())));
+            graph.add(Some(csg), SceneNode::Shape(Arc::new(Cube {
+                transform: Matrix4::translation(0.0, 0.0, -1.5).into(),
+                ..Cube::default()
+            })));
+
+            let objects = graph.build().unwrap();
+
+            assert_eq!(objects.len(), 1);
+
+            let group = objects[0].as_any().downcast_ref::<Group>().unwrap();
+            let csg = group.children[0].as_any().downcast_ref::<Csg>().unwrap();
+
+            assert_eq!(csg.parent().unwrap().id(), group.id());
+            assert_eq!(csg.children[0].parent().unwrap().id(), csg.id());
+            assert_eq!(csg.children[1].parent().unwrap().id(), csg.id());
+
+            // The cube cuts the front half of the sphere.
+            //
+            let ray = Ray::new((5, 0, -5), (0, 0, 1));
+            let intersections = objects[0].intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, 4.5);
+            assert_eq!(intersections[1].t, 6.0);
+        }
+
+        it "The graph can be edited and built again" {
+            let mut graph = SceneGraph::default();
+
+            let group1 = graph.add(None, SceneNode::Group { transform: Matrix4::identity() });
+            let group2 = graph.add(None, SceneNode::Group { transform: Matrix4::translation(5, 0, 0) });
+            let sphere = graph.add(Some(group1), SceneNode::Shape(Arc::new(Sphere::default())));
+
+            let ray = Ray::new((5, 0, -5), (0, 0, 1));
+
+            let objects = graph.build().unwrap();
+            objects.iter().for_each(|object| object.commit(None));
+
+            assert_eq!(objects[1].intersections(&ray).len(), 0);
+
+            drop(objects);
+
+            // The sphere world transforms change, so the cached ones must be invalidated.
+            //
+            graph.reparent(sphere, Some(group2));
+
+            let objects = graph.build().unwrap();
+            objects.iter().for_each(|object| object.commit(None));
+
+            let group2_shape = objects[1].as_any().downcast_ref::<Group>().unwrap();
+            let sphere_shape = &group2_shape.children[0];
+
+            assert_eq!(sphere_shape.parent().unwrap().id(), group2_shape.id());
+            assert_eq!(sphere_shape.world_transforms().get().unwrap().transform, Matrix4::translation(5, 0, 0));
+
+            drop(objects);
+
+            if let SceneNode::Shape(shape) = graph.content_mut(sphere) {
+                Arc::get_mut(shape).unwrap().transform_mut()[2][2] = 2.0;
+            }
+
+            let objects = graph.build().unwrap();
+            let intersections = objects[1].intersections(&ray);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, 3.0);
+        }
+
+        it "Building with a container with children as leaf shape fails" {
+            let mut graph = SceneGraph::default();
+
+            let group = graph.add(None, SceneNode::Group { transform: Matrix4::identity() });
+            let empty_group = graph.add(Some(group), SceneNode::Shape(Group::new(Matrix4::identity(), vec![])));
+
+            assert!(graph.build().is_ok());
+
+            let container = graph.add(
+                Some(group),
+                SceneNode::Shape(Group::new(Matrix4::identity(), vec![Arc::new(Sphere::default())])),
+            );
+
+            assert_eq!(graph.build().unwrap_err(), SceneGraphError::ContainerLeaf(container));
+
+            graph.remove(container);
+
+            assert_eq!(graph.build().unwrap().len(), 1);
+            assert_eq!(graph.children(group), &[empty_group]);
+        }
+
+        #[should_panic]
+        it "Building again while a previously built tree is alive panics" {
+            let mut graph = SceneGraph::default();
+
+            graph.add(None, SceneNode::Shape(Arc::new(Sphere::default())));
+
+            let _objects = graph.build().unwrap();
+
+            graph.build().unwrap();
+        }
+    }
+}
@@ -2,28 +2,121 @@ use std::{
     fmt,
     sync::{
         atomic::{AtomicU32, Ordering},
-        Arc, Weak,
+        Arc, OnceLock, RwLock, Weak,
     },
 };
 
-use super::{BoundedShape, Bounds, Intersection, PointLight, Ray};
+use super::{BoundedShape, Bounds, Group, Intersection, PointLight, Ray};
 use crate::{
-    math::{Matrix, Tuple},
-    properties::{Color, Material},
+    math::{Matrix4, Normal3, Point3, Transform, Vector3},
+    properties::{BumpMap, Color, Material, MaterialHandle},
 };
 
 #[cfg(test)]
 use std::any::Any;
 
+lazy_static::lazy_static! {
+    static ref DEFAULT_MATERIAL: MaterialHandle = MaterialHandle::default();
+}
+
 static NEXT_ID: AtomicU32 = AtomicU32::new(1);
 
 pub(crate) fn new_shape_id() -> u32 {
     NEXT_ID.fetch_add(1, Ordering::SeqCst)
 }
 
+// Reference to the parent shape. It's interior-mutable, so that it can be set after the child has been
+// wrapped in an Arc (see Group#new()); it's written only when building the tree, so the lock is
+// effectively uncontended.
+//
+#[derive(Debug)]
+pub struct ShapeParent(RwLock<Weak<dyn Shape>>);
+
+impl ShapeParent {
+    pub fn get(&self) -> Option<Arc<dyn Shape>> {
+        Weak::upgrade(&self.0.read().unwrap())
+    }
+
+    pub fn set(&self, parent: Weak<dyn Shape>) {
+        *self.0.write().unwrap() = parent;
+    }
+}
+
+impl Default for ShapeParent {
+    // The Weak type is irrelevant, since it never upgrades; it's required because an unsized Weak can't
+    // be directly created.
+    //
+    fn default() -> Self {
+        Self(RwLock::new(Weak::<Group>::new()))
+    }
+}
+
+// World-space transforms, precomputed by Shape#commit().
+//
+#[derive(Debug)]
+pub struct WorldTransforms {
+    pub transform: Matrix4,
+    pub inverse: Matrix4,
+    pub inverse_transpose: Matrix4,
+}
+
+// Holds the world transforms of a shape, when committed; see Shape#commit().
+//
+// The transforms are stored inline, and written once, so that the (per ray) reads don't need any
+// synchronization. Since they can't be overwritten via a shared reference, they're reset only via
+// `invalidate()`, which requires exclusive access (see ShapeAccessors#transform_mut()).
+//
+#[derive(Debug, Default)]
+pub struct WorldTransformsCache(OnceLock<WorldTransforms>);
+
+impl WorldTransformsCache {
+    pub fn get(&self) -> Option<&WorldTransforms> {
+        self.0.get()
+    }
+
+    // Computes the world transforms from the shape (local) transform and the parent world transform,
+    // and caches them. If they're already cached, they must match, since they can't be overwritten; a
+    // mismatch means that the shape has been moved to another tree without being invalidated (see
+    // SceneGraph#build()).
+    //
+    pub fn update(
+        &self,
+        transform: &Matrix4,
+        parent_world_transform: Option<&Matrix4>,
+    ) -> &WorldTransforms {
+        let world_transform = match parent_world_transform {
+            Some(parent_world_transform) => parent_world_transform * transform,
+            None => *transform,
+        };
+
+        let world_transforms = self.0.get_or_init(|| {
+            let inverse = world_transform.inverse();
+
+            WorldTransforms {
+                transform: world_transform,
+                inverse_transpose: inverse.transpose(),
+                inverse,
+            }
+        });
+
+        if world_transforms.transform != world_transform {
+            panic!("the cached world transforms are stale; they must be invalidated first");
+        }
+
+        world_transforms
+    }
+
+    pub fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
+
 pub(crate) mod private {
     use super::Ray;
-    use crate::{math::Tuple, space::Intersection};
+    use crate::{
+        math::{Normal3, Point3},
+        space::{Intersection, TextureCoordinates},
+    };
 
     pub trait ShapeLocal {
         // point: In object space.
@@ -32,13 +125,35 @@ pub(crate) mod private {
         //
         // In the book, this is local_normal_at().
         //
-        fn local_normal(&self, point: Tuple, intersection: &Intersection) -> Tuple;
+        fn local_normal(&self, point: Point3, intersection: &Intersection) -> Normal3;
 
         // ray: In object space.
         //
+        // Appends the intersections to the buffer, without touching the existing ones; the buffer is
+        // provided by the caller, so that it can be reused across rays.
+        //
         // In the book, this is local_intersect(), and returns also the shapes.
         //
-        fn local_intersections<'a>(&'a self, ray: &Ray) -> Vec<Intersection<'a>>;
+        fn local_intersections_into<'a>(
+            &'a self,
+            ray: &Ray,
+            intersections: &mut Vec<Intersection<'a>>,
+        );
+
+        // Allocating version of local_intersections_into(); convenient for tests and one-off queries.
+        //
+        fn local_intersections<'a>(&'a self, ray: &Ray) -> Vec<Intersection<'a>> {
+            let mut intersections = vec![];
+            self.local_intersections_into(ray, &mut intersections);
+            intersections
+        }
+
+        // Only the shapes with a texture mapping (currently, meshes with uvs) provide them; they're
+        // required by the normal maps.
+        //
+        fn texture_coordinates(&self, _intersection: &Intersection) -> Option<TextureCoordinates> {
+            None
+        }
     }
 }
 
@@ -50,30 +165,96 @@ pub(crate) mod private {
 // If the current design had to be pushed further, an attribute macro should be written, in order to
 // deduplicate the attributes; attributes macros have very little documentation around, though.
 //
-pub trait Shape: private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send {
+// Field accessors, generated by the ShapeAccessors derive macro. They're split from Shape, so that the
+// containers can use the macro, while still overriding the Shape methods.
+//
+pub trait ShapeAccessors {
     fn id(&self) -> u32;
     fn parent(&self) -> Option<Arc<dyn Shape>>;
-    fn parent_mut(&mut self) -> &mut Weak<dyn Shape>;
-    fn transform(&self) -> &Matrix;
-    fn transform_mut(&mut self) -> &mut Matrix;
-    fn material(&self) -> &Material;
+    fn set_parent(&self, parent: Weak<dyn Shape>);
+    fn world_transforms(&self) -> &WorldTransformsCache;
+    fn world_transforms_mut(&mut self) -> &mut WorldTransformsCache;
+    fn transform(&self) -> &Transform;
+    // Invalidates the cached (inverse) transforms.
+    //
+    fn transform_mut(&mut self) -> &mut Matrix4;
+    // The material assigned to the shape, if any; see Shape#material().
+    //
+    fn own_material(&self) -> Option<&MaterialHandle>;
     fn material_mut(&mut self) -> &mut Material;
 
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any;
+}
+
+pub trait Shape:
+    ShapeAccessors + private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send
+{
+    // Material of the shape alone, which is the default one, if the shape has none.
+    //
+    // At shading time, the shapes without a material inherit the one of the closest container having
+    // one (see Intersection#material()), so this is not necessarily the rendered material.
+    //
+    fn material_handle(&self) -> &MaterialHandle {
+        self.own_material().unwrap_or(&DEFAULT_MATERIAL)
+    }
+
+    // Current material of the handle (see MaterialHandle#load()).
+    //
+    fn material(&self) -> Arc<Material> {
+        self.material_handle().load()
+    }
+
     // The `intersection` is used only by smooth triangles, but it's not an option because it's always
     // passed when computing the IntersectionState.
     // In tests, just pass the `Intersection::default()`.
     //
     // In the book, this is normal_at().
     //
-    fn normal(&self, world_point: &Tuple, intersection: &Intersection) -> Tuple {
+    fn normal(&self, world_point: &Point3, intersection: &Intersection) -> Normal3 {
         let local_point = self.world_to_object(world_point);
         let local_normal = self.local_normal(local_point, intersection);
         self.normal_to_world(&local_normal)
     }
 
+    // Same as normal(), but perturbed by the bump map. The perturbation is applied in object space, so
+    // that the bumps scale with the object.
+    //
+    fn bumped_normal(
+        &self,
+        world_point: &Point3,
+        intersection: &Intersection,
+        bump_map: &BumpMap,
+    ) -> Normal3 {
+        let local_point = self.world_to_object(world_point);
+        let local_normal = self.local_normal(local_point, intersection).normalize();
+        let texture_coordinates = self.texture_coordinates(intersection);
+
+        let bumped_normal =
+            bump_map.perturb(&local_point, &local_normal, texture_coordinates.as_ref());
+
+        self.normal_to_world(&bumped_normal)
+    }
+
+    // Precomputes the world transforms (combined with the parents ones), so that the world/object
+    // conversions don't need to walk up the tree and invert the matrices at each level, for each ray.
+    //
+    // It must be invoked on the roots, after the tree is built (it's invoked by Camera#render()); if it's
+    // not, the conversions are computed on the fly. Containers override it, in order to propagate it to
+    // the children.
+    //
+    fn commit(&self, parent_world_transform: Option<&Matrix4>) {
+        self.world_transforms()
+            .update(self.transform().matrix(), parent_world_transform);
+    }
+
     // point: In world space.
     //
-    fn world_to_object(&self, point: &Tuple) -> Tuple {
+    fn world_to_object(&self, point: &Point3) -> Point3 {
+        if let Some(world_transforms) = self.world_transforms().get() {
+            return world_transforms.inverse * point;
+        }
+
         let transform_inverse = self.transform().inverse();
 
         if let Some(parent) = self.parent() {
@@ -85,10 +266,12 @@ pub trait Shape: private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send {
 
     // normal: In object space.
     //
-    fn normal_to_world(&self, normal: &Tuple) -> Tuple {
-        let mut normal = self.transform().inverse().transpose() * normal;
-        normal.w = 0.0;
-        normal = normal.normalize();
+    fn normal_to_world(&self, normal: &Normal3) -> Normal3 {
+        if let Some(world_transforms) = self.world_transforms().get() {
+            return normal.transform(&world_transforms.inverse_transpose);
+        }
+
+        let normal = self.transform() * normal;
 
         if let Some(parent) = self.parent() {
             parent.normal_to_world(&normal)
@@ -97,24 +280,76 @@ pub trait Shape: private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send {
         }
     }
 
-    // Return value properties:
+    // Appends the intersections to the buffer (see ShapeLocal#local_intersections_into()).
+    //
+    // Appended intersections properties:
     //
     // - they're not guaranteed to be ordered;
-    // - negative values are allowed (required to compute refraction indexes).
+    // - they're within the ray interval; with an unbounded ray, negative values are included (required
+    //   to compute refraction indexes).
     //
-    // A possible optimization is to pass from the top an ordered collection (e.g. BTreeSet), and add
-    // the intersections while traversing the tree, instead of creating separate arrays and sorting
-    // the end result. This is a valid design even without considering the performance, as it fits nicely.
+    fn intersections_into<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        let start = intersections.len();
+        let transformed_ray = ray.inverse_transform(self.transform());
+
+        self.local_intersections_into(&transformed_ray, intersections);
+
+        // Since the order is not guaranteed, the out-of-interval intersections can be swap-removed.
+        //
+        let mut i = start;
+
+        while i < intersections.len() {
+            if ray.contains(intersections[i].t) {
+                i += 1;
+            } else {
+                intersections.swap_remove(i);
+            }
+        }
+    }
+
+    // Allocating version of intersections_into(); convenient for tests and one-off queries.
     //
     fn intersections<'a>(&'a self, ray: &Ray) -> Vec<Intersection<'a>> {
-        let transformed_ray = ray.inverse_transform(self.transform());
-        self.local_intersections(&transformed_ray)
+        let mut intersections = vec![];
+        self.intersections_into(ray, &mut intersections);
+        intersections
+    }
+
+    // Closest intersection within the ray interval. Containers override this (and any_hit()), in order
+    // to skip the children that can't yield a closer intersection.
+    //
+    // The buffer is used as scratch space; it's left as it was found.
+    //
+    fn closest_hit<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Option<Intersection<'a>> {
+        let start = intersections.len();
+        self.intersections_into(ray, intersections);
+        intersections.drain(start..).min()
     }
 
-    // Default implementation, for non-nested shapes.
+    // If there is any intersection within the ray interval; used for shadow rays, which don't need the
+    // closest one.
     //
-    fn includes(&self, object: &dyn Shape) -> bool {
-        self.id() == object.id()
+    // The buffer is used as scratch space; it's left as it was found.
+    //
+    fn any_hit<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) -> bool {
+        let start = intersections.len();
+        self.intersections_into(ray, intersections);
+        let found = intersections.len() > start;
+        intersections.truncate(start);
+        found
+    }
+
+    // Whether the intersection belongs to the shape, or to one of its descendants.
+    //
+    // Default implementation, for non-nested shapes; the intersections of instanced objects belong to
+    // the instances (see Instance#includes()).
+    //
+    fn includes(&self, intersection: &Intersection) -> bool {
+        intersection.instance.is_none() && self.id() == intersection.object.id()
     }
 
     // Local (object-level) bounds, with the shape transformation applied.
@@ -128,14 +363,14 @@ pub trait Shape: private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send {
         // Group type).
 
         let local_corners = [
-            Tuple::point(local_bounds.min.x, local_bounds.min.y, local_bounds.min.z),
-            Tuple::point(local_bounds.min.x, local_bounds.min.y, local_bounds.max.z),
-            Tuple::point(local_bounds.min.x, local_bounds.max.y, local_bounds.min.z),
-            Tuple::point(local_bounds.min.x, local_bounds.max.y, local_bounds.max.z),
-            Tuple::point(local_bounds.max.x, local_bounds.min.y, local_bounds.min.z),
-            Tuple::point(local_bounds.max.x, local_bounds.min.y, local_bounds.max.z),
-            Tuple::point(local_bounds.max.x, local_bounds.max.y, local_bounds.min.z),
-            Tuple::point(local_bounds.max.x, local_bounds.max.y, local_bounds.max.z),
+            Point3::new(local_bounds.min.x, local_bounds.min.y, local_bounds.min.z),
+            Point3::new(local_bounds.min.x, local_bounds.min.y, local_bounds.max.z),
+            Point3::new(local_bounds.min.x, local_bounds.max.y, local_bounds.min.z),
+            Point3::new(local_bounds.min.x, local_bounds.max.y, local_bounds.max.z),
+            Point3::new(local_bounds.max.x, local_bounds.min.y, local_bounds.min.z),
+            Point3::new(local_bounds.max.x, local_bounds.min.y, local_bounds.max.z),
+            Point3::new(local_bounds.max.x, local_bounds.max.y, local_bounds.min.z),
+            Point3::new(local_bounds.max.x, local_bounds.max.y, local_bounds.max.z),
         ];
 
         let transform = self.transform();
@@ -144,7 +379,7 @@ pub trait Shape: private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send {
         for corner in local_corners.iter() {
             let transformed_corner = transform * corner;
 
-            Bounds::update_from_tuple(&mut bounds, &transformed_corner);
+            Bounds::update_from_point(&mut bounds, &transformed_corner);
         }
 
         bounds
@@ -156,9 +391,9 @@ pub trait Shape: private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send {
     fn lighting(
         &self,
         light: &PointLight,
-        world_point: &Tuple,
-        eyev: &Tuple,
-        normalv: &Tuple,
+        world_point: &Point3,
+        eyev: &Vector3,
+        normalv: &Normal3,
         in_shadow: bool,
     ) -> Color {
         let object_point = self.world_to_object(&world_point);
@@ -166,9 +401,6 @@ pub trait Shape: private::ShapeLocal + BoundedShape + fmt::Debug + Sync + Send {
         self.material()
             .lighting(light, &object_point, world_point, eyev, normalv, in_shadow)
     }
-
-    #[cfg(test)]
-    fn as_any(&self) -> &dyn Any;
 }
 
 impl PartialEq for dyn Shape + '_ {
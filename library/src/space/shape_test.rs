@@ -7,25 +7,28 @@ demonstrate! {
         use crate::math::*;
         use crate::space::*;
         use std::sync::Arc;
-        use std::f64::consts::PI;
+        use crate::lang::consts::{FRAC_1_SQRT_2, PI};
 
         before {
             #[allow(unused_variables)]
             let default_intersection = Intersection {
                 t: 0.0,
                 uv: None,
+                face: None,
+                instance: None,
+                material: None,
                 object: &Plane::default(),
             };
         }
 
         it "should return the normal on a transformed sphere" {
             let test_shape: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(0, 1, 0),
+                transform: Matrix4::translation(0, 1, 0).into(),
                 ..Sphere::default()
             });
 
-            let actual_normal = test_shape.normal(&Tuple::point(0.0, 1.70711, -0.70711), &default_intersection);
-            let expected_normal = Tuple::vector(0, 0.70711, -0.70711);
+            let actual_normal = test_shape.normal(&Point3::new(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2), &default_intersection);
+            let expected_normal = Normal3::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
 
             assert_eq!(actual_normal, expected_normal);
         }
@@ -43,7 +46,7 @@ demonstrate! {
                     let ray = Ray::new((0, 0, -5), (0, 0, 1));
 
                     let test_shape: Arc<dyn Shape> = Arc::new(Sphere {
-                        transform: Matrix::scaling(2, 2, 2),
+                        transform: Matrix4::scaling(2, 2, 2).into(),
                         ..Sphere::default()
                     });
 
@@ -59,7 +62,7 @@ demonstrate! {
                     let ray = Ray::new((0, 0, -5), (0, 0, 1));
 
                     let test_shape: Arc<dyn Shape> = Arc::new(Sphere {
-                        transform: Matrix::translation(5, 0, 0),
+                        transform: Matrix4::translation(5, 0, 0).into(),
                         ..Sphere::default()
                     });
 
@@ -70,74 +73,145 @@ demonstrate! {
 
         it "Converting a point from world to object space" {
             let sphere: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(5, 0, 0),
+                transform: Matrix4::translation(5, 0, 0).into(),
                 ..Sphere::default()
             });
 
             let group2 = Group::new(
-                Matrix::scaling(2, 2, 2),
+                Matrix4::scaling(2, 2, 2),
                 vec![sphere],
             );
 
             let group1 = Group::new(
-                Matrix::rotation(Axis::Y, PI / 2.0),
+                Matrix4::rotation(Axis::Y, PI / 2.0),
                 vec![group2],
             );
 
-            let expected_point = Tuple::point(0, 0, -1);
+            let expected_point = Point3::new(0, 0, -1);
 
             let group2 = group1.children[0].as_any().downcast_ref::<Group>().unwrap();
             let sphere = &group2.children[0];
 
-            assert_eq!(sphere.world_to_object(&Tuple::point(-2, 0, -10)), expected_point);
+            assert_eq!(sphere.world_to_object(&Point3::new(-2, 0, -10)), expected_point);
         }
 
         it "Converting a normal from object to world space" {
             let sphere: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(5, 0, 0),
+                transform: Matrix4::translation(5, 0, 0).into(),
                 ..Sphere::default()
             });
 
             let group2 = Group::new(
-                Matrix::scaling(1, 2, 3),
+                Matrix4::scaling(1, 2, 3),
                 vec![sphere],
             );
 
             let group1 = Group::new(
-                Matrix::rotation(Axis::Y, PI / 2.0),
+                Matrix4::rotation(Axis::Y, PI / 2.0),
                 vec![group2],
             );
 
             let group2 = &group1.children[0].as_any().downcast_ref::<Group>().unwrap();
             let sphere = &group2.children[0];
 
-            let actual_normal = sphere.normal_to_world(&Tuple::vector(sqrt(3) / 3.0, sqrt(3) / 3.0, sqrt(3) / 3.0));
+            let actual_normal = sphere.normal_to_world(&Normal3::new(sqrt(3) / 3.0, sqrt(3) / 3.0, sqrt(3) / 3.0));
 
-            assert_eq!(actual_normal, Tuple::vector(0.2857, 0.4286, -0.8571));
+            assert_eq!(actual_normal, Normal3::new(0.2857, 0.4286, -0.8571));
         }
 
         it "Finding the normal on a child object" {
             let sphere: Arc<dyn Shape> = Arc::new(Sphere {
-                transform: Matrix::translation(5, 0, 0),
+                transform: Matrix4::translation(5, 0, 0).into(),
                 ..Sphere::default()
             });
 
             let group2 = Group::new(
-                Matrix::scaling(1, 2, 3),
+                Matrix4::scaling(1, 2, 3),
                 vec![sphere],
             );
 
             let group1 = Group::new(
-                Matrix::rotation(Axis::Y, PI / 2.0),
+                Matrix4::rotation(Axis::Y, PI / 2.0),
                 vec![group2],
             );
 
             let group2 = group1.children[0].as_any().downcast_ref::<Group>();
             let sphere = &group2.unwrap().children[0];
 
-            let actual_normal = sphere.normal(&Tuple::point(1.7321, 1.1547, -5.5774), &default_intersection);
+            let actual_normal = sphere.normal(&Point3::new(1.7321, 1.1547, -5.5774), &default_intersection);
 
-            assert_eq!(actual_normal, Tuple::vector(0.2857, 0.4286, -0.8571));
+            assert_eq!(actual_normal, Normal3::new(0.2857, 0.4286, -0.8571));
+        }
+
+        it "Committing caches the world transforms of the children" {
+            let sphere: Arc<dyn Shape> = Arc::new(Sphere {
+                transform: Matrix4::translation(5, 0, 0).into(),
+                ..Sphere::default()
+            });
+
+            let group2 = Group::new(
+                Matrix4::scaling(1, 2, 3),
+                vec![sphere],
+            );
+
+            let group1 = Group::new(
+                Matrix4::rotation(Axis::Y, PI / 2.0),
+                vec![group2],
+            );
+
+            let group2 = group1.children[0].as_any().downcast_ref::<Group>().unwrap();
+            let sphere = &group2.children[0];
+
+            let world_point = Point3::new(-2, 0, -10);
+            let object_normal = Normal3::new(sqrt(3) / 3.0, sqrt(3) / 3.0, sqrt(3) / 3.0);
+
+            let uncached_object_point = sphere.world_to_object(&world_point);
+            let uncached_world_normal = sphere.normal_to_world(&object_normal);
+
+            assert!(sphere.world_transforms().get().is_none());
+
+            group1.commit(None);
+
+            let expected_transform = Matrix4::translation(5, 0, 0).scale(1, 2, 3).rotate(Axis::Y, PI / 2.0);
+            let world_transforms = sphere.world_transforms().get().unwrap();
+
+            assert_eq!(world_transforms.transform, expected_transform);
+            assert_eq!(world_transforms.inverse, expected_transform.inverse());
+
+            assert_eq!(sphere.world_to_object(&world_point), uncached_object_point);
+            assert_eq!(sphere.normal_to_world(&object_normal), uncached_world_normal);
+        }
+
+        it "Modifying the transform invalidates the cached world transforms" {
+            let mut sphere = Sphere::default();
+
+            sphere.commit(None);
+
+            assert!(sphere.world_transforms().get().is_some());
+
+            *sphere.transform_mut() = Matrix4::translation(1, 0, 0);
+
+            assert!(sphere.world_transforms().get().is_none());
+        }
+
+        it "Intersections are appended to the buffer, without touching the existing ones" {
+            let plane = Plane::default();
+            let sphere = Sphere::default();
+            let mut intersections = vec![Intersection { t: 10.0, ..default_intersection }];
+
+            let ray = Ray::new((0, 0, -5), (0, 0, 1)).with_interval(0.0, 5.0);
+
+            sphere.intersections_into(&ray, &mut intersections);
+
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, 10.0);
+            assert_eq!(intersections[1].t, 4.0);
+
+            // Scratch space is left as it was found.
+            //
+            assert!(plane.closest_hit(&ray, &mut intersections).is_none());
+            assert!(sphere.any_hit(&ray, &mut intersections));
+            assert_eq!(intersections.len(), 2);
         }
     }
 }
@@ -1,35 +1,39 @@
-use std::sync::Weak;
-
-use super::{shape, shape::private::ShapeLocal, BoundedShape, Bounds, Intersection, Shape};
+use super::{
+    shape, shape::private::ShapeLocal, BoundedShape, Bounds, Intersection, ShapeParent,
+    WorldTransformsCache,
+};
 use crate::{
     lang::math::sqrt,
-    math::{Matrix, Tuple},
-    properties::Material,
+    math::{Normal3, Point3, Transform},
+    properties::MaterialHandle,
 };
 
 #[derive(Debug, ShapeAccessors, SmartDefault)]
 pub struct Sphere {
     #[default(_code = "shape::new_shape_id()")]
     pub id: u32,
-    #[default(Weak::<Self>::new())]
-    pub parent: Weak<dyn Shape>,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
-    #[default(Material::default())]
-    pub material: Material,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
 }
 
 impl ShapeLocal for Sphere {
     // point: In object space.
     //
-    fn local_normal(&self, point: Tuple, _intersection: &Intersection) -> Tuple {
-        point - &Tuple::point(0, 0, 0)
+    fn local_normal(&self, point: Point3, _intersection: &Intersection) -> Normal3 {
+        Normal3::new(point.x, point.y, point.z)
     }
 
     // ray: In object space.
     //
-    fn local_intersections(&self, ray: &super::Ray) -> Vec<Intersection> {
-        let sphere_location = Tuple::point(0, 0, 0);
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &super::Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        let sphere_location = Point3::new(0, 0, 0);
         let sphere_to_ray = ray.origin - &sphere_location;
 
         let a = ray.direction.dot_product(&ray.direction);
@@ -38,24 +42,28 @@ impl ShapeLocal for Sphere {
 
         let discriminant = b.powi(2) - 4.0 * a * c;
 
-        if discriminant < 0.0 {
-            vec![]
-        } else {
+        if discriminant >= 0.0 {
             let t1 = (-b - sqrt(discriminant)) / (2.0 * a);
             let t2 = (-b + sqrt(discriminant)) / (2.0 * a);
 
-            vec![
+            intersections.extend([
                 Intersection {
                     t: t1,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object: self,
                 },
                 Intersection {
                     t: t2,
                     uv: None,
+                    face: None,
+                    instance: None,
+                    material: None,
                     object: self,
                 },
-            ]
+            ]);
         }
     }
 }
@@ -63,8 +71,8 @@ impl ShapeLocal for Sphere {
 impl BoundedShape for Sphere {
     fn local_bounds(&self) -> Bounds {
         Bounds {
-            min: Tuple::point(-1, -1, -1),
-            max: Tuple::point(1, 1, 1),
+            min: Point3::new(-1, -1, -1),
+            max: Point3::new(1, 1, 1),
         }
     }
 }
@@ -7,16 +7,16 @@ demonstrate! {
         use crate::math::*;
 
         it "should have an identity transformation of order 4 as default" {
-            assert_eq!(Sphere::default().transform, Matrix::identity(4));
+            assert_eq!(Sphere::default().transform, Matrix4::identity());
         }
 
         it "should allow a transformation to be set" {
             let sphere = Sphere {
-                transform: Matrix::scaling(1, 2, 3),
+                transform: Matrix4::scaling(1, 2, 3).into(),
                 ..Sphere::default()
             };
 
-            let expected_transform = Matrix::scaling(1, 2, 3);
+            let expected_transform = Matrix4::scaling(1, 2, 3);
 
             assert_eq!(sphere.transform, expected_transform);
         }
@@ -1,53 +1,54 @@
-use std::sync::Weak;
-
 use super::{
     shape::{self, private::ShapeLocal},
-    BoundedShape, Bounds, Intersection, Ray, Shape,
+    BoundedShape, Bounds, Intersection, Ray, ShapeParent, WorldTransformsCache,
+};
+use crate::{
+    lang::ApproximateFloatOps,
+    math::{Normal3, Point3, Transform, Vector3},
+    properties::MaterialHandle,
 };
-use crate::{lang::ApproximateFloat64Ops, math::Matrix, math::Tuple, properties::Material};
 
 #[derive(Debug, ShapeAccessors, SmartDefault)]
 pub struct Triangle {
     #[default(_code = "shape::new_shape_id()")]
     pub id: u32,
-    #[default(Weak::<Self>::new())]
-    pub parent: Weak<dyn Shape>,
-    #[default(Matrix::identity(4))]
-    pub transform: Matrix,
-    #[default(Material::default())]
-    pub material: Material,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
 
     // The following defaults are not meaningful, but are required in order to allow type defaulting.
     // `n1`..`n3` are meaningful for smooth triangles.
     //
-    #[default(Tuple::point(0, 0, 0))]
-    pub p1: Tuple,
-    #[default(Tuple::point(0, 0, 0))]
-    pub p2: Tuple,
-    #[default(Tuple::point(0, 0, 0))]
-    pub p3: Tuple,
-    #[default(Tuple::point(0, 0, 0))]
-    pub e1: Tuple,
-    #[default(Tuple::point(0, 0, 0))]
-    pub e2: Tuple,
-    #[default(Tuple::point(0, 0, 0))]
-    pub normal: Tuple,
+    #[default(Point3::origin())]
+    pub p1: Point3,
+    #[default(Point3::origin())]
+    pub p2: Point3,
+    #[default(Point3::origin())]
+    pub p3: Point3,
+    #[default(Vector3::new(0, 0, 0))]
+    pub e1: Vector3,
+    #[default(Vector3::new(0, 0, 0))]
+    pub e2: Vector3,
+    #[default(Normal3::new(0, 0, 0))]
+    pub normal: Normal3,
 
     // <Some> for smooth triangles.
     // WATCH OUT! If this is accessed via indexing, remember that the math indexing is 1-based, but
     // the tuple is 0-based.
     //
-    pub vertex_normals: Option<(Tuple, Tuple, Tuple)>,
+    pub vertex_normals: Option<(Normal3, Normal3, Normal3)>,
 }
 
 impl Triangle {
     // Also computes the normal.
     //
-    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple) -> Self {
+    pub fn new(p1: Point3, p2: Point3, p3: Point3) -> Self {
         let e1 = p2 - &p1;
         let e2 = p3 - &p1;
 
-        let normal = e2.cross_product(e1).normalize();
+        let normal = e2.cross_product(&e1).normalize().into();
 
         Triangle {
             p1,
@@ -60,11 +61,18 @@ impl Triangle {
         }
     }
 
-    pub fn smooth(p1: Tuple, p2: Tuple, p3: Tuple, n1: Tuple, n2: Tuple, n3: Tuple) -> Self {
+    pub fn smooth(
+        p1: Point3,
+        p2: Point3,
+        p3: Point3,
+        n1: Normal3,
+        n2: Normal3,
+        n3: Normal3,
+    ) -> Self {
         let e1 = p2 - &p1;
         let e2 = p3 - &p1;
 
-        let normal = e2.cross_product(e1).normalize();
+        let normal = e2.cross_product(&e1).normalize().into();
 
         Triangle {
             p1,
@@ -81,23 +89,23 @@ impl Triangle {
     // Convenience for the test suite.
     //
     pub fn from_ints(p1: (i32, i32, i32), p2: (i32, i32, i32), p3: (i32, i32, i32)) -> Self {
-        let p1 = Tuple::point(p1.0, p1.1, p1.2);
-        let p2 = Tuple::point(p2.0, p2.1, p2.2);
-        let p3 = Tuple::point(p3.0, p3.1, p3.2);
+        let p1 = Point3::new(p1.0, p1.1, p1.2);
+        let p2 = Point3::new(p2.0, p2.1, p2.2);
+        let p3 = Point3::new(p3.0, p3.1, p3.2);
 
         Self::new(p1, p2, p3)
     }
 }
 
 impl ShapeLocal for Triangle {
-    fn local_normal(&self, _point: Tuple, intersection: &Intersection) -> Tuple {
+    fn local_normal(&self, _point: Point3, intersection: &Intersection) -> Normal3 {
         // We can unwrap in the inner block because the intersection that is passed here comes indirectly
         // from `self.local_intersections()`. Considering this, unwrapping also acts as assertion.
         //
         if let Some((n1, n2, n3)) = self.vertex_normals {
             let (u, v) = intersection.uv.unwrap();
 
-            n2 * u + &(n3 * v) + &(n1 * (1.0 - u - v))
+            (n2 * u + &(n3 * v) + &(n1 * (1.0 - u - v))).into()
         } else {
             self.normal
         }
@@ -107,12 +115,16 @@ impl ShapeLocal for Triangle {
     //
     // In the book, this is `intersection_with_uv`, when self.smooth is true.
     //
-    fn local_intersections(&self, ray: &Ray) -> Vec<Intersection> {
-        let dir_cross_e2 = ray.direction.cross_product(self.e2);
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        let dir_cross_e2 = ray.direction.cross_product(&self.e2);
         let determinant = self.e1.dot_product(&dir_cross_e2);
 
         if determinant.within_epsilon() {
-            return vec![];
+            return;
         }
 
         let f = 1.0 / determinant;
@@ -120,14 +132,14 @@ impl ShapeLocal for Triangle {
         let u = f * p1_to_origin.dot_product(&dir_cross_e2);
 
         if !(0.0..=1.0).contains(&u) {
-            return vec![];
+            return;
         }
 
-        let origin_cross_e1 = p1_to_origin.cross_product(self.e1);
+        let origin_cross_e1 = p1_to_origin.cross_product(&self.e1);
         let v = f * ray.direction.dot_product(&origin_cross_e1);
 
         if v < 0.0 || (u + v) > 1.0 {
-            return vec![];
+            return;
         }
 
         let t = f * self.e2.dot_product(&origin_cross_e1);
@@ -138,11 +150,14 @@ impl ShapeLocal for Triangle {
             None
         };
 
-        vec![Intersection {
+        intersections.push(Intersection {
             t,
             uv,
+            face: None,
+            instance: None,
+            material: None,
             object: self,
-        }]
+        });
     }
 }
 
@@ -151,7 +166,7 @@ impl BoundedShape for Triangle {
         let mut bounds = Bounds::default();
 
         for p in &[self.p1, self.p2, self.p3] {
-            Bounds::update_from_tuple(&mut bounds, p);
+            Bounds::update_from_point(&mut bounds, p);
         }
 
         bounds
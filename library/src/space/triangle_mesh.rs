@@ -0,0 +1,414 @@
+use super::{
+    shape::{self, private::ShapeLocal},
+    BoundedShape, Bounds, Intersection, Ray, ShapeParent, TextureCoordinates, WorldTransformsCache,
+};
+use crate::{
+    lang::ApproximateFloatOps,
+    lang::Float,
+    math::{Normal3, Point3, Transform},
+    properties::MaterialHandle,
+};
+
+// Nodes with more than this number of faces are split, when building the BVH.
+//
+const MAX_LEAF_FACES: usize = 4;
+
+// The BVH is split at the median, so its depth is at most log2(faces), which is below 64 for any
+// mesh that fits in memory.
+//
+const MAX_BVH_DEPTH: usize = 64;
+
+// Indexes are 0-based (unlike the OBJ format ones), and refer to the mesh buffers.
+// `u32` is used in order to keep the face compact; meshes with more than 4G vertices are not a concern.
+//
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshFace {
+    pub vertices: [u32; 3],
+    // <Some> for smooth faces.
+    //
+    pub normals: Option<[u32; 3]>,
+    pub uvs: Option<[u32; 3]>,
+}
+
+impl MeshFace {
+    pub fn new(vertices: [u32; 3]) -> Self {
+        Self {
+            vertices,
+            normals: None,
+            uvs: None,
+        }
+    }
+
+    pub fn smooth(vertices: [u32; 3], normals: [u32; 3]) -> Self {
+        Self {
+            vertices,
+            normals: Some(normals),
+            uvs: None,
+        }
+    }
+}
+
+// Leaves refer to a contiguous range of faces; branches to the indexes of the children nodes.
+//
+#[derive(Debug)]
+enum BvhNode {
+    Leaf {
+        bounds: Bounds,
+        first: usize,
+        count: usize,
+    },
+    Branch {
+        bounds: Bounds,
+        left: usize,
+        right: usize,
+    },
+}
+
+// Compact alternative to a Group of Triangles: the vertex data is stored once in indexed buffers, and
+// there is a single transform/material, instead of one per triangle (see the `Triangle` attributes).
+//
+// Intersections are accelerated via an internal BVH, which is built on construction; for this reason,
+// the geometry is not public, and the mesh must be created via `new()`.
+//
+// WATCH OUT! The BVH construction reorders the faces, so the face indexes don't correspond to the
+// order of the faces passed to the constructor.
+//
+#[derive(Debug, ShapeAccessors, SmartDefault)]
+pub struct TriangleMesh {
+    #[default(_code = "shape::new_shape_id()")]
+    pub id: u32,
+    pub parent: ShapeParent,
+    pub world_transforms: WorldTransformsCache,
+    pub transform: Transform,
+    #[default(None)]
+    pub material: Option<MaterialHandle>,
+
+    vertices: Vec<Point3>,
+    normals: Vec<Normal3>,
+    uvs: Vec<(Float, Float)>,
+    faces: Vec<MeshFace>,
+    bvh: Vec<BvhNode>,
+}
+
+impl TriangleMesh {
+    pub fn new(
+        vertices: Vec<Point3>,
+        normals: Vec<Normal3>,
+        uvs: Vec<(Float, Float)>,
+        mut faces: Vec<MeshFace>,
+    ) -> Self {
+        let mut bvh = vec![];
+
+        if !faces.is_empty() {
+            Self::build_bvh(&vertices, &mut faces, 0, &mut bvh);
+        }
+
+        TriangleMesh {
+            vertices,
+            normals,
+            uvs,
+            faces,
+            bvh,
+            ..TriangleMesh::default()
+        }
+    }
+
+    pub fn vertices(&self) -> &[Point3] {
+        &self.vertices
+    }
+
+    pub fn normals(&self) -> &[Normal3] {
+        &self.normals
+    }
+
+    pub fn uvs(&self) -> &[(Float, Float)] {
+        &self.uvs
+    }
+
+    pub fn faces(&self) -> &[MeshFace] {
+        &self.faces
+    }
+
+    // Interpolated texture coordinates of the hit, if the face has them.
+    //
+    pub fn texture_uv(&self, intersection: &Intersection) -> Option<(Float, Float)> {
+        let face = &self.faces[intersection.face.unwrap()];
+        let (u, v) = intersection.uv.unwrap();
+
+        face.uvs.map(|[uv1i, uv2i, uv3i]| {
+            let (uv1, uv2, uv3) = (
+                self.uvs[uv1i as usize],
+                self.uvs[uv2i as usize],
+                self.uvs[uv3i as usize],
+            );
+
+            (
+                uv2.0 * u + uv3.0 * v + uv1.0 * (1.0 - u - v),
+                uv2.1 * u + uv3.1 * v + uv1.1 * (1.0 - u - v),
+            )
+        })
+    }
+
+    fn face_points(&self, face: &MeshFace) -> (Point3, Point3, Point3) {
+        let [p1i, p2i, p3i] = face.vertices;
+
+        (
+            self.vertices[p1i as usize],
+            self.vertices[p2i as usize],
+            self.vertices[p3i as usize],
+        )
+    }
+
+    fn face_bounds(vertices: &[Point3], faces: &[MeshFace]) -> Bounds {
+        let mut bounds = Bounds::default();
+
+        for face in faces {
+            for vertex_i in face.vertices.iter() {
+                Bounds::update_from_point(&mut bounds, &vertices[*vertex_i as usize]);
+            }
+        }
+
+        bounds
+    }
+
+    // Sum of the vertices; since it's used only for comparisons, there's no need to divide by 3.
+    //
+    fn face_centroid(vertices: &[Point3], face: &MeshFace, axis: usize) -> Float {
+        face.vertices
+            .iter()
+            .map(|vertex_i| vertices[*vertex_i as usize][axis])
+            .sum()
+    }
+
+    // Simple median split on the longest axis of the centroids bounds. It's not as good as SAH, but
+    // it's simple and fast to build.
+    //
+    // Returns the index of the created node.
+    //
+    fn build_bvh(
+        vertices: &[Point3],
+        faces: &mut [MeshFace],
+        first: usize,
+        nodes: &mut Vec<BvhNode>,
+    ) -> usize {
+        let bounds = Self::face_bounds(vertices, faces);
+        let node_i = nodes.len();
+
+        let mut centroid_bounds = Bounds::default();
+
+        for face in faces.iter() {
+            let centroid = Point3::new(
+                Self::face_centroid(vertices, face, 0),
+                Self::face_centroid(vertices, face, 1),
+                Self::face_centroid(vertices, face, 2),
+            );
+            Bounds::update_from_point(&mut centroid_bounds, &centroid);
+        }
+
+        let extent = centroid_bounds.max - &centroid_bounds.min;
+        let axis = if extent.x > extent.y && extent.x > extent.z {
+            0
+        } else if extent.y > extent.z {
+            1
+        } else {
+            2
+        };
+
+        // If all the centroids coincide, splitting is pointless.
+        //
+        if faces.len() <= MAX_LEAF_FACES || extent[axis].within_epsilon() {
+            nodes.push(BvhNode::Leaf {
+                bounds,
+                first,
+                count: faces.len(),
+            });
+
+            return node_i;
+        }
+
+        faces.sort_by(|a, b| {
+            let a_centroid = Self::face_centroid(vertices, a, axis);
+            let b_centroid = Self::face_centroid(vertices, b, axis);
+            a_centroid.partial_cmp(&b_centroid).unwrap()
+        });
+
+        // Placeholder, until the children indexes are known.
+        //
+        nodes.push(BvhNode::Leaf {
+            bounds,
+            first,
+            count: 0,
+        });
+
+        let middle = faces.len() / 2;
+        let (left_faces, right_faces) = faces.split_at_mut(middle);
+
+        let left = Self::build_bvh(vertices, left_faces, first, nodes);
+        let right = Self::build_bvh(vertices, right_faces, first + middle, nodes);
+
+        nodes[node_i] = BvhNode::Branch {
+            bounds,
+            left,
+            right,
+        };
+
+        node_i
+    }
+
+    // Same algorithm as Triangle#local_intersections_into(); the edges are computed on the fly, in order
+    // to keep the faces compact.
+    //
+    fn face_intersection<'a>(&'a self, face_i: usize, ray: &Ray) -> Option<Intersection<'a>> {
+        let (p1, p2, p3) = self.face_points(&self.faces[face_i]);
+        let e1 = p2 - &p1;
+        let e2 = p3 - &p1;
+
+        let dir_cross_e2 = ray.direction.cross_product(&e2);
+        let determinant = e1.dot_product(&dir_cross_e2);
+
+        if determinant.within_epsilon() {
+            return None;
+        }
+
+        let f = 1.0 / determinant;
+        let p1_to_origin = ray.origin - &p1;
+        let u = f * p1_to_origin.dot_product(&dir_cross_e2);
+
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let origin_cross_e1 = p1_to_origin.cross_product(&e1);
+        let v = f * ray.direction.dot_product(&origin_cross_e1);
+
+        if v < 0.0 || (u + v) > 1.0 {
+            return None;
+        }
+
+        let t = f * e2.dot_product(&origin_cross_e1);
+
+        Some(Intersection {
+            t,
+            uv: Some((u, v)),
+            face: Some(face_i),
+            instance: None,
+            material: None,
+            object: self,
+        })
+    }
+}
+
+impl ShapeLocal for TriangleMesh {
+    // The intersection is required in order to find the face (and to interpolate the normals, for
+    // smooth faces); see Triangle#local_normal().
+    //
+    fn local_normal(&self, _point: Point3, intersection: &Intersection) -> Normal3 {
+        let face = &self.faces[intersection.face.unwrap()];
+
+        if let Some([n1i, n2i, n3i]) = face.normals {
+            let (u, v) = intersection.uv.unwrap();
+            let (n1, n2, n3) = (
+                self.normals[n1i as usize],
+                self.normals[n2i as usize],
+                self.normals[n3i as usize],
+            );
+
+            (n2 * u + &(n3 * v) + &(n1 * (1.0 - u - v))).into()
+        } else {
+            let (p1, p2, p3) = self.face_points(face);
+
+            (p3 - &p1).cross_product(&(p2 - &p1)).normalize().into()
+        }
+    }
+
+    // The tangent frame is constant over the face; it's derived from the edges, and the differences of
+    // the uvs along them.
+    //
+    fn texture_coordinates(&self, intersection: &Intersection) -> Option<TextureCoordinates> {
+        let face = &self.faces[intersection.face.unwrap()];
+        let [uv1i, uv2i, uv3i] = face.uvs?;
+        let uv = self.texture_uv(intersection)?;
+
+        let (p1, p2, p3) = self.face_points(face);
+        let (uv1, uv2, uv3) = (
+            self.uvs[uv1i as usize],
+            self.uvs[uv2i as usize],
+            self.uvs[uv3i as usize],
+        );
+
+        let (e1, e2) = (p2 - &p1, p3 - &p1);
+        let (du1, dv1) = (uv2.0 - uv1.0, uv2.1 - uv1.1);
+        let (du2, dv2) = (uv3.0 - uv1.0, uv3.1 - uv1.1);
+
+        let determinant = du1 * dv2 - du2 * dv1;
+
+        // Degenerate mapping (the face uvs are collinear).
+        //
+        if determinant == 0.0 {
+            return None;
+        }
+
+        Some(TextureCoordinates {
+            uv,
+            tangent: (e1 * dv2 - &(e2 * dv1)) / determinant,
+            bitangent: (e2 * du1 - &(e1 * du2)) / determinant,
+        })
+    }
+
+    // ray: In object space.
+    //
+    fn local_intersections_into<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        if self.bvh.is_empty() {
+            return;
+        }
+
+        // The stack holds at most one pending node per level, so a fixed array is enough.
+        //
+        let mut nodes_stack = [0; MAX_BVH_DEPTH];
+        let mut stack_size = 1;
+
+        while stack_size > 0 {
+            stack_size -= 1;
+
+            match self.bvh[nodes_stack[stack_size]] {
+                BvhNode::Leaf {
+                    ref bounds,
+                    first,
+                    count,
+                } => {
+                    if bounds.intersected_by(ray) {
+                        for face_i in first..(first + count) {
+                            if let Some(intersection) = self.face_intersection(face_i, ray) {
+                                intersections.push(intersection);
+                            }
+                        }
+                    }
+                }
+                BvhNode::Branch {
+                    ref bounds,
+                    left,
+                    right,
+                } => {
+                    if bounds.intersected_by(ray) {
+                        nodes_stack[stack_size] = right;
+                        nodes_stack[stack_size + 1] = left;
+                        stack_size += 2;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl BoundedShape for TriangleMesh {
+    fn local_bounds(&self) -> Bounds {
+        match self.bvh.first() {
+            Some(BvhNode::Leaf { bounds, .. }) | Some(BvhNode::Branch { bounds, .. }) => *bounds,
+            None => Bounds::default(),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "TriangleMesh" {
+        use crate::math::*;
+        use crate::space::{*, shape::private::ShapeLocal};
+
+        before {
+            // A quad (two triangles) on the z=0 plane, with a smooth and a flat face.
+            //
+            let vertices = vec![
+                Point3::new(-1, -1, 0),
+                Point3::new(1, -1, 0),
+                Point3::new(1, 1, 0),
+                Point3::new(-1, 1, 0),
+            ];
+            let normals = vec![
+                Normal3::new(0, 0, -1),
+                Normal3::new(-1, 0, 0),
+                Normal3::new(1, 0, 0),
+            ];
+            let faces = vec![
+                MeshFace::new([0, 1, 2]),
+                MeshFace::smooth([0, 2, 3], [0, 1, 2]),
+            ];
+
+            #[allow(unused_variables)]
+            let mesh = TriangleMesh::new(vertices, normals, vec![], faces);
+        }
+
+        it "Computes the bounds from the vertices" {
+            let bounds = mesh.local_bounds();
+
+            assert_eq!(bounds.min, Point3::new(-1, -1, 0));
+            assert_eq!(bounds.max, Point3::new(1, 1, 0));
+        }
+
+        it "A ray misses the mesh" {
+            let ray = Ray::new((2, 0, -2), (0, 0, 1));
+
+            assert_eq!(mesh.local_intersections(&ray).len(), 0);
+        }
+
+        it "A ray strikes a flat face" {
+            let ray = Ray::new((0.5, -0.5, -2.0), (0, 0, 1));
+
+            let intersections = mesh.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 1);
+            assert_eq!(intersections[0].t, 2.0);
+
+            let face = mesh.faces()[intersections[0].face.unwrap()];
+
+            assert_eq!(face.normals, None);
+            assert_eq!(mesh.local_normal(Point3::new(0.5, -0.5, 0), &intersections[0]), Normal3::new(0, 0, -1));
+        }
+
+        it "A ray strikes a smooth face, and the normal is interpolated" {
+            let ray = Ray::new((-0.5, 0.5, -2.0), (0, 0, 1));
+
+            let intersections = mesh.local_intersections(&ray);
+
+            assert_eq!(intersections.len(), 1);
+
+            let (u, v) = intersections[0].uv.unwrap();
+            let expected_normal = Normal3::from(Vector3::new(-1, 0, 0) * u + &(Vector3::new(1, 0, 0) * v) + &(Vector3::new(0, 0, -1) * (1.0 - u - v)));
+
+            assert_eq!(mesh.local_normal(Point3::new(-0.5, 0.5, 0), &intersections[0]), expected_normal);
+        }
+
+        it "Computes the texture coordinates and the tangent frame, for faces with uvs" {
+            let uvs = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)];
+            let faces = vec![
+                MeshFace { uvs: Some([0, 1, 2]), ..MeshFace::new([0, 1, 2]) },
+                MeshFace::new([0, 2, 3]),
+            ];
+            let mesh = TriangleMesh::new(mesh.vertices().to_vec(), vec![], uvs, faces);
+
+            let ray = Ray::new((0.5, -0.5, -2.0), (0, 0, 1));
+            let intersections = mesh.local_intersections(&ray);
+
+            let expected_texture_coordinates = TextureCoordinates {
+                uv: (0.75, 0.25),
+                tangent: Vector3::new(2, 0, 0),
+                bitangent: Vector3::new(0, 2, 0),
+            };
+
+            assert_eq!(mesh.texture_coordinates(&intersections[0]), Some(expected_texture_coordinates));
+
+            let ray = Ray::new((-0.5, 0.5, -2.0), (0, 0, 1));
+            let intersections = mesh.local_intersections(&ray);
+
+            assert_eq!(mesh.texture_coordinates(&intersections[0]), None);
+        }
+
+        it "Intersections through the BVH match the ones of the equivalent triangles" {
+            // A grid of 10x10 quads, which is large enough to produce a multi-level BVH.
+            //
+            let mut vertices = vec![];
+            let mut faces = vec![];
+            let mut triangles = vec![];
+
+            for y in 0..=10 {
+                for x in 0..=10 {
+                    vertices.push(Point3::new(x, y, (x * y) % 3));
+                }
+            }
+
+            for y in 0..10 {
+                for x in 0..10 {
+                    let (p1i, p2i, p3i, p4i) = (y * 11 + x, y * 11 + x + 1, (y + 1) * 11 + x + 1, (y + 1) * 11 + x);
+
+                    faces.push(MeshFace::new([p1i, p2i, p3i]));
+                    faces.push(MeshFace::new([p1i, p3i, p4i]));
+
+                    triangles.push(Triangle::new(vertices[p1i as usize], vertices[p2i as usize], vertices[p3i as usize]));
+                    triangles.push(Triangle::new(vertices[p1i as usize], vertices[p3i as usize], vertices[p4i as usize]));
+                }
+            }
+
+            let mesh = TriangleMesh::new(vertices, vec![], vec![], faces);
+
+            for (ox, oy) in &[(0.5, 0.25), (3.3, 7.7), (9.9, 0.1), (5.0, 5.5), (11.0, 2.0)] {
+                let ray = Ray::new((*ox, *oy, -5.0), (0.1, 0.05, 1.0));
+
+                let mut actual_ts = mesh
+                    .local_intersections(&ray)
+                    .iter()
+                    .map(|intersection| intersection.t)
+                    .collect::<Vec<_>>();
+                let mut expected_ts = triangles
+                    .iter()
+                    .flat_map(|triangle| triangle.local_intersections(&ray))
+                    .map(|intersection| intersection.t)
+                    .collect::<Vec<_>>();
+
+                actual_ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                expected_ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+                assert_eq!(actual_ts, expected_ts);
+            }
+        }
+
+        it "Intersecting an empty mesh" {
+            let mesh = TriangleMesh::new(vec![], vec![], vec![], vec![]);
+            let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+            assert_eq!(mesh.local_intersections(&ray).len(), 0);
+        }
+    }
+}
@@ -13,12 +13,12 @@ demonstrate! {
                 (1, 0, 0),
             );
 
-            assert_eq!(t.p1, Tuple::point(0, 1, 0));
-            assert_eq!(t.p2, Tuple::point(-1, 0, 0));
-            assert_eq!(t.p3, Tuple::point(1, 0, 0));
-            assert_eq!(t.e1, Tuple::vector(-1, -1, 0));
-            assert_eq!(t.e2, Tuple::vector(1, -1, 0));
-            assert_eq!(t.normal, Tuple::vector(0, 0, -1));
+            assert_eq!(t.p1, Point3::new(0, 1, 0));
+            assert_eq!(t.p2, Point3::new(-1, 0, 0));
+            assert_eq!(t.p3, Point3::new(1, 0, 0));
+            assert_eq!(t.e1, Vector3::new(-1, -1, 0));
+            assert_eq!(t.e2, Vector3::new(1, -1, 0));
+            assert_eq!(t.normal, Normal3::new(0, 0, -1));
         }
 
         it "Intersecting a ray parallel to the triangle" {
@@ -66,12 +66,15 @@ demonstrate! {
             let default_intersection = Intersection {
                 t: 0.0,
                 uv: None,
+                face: None,
+                instance: None,
+                material: None,
                 object: &Plane::default(),
             };
             let t = Triangle::from_ints((0, 1, 0), (-1, 0, 0), (1, 0, 0));
-            let n1 = t.local_normal(Tuple::point(0, 0.5, 0), &default_intersection);
-            let n2 = t.local_normal(Tuple::point(-0.5, 0.75, 0), &default_intersection);
-            let n3 = t.local_normal(Tuple::point(0.5, 0.25, 0), &default_intersection);
+            let n1 = t.local_normal(Point3::new(0, 0.5, 0), &default_intersection);
+            let n2 = t.local_normal(Point3::new(-0.5, 0.75, 0), &default_intersection);
+            let n3 = t.local_normal(Point3::new(0.5, 0.25, 0), &default_intersection);
 
             assert_eq!(n1, t.normal);
             assert_eq!(n2, t.normal);
@@ -80,17 +83,17 @@ demonstrate! {
     } // describe "Triangle"
 
     describe "Smooth triangle" {
-        use crate::lang::ApproximateFloat64Ops;
+        use crate::lang::ApproximateFloatOps;
         use crate::math::*;
         use crate::space::{*, shape::private::ShapeLocal};
 
         before {
-            let p1 = Tuple::point(0, 1, 0);
-            let p2 = Tuple::point(-1, 0, 0);
-            let p3 = Tuple::point(1, 0, 0);
-            let n1 = Tuple::vector(0, 1, 0);
-            let n2 = Tuple::vector(-1, 0, 0);
-            let n3 = Tuple::vector(1, 0, 0);
+            let p1 = Point3::new(0, 1, 0);
+            let p2 = Point3::new(-1, 0, 0);
+            let p3 = Point3::new(1, 0, 0);
+            let n1 = Normal3::new(0, 1, 0);
+            let n2 = Normal3::new(-1, 0, 0);
+            let n3 = Normal3::new(1, 0, 0);
 
             let triangle = Triangle::smooth(p1, p2, p3, n1, n2, n3);
         }
@@ -114,23 +117,23 @@ demonstrate! {
         }
 
         it "A smooth triangle uses u/v to interpolate the normal" {
-            let intersection = Intersection { t: 1.0, uv: Some((0.45, 0.25)), object: &triangle };
+            let intersection = Intersection { t: 1.0, uv: Some((0.45, 0.25)), face: None, instance: None, material: None, object: &triangle };
 
             // Requires `i`
             // And n ← normal_at(tri, point(0, 0, 0), i)
             //
-            let normal = intersection.object.normal(&Tuple::point(0, 0, 0), &intersection);
+            let normal = intersection.object.normal(&Point3::new(0, 0, 0), &intersection);
 
-            assert_eq!(normal, Tuple::vector(-0.5547, 0.83205, 0));
+            assert_eq!(normal, Normal3::new(-0.5547, 0.83205, 0));
         }
 
         it "Preparing the normal on a smooth triangle" {
-            let intersections = [Intersection { t: 1.0, uv: Some((0.45, 0.25)), object: &triangle }];
+            let intersections = [Intersection { t: 1.0, uv: Some((0.45, 0.25)), face: None, instance: None, material: None, object: &triangle }];
             let ray = Ray::new((-0.2, 0.3, -2.0), (0, 0, 1));
 
             let comps = ray.intersection_state(&intersections[0], &intersections);
 
-            assert_eq!(comps.normalv, Tuple::vector(-0.5547, 0.83205, 0));
+            assert_eq!(comps.normalv, Normal3::new(-0.5547, 0.83205, 0));
         }
     } // describe "Smooth triangle"
 }
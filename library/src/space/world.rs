@@ -1,16 +1,22 @@
-use std::{collections::BTreeSet, sync::Arc};
+use std::sync::Arc;
 
-use super::{intersection::Intersection, IntersectionState, PointLight, Ray, Shape, Sphere};
+use super::{
+    intersection::Intersection, Fog, IntersectionState, Medium, PointLight, Ray, Shape, Sphere,
+};
 use crate::{
     lang::math::sqrt,
-    lang::ApproximateFloat64Ops,
-    math::{Matrix, Tuple},
+    lang::{ApproximateFloatOps, Float},
+    math::{Matrix4, Point3},
     properties::{Color, FlatPattern, Material, COLOR_BLACK, COLOR_WHITE},
 };
 
+// Without fog and media, the space between the objects is a vacuum.
+//
 pub struct World {
     pub objects: Vec<Arc<dyn Shape>>,
     pub light_source: PointLight,
+    pub fog: Option<Fog>,
+    pub media: Vec<Medium>,
 }
 
 impl World {
@@ -23,97 +29,129 @@ impl World {
                         diffuse: 0.7,
                         specular: 0.2,
                         ..Material::default()
-                    },
+                    }
+                    .into(),
                     ..Sphere::default()
                 }),
                 Arc::new(Sphere {
-                    transform: Matrix::scaling(0.5, 0.5, 0.5),
+                    transform: Matrix4::scaling(0.5, 0.5, 0.5).into(),
                     ..Sphere::default()
                 }),
             ],
             light_source: PointLight {
-                position: Tuple::point(-10, 10, -10),
+                position: Point3::new(-10, 10, -10),
                 intensity: COLOR_WHITE,
             },
+            fog: None,
+            media: vec![],
         }
     }
 
-    // Returns the hit, and all the (sorted) intersections.
+    // Precomputes the objects world transforms; see Shape#commit().
     //
-    // Minor optimizations could be applied, but they're possibly not meaningful.
-    //
-    // See Shape#intersections() for an interesting optimization.
+    pub fn commit(&self) {
+        for object in self.objects.iter() {
+            object.commit(None);
+        }
+
+        for medium in self.media.iter() {
+            medium.shape.commit(None);
+        }
+    }
+
+    // Fills the buffer with all the (sorted) intersections, and returns the hit. The buffer is cleared
+    // first; it's provided by the caller, so that it can be reused across rays.
     //
     pub fn intersections<'a>(
         &'a self,
-        ray: &'a Ray,
-    ) -> (Option<Intersection>, Vec<Intersection<'a>>) {
-        let mut all_intersections = BTreeSet::new();
-        let mut hit: Option<Intersection> = None;
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Option<Intersection<'a>> {
+        let ray = Self::forward_ray(ray);
+
+        intersections.clear();
 
         for object in self.objects.iter() {
-            let object_intersections = object.intersections(ray);
-
-            // Object intersections are not guaranteed to be ordered, so we need to go through each.
-            //
-            for intersection in object_intersections {
-                if intersection.t >= 0.0 {
-                    // Note that there is a case where we don't need to clone, but it's not worth bothering.
-                    //
-                    all_intersections.insert(intersection.clone());
-
-                    match hit {
-                        None => {
-                            hit.replace(intersection);
-                        }
-                        Some(Intersection { t, .. }) => {
-                            if intersection.t < t {
-                                hit.replace(intersection);
-                            }
-                        }
-                    }
-                }
-            }
+            object.intersections_into(&ray, intersections);
         }
 
-        let all_intersections = all_intersections.into_iter().collect::<Vec<_>>();
+        Intersection::sort(intersections);
+        intersections.dedup();
 
-        (hit, all_intersections)
+        intersections.first().cloned()
     }
 
-    // Optimized version of intersections(), which stops at the first obstructing intersection.
+    // Optimized version of intersections(), which returns only the hit; the interval is narrowed at each
+    // hit, so that the following objects are searched only for closer intersections.
     //
-    pub fn is_ray_obstructed(&self, ray: &Ray, distance: f64) -> bool {
-        for object in self.objects.iter() {
-            let object_intersections = object.intersections(ray);
+    // The buffer is used as scratch space.
+    //
+    pub fn closest_hit<'a>(
+        &'a self,
+        ray: &Ray,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Option<Intersection<'a>> {
+        let mut ray = Self::forward_ray(ray);
+        let mut closest_hit = None;
 
-            for intersection in object_intersections {
-                if intersection.t >= 0.0 && intersection.t < distance {
-                    return true;
-                }
+        for object in self.objects.iter() {
+            if let Some(hit) = object.closest_hit(&ray, intersections) {
+                ray.t_max = hit.t;
+                closest_hit = Some(hit);
             }
         }
 
-        false
+        closest_hit
     }
 
-    pub fn shade_hit(&self, intersection_state: IntersectionState, max_recursions: u8) -> Color {
-        let is_shadowed = self.is_shadowed(&intersection_state.over_point);
+    // Optimized version of intersections(), which stops at the first intersection.
+    //
+    // The buffer is used as scratch space.
+    //
+    pub fn any_hit<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) -> bool {
+        let ray = Self::forward_ray(ray);
 
-        let surface_color = intersection_state.object.lighting(
-            &self.light_source,
-            &intersection_state.point,
-            &intersection_state.eyev,
-            &intersection_state.normalv,
-            is_shadowed,
-        );
+        self.objects
+            .iter()
+            .any(|object| object.any_hit(&ray, intersections))
+    }
 
-        let reflected_color = self.reflected_color(&intersection_state, max_recursions);
-        let refracted_color = self.refracted_color(&intersection_state, max_recursions);
+    // The world queries consider only the intersections in front of the ray origin.
+    //
+    fn forward_ray(ray: &Ray) -> Ray {
+        ray.with_interval(ray.t_min.max(0.0), ray.t_max)
+    }
 
-        let material = intersection_state.object.material();
+    // The shading methods take the intersections buffer (see intersections()), which is reused by all
+    // the rays cast while computing the color.
+    //
+    pub fn shade_hit<'a>(
+        &'a self,
+        intersection_state: IntersectionState,
+        max_recursions: u8,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Color {
+        let is_shadowed = self.is_shadowed(&intersection_state.over_point, intersections);
 
-        if material.reflective > 0.0 && material.transparency > 0.0 {
+        // The light reaching the surface is attenuated by the media it crosses.
+        //
+        let light = PointLight {
+            position: self.light_source.position,
+            intensity: self.light_source.intensity
+                * self.media_transmittance(&intersection_state.over_point, intersections),
+        };
+
+        let surface_color = intersection_state.lighting(&light, is_shadowed)
+            + &self.translucent_color(&intersection_state, intersections);
+
+        let reflected_color =
+            self.reflected_color(&intersection_state, max_recursions, intersections);
+        let refracted_color =
+            self.refracted_color(&intersection_state, max_recursions, intersections);
+
+        let surface = &intersection_state.surface;
+
+        if surface.reflective > 0.0 && surface.transparency > 0.0 {
             let reflectance = intersection_state.schlick();
 
             surface_color
@@ -124,49 +162,195 @@ impl World {
         }
     }
 
-    pub fn color_at(&self, ray: &Ray, max_recursions: u8) -> Color {
-        let (hit, intersections) = self.intersections(ray);
+    // The refraction indexes are required only for transparent and translucent materials, so only in
+    // that case all the intersections are collected. The check uses the material value, which is the
+    // maximum of the mapped one (see MaterialMaps).
+    //
+    // The media and the fog are applied to the segment from the ray origin to the hit (or to infinity,
+    // if nothing is hit).
+    //
+    pub fn color_at<'a>(
+        &'a self,
+        ray: &Ray,
+        max_recursions: u8,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Color {
+        let (color, hit_t) = if let Some(hit) = self.closest_hit(ray, intersections) {
+            let material = hit.material();
+
+            let intersection_state = if material.transparency > 0.0 || material.subsurface.is_some()
+            {
+                self.intersections(ray, intersections);
+                ray.intersection_state(&hit, intersections)
+            } else {
+                ray.intersection_state(&hit, &[])
+            };
 
-        if let Some(hit) = hit {
-            let intersection_state = ray.intersection_state(&hit, &intersections);
-            self.shade_hit(intersection_state, max_recursions)
+            let color = self.shade_hit(intersection_state, max_recursions, intersections);
+
+            (color, hit.t)
         } else {
-            COLOR_BLACK
+            (COLOR_BLACK, Float::INFINITY)
+        };
+
+        let color = self.media_color(ray, color, hit_t, intersections);
+
+        match &self.fog {
+            Some(fog) => fog.apply(color, hit_t * ray.direction.magnitude()),
+            None => color,
         }
     }
 
-    pub fn reflected_color(
-        &self,
+    // Attenuates the color reaching the ray origin from t_max, and adds the light scattered towards the
+    // ray origin by the media (see Medium). The light is sampled at equally spaced points; each sample
+    // is shadowed by the objects, and attenuated by the media crossed to reach it.
+    //
+    // The ray is split into segments crossing a constant set of media, which are composited back to
+    // front, so that the farther segments are attenuated by the nearer ones; this way, overlapping media
+    // are combined, regardless of their order.
+    //
+    pub fn media_color<'a>(
+        &'a self,
+        ray: &Ray,
+        color: Color,
+        t_max: Float,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Color {
+        let mut color = color;
+        let direction_length = ray.direction.magnitude();
+        let ray_direction = ray.direction.normalize();
+        let mut t_max = t_max;
+
+        // The buffer is reused by the light samples, so the intervals are computed again for each
+        // segment (and medium).
+        //
+        while let Some((start, end)) = self.media_segment(ray, t_max, intersections) {
+            let density = self
+                .media
+                .iter()
+                .filter(|medium| medium.overlaps(ray, start, end, intersections))
+                .map(|medium| medium.density)
+                .sum::<Float>();
+
+            let mut scattered_color = COLOR_BLACK;
+
+            for medium in self.media.iter() {
+                if !medium.overlaps(ray, start, end, intersections) {
+                    continue;
+                }
+
+                let step = (end - start) / medium.samples as Float;
+                let step_length = step * direction_length;
+
+                for i in 0..medium.samples {
+                    let sample_distance = (i as Float + 0.5) * step_length;
+                    let point = ray.position(start + (i as Float + 0.5) * step);
+
+                    if self.is_shadowed(&point, intersections) {
+                        continue;
+                    }
+
+                    let light_direction = (self.light_source.position - &point).normalize();
+                    let phase = medium.phase(light_direction.dot_product(&ray_direction));
+                    let light_intensity = self.light_source.intensity
+                        * self.media_transmittance(&point, intersections);
+
+                    let scattering = medium.density * step_length * phase;
+                    let attenuation = (-density * sample_distance).exp();
+
+                    scattered_color = scattered_color
+                        + &(light_intensity * &medium.color * (scattering * attenuation));
+                }
+            }
+
+            let transmittance = (-density * (end - start) * direction_length).exp();
+
+            color = color * transmittance + &scattered_color;
+            t_max = start;
+        }
+
+        color
+    }
+
+    // Farthest segment of the ray before t_max, delimited by the bounds of the media intervals, so that
+    // the same media are crossed along all of it; None if no medium is crossed before t_max.
+    //
+    fn media_segment<'a>(
+        &'a self,
+        ray: &Ray,
+        t_max: Float,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Option<(Float, Float)> {
+        let end = self
+            .media
+            .iter()
+            .filter_map(|medium| {
+                medium
+                    .intervals(ray, 0.0, t_max, intersections)
+                    .next_back()
+                    .map(|(_, end)| end)
+            })
+            .max_by(Float::total_cmp)?;
+
+        let start = self
+            .media
+            .iter()
+            .map(|medium| {
+                medium
+                    .intervals(ray, 0.0, end, intersections)
+                    .flat_map(|(start, end)| [start, end])
+                    .filter(|bound| *bound < end)
+                    .fold(0.0, Float::max)
+            })
+            .fold(0.0, Float::max);
+
+        Some((start, end))
+    }
+
+    // Fraction of the light reaching the point, after crossing the media.
+    //
+    pub fn media_transmittance<'a>(
+        &'a self,
+        point: &Point3,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Float {
+        let ray = Ray::unbounded(*point, self.light_source.position - point);
+
+        self.media
+            .iter()
+            .map(|medium| medium.transmittance(&ray, 0.0, 1.0, intersections))
+            .product()
+    }
+
+    pub fn reflected_color<'a>(
+        &'a self,
         intersection_state: &IntersectionState,
         max_recursions: u8,
+        intersections: &mut Vec<Intersection<'a>>,
     ) -> Color {
-        if max_recursions == 0
-            || intersection_state
-                .object
-                .material()
-                .reflective
-                .approximate()
-                == 0.0
-        {
+        let reflective = intersection_state.surface.reflective;
+
+        if max_recursions == 0 || reflective.approximate() == 0.0 {
             return COLOR_BLACK;
         }
 
-        let reflect_ray = Ray {
-            origin: intersection_state.over_point,
-            direction: intersection_state.reflectv,
-        };
+        let reflect_ray =
+            Ray::unbounded(intersection_state.over_point, intersection_state.reflectv);
 
-        let color = self.color_at(&reflect_ray, max_recursions - 1);
+        let color = self.color_at(&reflect_ray, max_recursions - 1, intersections);
 
-        return color * intersection_state.object.material().reflective;
+        color * reflective
     }
 
-    pub fn refracted_color(
-        &self,
+    pub fn refracted_color<'a>(
+        &'a self,
         intersection_state: &IntersectionState,
         max_recursions: u8,
+        intersections: &mut Vec<Intersection<'a>>,
     ) -> Color {
-        if max_recursions == 0 || intersection_state.object.material().transparency == 0.0 {
+        let transparency = intersection_state.surface.transparency;
+
+        if max_recursions == 0 || transparency == 0.0 {
             return COLOR_BLACK;
         }
 
@@ -174,8 +358,8 @@ impl World {
         //
         let n_ratio = intersection_state.n1 / intersection_state.n2;
         let cos_i = intersection_state
-            .eyev
-            .dot_product(&intersection_state.normalv);
+            .normalv
+            .dot_product(&intersection_state.eyev);
         let sin2_t = n_ratio.powi(2) * (1.0 - cos_i.powi(2));
 
         if sin2_t > 1.0 {
@@ -185,25 +369,80 @@ impl World {
         let cos_t = sqrt(1.0 - sin2_t);
         let direction = intersection_state.normalv * (n_ratio * cos_i - cos_t)
             - &(intersection_state.eyev * n_ratio);
-        let refracted_ray = Ray {
-            origin: intersection_state.under_point,
-            direction,
+        let refracted_ray = Ray::unbounded(intersection_state.under_point, direction);
+
+        self.color_at(&refracted_ray, max_recursions - 1, intersections) * transparency
+    }
+
+    // Light crossing a translucent object, when the light is behind the shaded surface (when it's in
+    // front, the scattering is accounted by the lighting; see Subsurface).
+    //
+    // The light is assumed to enter where the ray from the point towards the light exits the object;
+    // the light can be blocked by the other objects, before entering. Open surfaces (which have no
+    // exit) don't transmit light.
+    //
+    pub fn translucent_color<'a>(
+        &'a self,
+        intersection_state: &IntersectionState,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> Color {
+        if intersection_state.material.subsurface.is_none() {
+            return COLOR_BLACK;
+        }
+
+        let lightv = self.light_source.position - &intersection_state.under_point;
+        let distance = lightv.magnitude();
+        let direction = lightv.normalize();
+
+        if intersection_state.normalv.dot_product(&direction) >= 0.0 {
+            return COLOR_BLACK;
+        }
+
+        let ray =
+            Ray::unbounded(intersection_state.under_point, direction).with_interval(0.0, distance);
+
+        self.intersections(&ray, intersections);
+
+        let is_same_object = |intersection: &&Intersection| {
+            intersection.object.id() == intersection_state.object.id()
+                && intersection.instance.map(|instance| instance.id)
+                    == intersection_state.instance.map(|instance| instance.id)
+        };
+
+        let entry = match intersections.iter().find(is_same_object) {
+            Some(entry) => entry.clone(),
+            None => return COLOR_BLACK,
         };
 
-        self.color_at(&refracted_ray, max_recursions - 1)
-            * intersection_state.object.material().transparency
+        let entry_point = ray.position(entry.t);
+        let entry_normal = entry.normal(&entry_point).normalize();
+        let light_cos = entry_normal.dot_product(&direction);
+
+        if light_cos <= 0.0 {
+            return COLOR_BLACK;
+        }
+
+        let over_entry_point =
+            entry_point + &(entry_normal * ray.hit_offset(&entry_point, entry.t));
+
+        if self.is_shadowed(&over_entry_point, intersections) {
+            return COLOR_BLACK;
+        }
+
+        intersection_state.translucency(&self.light_source, entry.t, light_cos)
     }
 
-    pub fn is_shadowed(&self, point: &Tuple) -> bool {
+    pub fn is_shadowed<'a>(
+        &'a self,
+        point: &Point3,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) -> bool {
         let lightv = self.light_source.position - point;
         let distance = lightv.magnitude();
         let direction = lightv.normalize();
 
-        let ray = Ray {
-            origin: *point,
-            direction,
-        };
+        let ray = Ray::unbounded(*point, direction).with_interval(0.0, distance);
 
-        self.is_ray_obstructed(&ray, distance)
+        self.any_hit(&ray, intersections)
     }
 }
@@ -16,51 +16,55 @@ demonstrate! {
         it "should intersect with a ray" {
             let ray = Ray::new((0, 0, -5), (0, 0, 1));
 
-            let intersections = world
-                .intersections(&ray).1
+            let mut intersections = vec![];
+
+            let hit = world.intersections(&ray, &mut intersections);
+
+            let intersections = intersections
                 .iter()
                 .map(|intersection| intersection.t).collect::<Vec<_>>();
 
             let expected_intersections = vec![4.0, 4.5, 5.5, 6.0];
 
+            assert_eq!(hit.unwrap().t, 4.0);
             assert_eq!(intersections, expected_intersections);
         }
 
         it "should find the refractive indexes at various scenarios" {
             let sphere_a = Sphere {
-                transform: Matrix::scaling(2, 2, 2),
+                transform: Matrix4::scaling(2, 2, 2).into(),
                 material: Material {
                     refractive_index: 1.5,
                     ..Material::default()
-                },
+                }.into(),
                 ..Sphere::default()
             };
             let sphere_b = Sphere {
-                transform: Matrix::translation(0.0, 0.0, -0.25),
+                transform: Matrix4::translation(0.0, 0.0, -0.25).into(),
                 material: Material {
                     refractive_index: 2.0,
                     ..Material::default()
-                },
+                }.into(),
                 ..Sphere::default()
             };
             let sphere_c = Sphere {
-                transform: Matrix::translation(0.0, 0.0, 0.25),
+                transform: Matrix4::translation(0.0, 0.0, 0.25).into(),
                 material: Material {
                     refractive_index: 2.5,
                     ..Material::default()
-                },
+                }.into(),
                 ..Sphere::default()
             };
 
             let ray = Ray::new((0, 0, -4), (0, 0, 1));
 
             let intersections = [
-                Intersection { t: 2.0, uv: None, object: &sphere_a },
-                Intersection { t: 2.75, uv: None, object: &sphere_b },
-                Intersection { t: 3.25, uv: None, object: &sphere_c },
-                Intersection { t: 4.75, uv: None, object: &sphere_b },
-                Intersection { t: 5.25, uv: None, object: &sphere_c },
-                Intersection { t: 6.0, uv: None, object: &sphere_a },
+                Intersection { t: 2.0, uv: None, face: None, instance: None, material: None, object: &sphere_a },
+                Intersection { t: 2.75, uv: None, face: None, instance: None, material: None, object: &sphere_b },
+                Intersection { t: 3.25, uv: None, face: None, instance: None, material: None, object: &sphere_c },
+                Intersection { t: 4.75, uv: None, face: None, instance: None, material: None, object: &sphere_b },
+                Intersection { t: 5.25, uv: None, face: None, instance: None, material: None, object: &sphere_c },
+                Intersection { t: 6.0, uv: None, face: None, instance: None, material: None, object: &sphere_a },
             ];
 
             // [n1, n2]
@@ -86,18 +90,18 @@ demonstrate! {
             it "should be performed in direct light" {
                 let ray = Ray::new((0, 0, -5), (0, 0, 1));
                 let sphere = &world.objects[0];
-                let intersection = Intersection { t: 4.0, uv: None, object: sphere.as_ref() };
+                let intersection = Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: sphere.as_ref() };
                 let intersection_state = ray.intersection_state(&intersection, &[]);
 
                 let expected_shade = Color::new(0.38066, 0.47583, 0.2855);
 
-                assert_eq!(world.shade_hit(intersection_state, 0), expected_shade);
+                assert_eq!(world.shade_hit(intersection_state, 0, &mut vec![]), expected_shade);
             }
 
             it "should be performed in the shadow" {
                 let sphere1 = Sphere::default();
                 let sphere2 = Sphere {
-                    transform: Matrix::translation(0, 0, 10),
+                    transform: Matrix4::translation(0, 0, 10).into(),
                     ..Sphere::default()
                 };
 
@@ -108,19 +112,19 @@ demonstrate! {
                     (1, 1, 1),
                 );
 
-                let world = World { objects, light_source };
+                let world = World { objects, light_source, fog: None, media: vec![] };
 
                 let ray = Ray::new(
                     (0, 0, 5),
                     (0, 0, 1),
                 );
 
-                let intersection = Intersection { t: 4.0, uv: None, object: world.objects[1].as_ref() };
+                let intersection = Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: world.objects[1].as_ref() };
                 let intersection_state = ray.intersection_state(&intersection, &[]);
 
                 let expected_color = Color::new(0.1, 0.1, 0.1);
 
-                assert_eq!(world.shade_hit(intersection_state, 0), expected_color);
+                assert_eq!(world.shade_hit(intersection_state, 0, &mut vec![]), expected_color);
             }
 
             it "should be performed with a reflective material" {
@@ -128,8 +132,8 @@ demonstrate! {
                     material: Material {
                         reflective: 0.5,
                         ..Material::default()
-                    },
-                    transform: Matrix::translation(0, -1, 0),
+                    }.into(),
+                    transform: Matrix4::translation(0, -1, 0).into(),
                     ..Plane::default()
                 };
 
@@ -138,11 +142,11 @@ demonstrate! {
                 let ray = Ray::new((0, 0, -3), (0.0, -sqrt(2) / 2.0, sqrt(2) / 2.0));
 
                 let intersections = [
-                    Intersection { t: sqrt(2), uv: None, object: world.objects[2].as_ref() },
+                    Intersection { t: sqrt(2), uv: None, face: None, instance: None, material: None, object: world.objects[2].as_ref() },
                 ];
                 let intersection_state = ray.intersection_state(&intersections[0], &intersections);
 
-                let actual_color = world.shade_hit(intersection_state, 1);
+                let actual_color = world.shade_hit(intersection_state, 1, &mut vec![]);
 
                 assert_eq!(actual_color, Color::new(0.87677, 0.92436, 0.82918));
             }
@@ -154,8 +158,8 @@ demonstrate! {
                     material: Material {
                         reflective: 1.0,
                         ..Material::default()
-                    },
-                    transform: Matrix::translation(0, -1, 0),
+                    }.into(),
+                    transform: Matrix4::translation(0, -1, 0).into(),
                     ..Plane::default()
                 };
 
@@ -165,8 +169,8 @@ demonstrate! {
                     material: Material {
                         reflective: 1.0,
                         ..Material::default()
-                    },
-                    transform: Matrix::translation(0, 1, 0),
+                    }.into(),
+                    transform: Matrix4::translation(0, 1, 0).into(),
                     ..Plane::default()
                 };
 
@@ -174,24 +178,24 @@ demonstrate! {
 
                 let ray = Ray::new((0, 0, 0), (0, 1, 0));
 
-                world.color_at(&ray, 5);
+                world.color_at(&ray, 5, &mut vec![]);
             }
 
             it "should be performed on a transparent material" {
                 let floor = Plane {
-                    transform: Matrix::translation(0, -1, 0),
+                    transform: Matrix4::translation(0, -1, 0).into(),
                     material: Material {
                             transparency: 0.5,
                             refractive_index: 1.5,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Plane::default()
                 };
 
                 world.objects.push(Arc::new(floor));
 
                 let ball = Sphere {
-                    transform: Matrix::translation(0.0, -3.5, -0.5),
+                    transform: Matrix4::translation(0.0, -3.5, -0.5).into(),
                     material: Material {
                         pattern: Box::new(FlatPattern {
                             color: Color::new(1, 0, 0),
@@ -199,7 +203,7 @@ demonstrate! {
                         }),
                         ambient: 0.5,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Sphere::default()
                 };
 
@@ -208,11 +212,11 @@ demonstrate! {
                 let ray = Ray::new((0, 0, -3), (0.0, -sqrt(2) / 2.0, sqrt(2) / 2.0));
 
                 let intersections = [
-                    Intersection { t: sqrt(2), uv: None, object: world.objects[2].as_ref() },
+                    Intersection { t: sqrt(2), uv: None, face: None, instance: None, material: None, object: world.objects[2].as_ref() },
                 ];
                 let intersection_state = ray.intersection_state(&intersections[0], &intersections);
 
-                let actual_color = world.shade_hit(intersection_state, 5);
+                let actual_color = world.shade_hit(intersection_state, 5, &mut vec![]);
 
                 assert_eq!(actual_color, Color::new(0.93642, 0.68642, 0.68642));
             }
@@ -221,38 +225,38 @@ demonstrate! {
                 let ray = Ray::new((0, 0, -3), (0.0, -sqrt(2) / 2.0, sqrt(2) / 2.0));
 
                 let floor = Plane {
-                    transform: Matrix::translation(0, -1, 0),
+                    transform: Matrix4::translation(0, -1, 0).into(),
                     material: Material {
                         reflective:       0.5,
                         transparency:     0.5,
                         refractive_index: 1.5,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Plane::default()
                 };
 
                 world.objects.push(Arc::new(floor));
 
                 let ball = Sphere {
-                    transform: Matrix::translation(0.0, -3.5, -0.5),
+                    transform: Matrix4::translation(0.0, -3.5, -0.5).into(),
                     material: Material {
                         pattern: Box::new(FlatPattern::new(1, 0, 0)),
                         ambient: 0.5,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Sphere::default()
                 };
 
                 world.objects.push(Arc::new(ball));
 
                 let intersections = [
-                    Intersection { t: sqrt(2), uv: None, object: world.objects[2].as_ref() },
+                    Intersection { t: sqrt(2), uv: None, face: None, instance: None, material: None, object: world.objects[2].as_ref() },
                 ];
                 let intersection_state = ray.intersection_state(&intersections[0], &intersections);
 
                 let expected_color = Color::new(0.93391, 0.69643, 0.69243);
 
-                assert_eq!(world.shade_hit(intersection_state, 5), expected_color);
+                assert_eq!(world.shade_hit(intersection_state, 5, &mut vec![]), expected_color);
             }
         } // context "intersection shading"
 
@@ -261,14 +265,14 @@ demonstrate! {
                 let ray =  Ray::new((0, 0, -5), (0, 1, 0));
                 let expected_color = COLOR_BLACK;
 
-                assert_eq!(world.color_at(&ray, 0), expected_color);
+                assert_eq!(world.color_at(&ray, 0, &mut vec![]), expected_color);
             }
 
             it "when a ray hits" {
                 let ray = Ray::new((0, 0, -5), (0, 0, 1));
                 let expected_color = Color::new(0.38066, 0.47583, 0.2855);
 
-                assert_eq!(world.color_at(&ray, 0), expected_color);
+                assert_eq!(world.color_at(&ray, 0, &mut vec![]), expected_color);
             }
 
             it "with the intersection behind the ray" {
@@ -287,24 +291,24 @@ demonstrate! {
                             specular: 0.2,
                             ambient: 1.0,
                             ..Material::default()
-                        },
+                        }.into(),
                         ..Sphere::default()
                     }),
                     Arc::new(Sphere {
-                        transform: Matrix::scaling(0.5, 0.5, 0.5),
+                        transform: Matrix4::scaling(0.5, 0.5, 0.5).into(),
                         material: Material {
                             ambient: 1.0,
                             ..Material::default()
-                        },
+                        }.into(),
                         ..Sphere::default()
                     }),
                 ];
 
                 // With the flat pattern, the color is the same at any point.
                 //
-                let expected_color = world.objects[1].material().pattern.color_at(&Tuple::point(0, 0, 0));
+                let expected_color = world.objects[1].material().pattern.color_at(&Point3::new(0, 0, 0));
 
-                assert_eq!(world.color_at(&ray, 0), expected_color);
+                assert_eq!(world.color_at(&ray, 0, &mut vec![]), expected_color);
             }
         } // context "color of a ray intersection"
 
@@ -317,18 +321,18 @@ demonstrate! {
                 // world.objects[1].material_mut().ambient = 1.0;
 
                 world.objects[1] = Arc::new(Sphere {
-                    transform: Matrix::scaling(0.5, 0.5, 0.5),
+                    transform: Matrix4::scaling(0.5, 0.5, 0.5).into(),
                     material: Material {
                         ambient: 1.0,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Sphere::default()
                 });
 
-                let intersection = Intersection { t: 1.0, uv: None, object: world.objects[1].as_ref() };
+                let intersection = Intersection { t: 1.0, uv: None, face: None, instance: None, material: None, object: world.objects[1].as_ref() };
                 let intersection_state = ray.intersection_state(&intersection, &[]);
 
-                let actual_color = world.reflected_color(&intersection_state, 0);
+                let actual_color = world.reflected_color(&intersection_state, 0, &mut vec![]);
 
                 assert_eq!(actual_color, COLOR_BLACK);
 
@@ -339,8 +343,8 @@ demonstrate! {
                     material: Material {
                         reflective: 0.5,
                         ..Material::default()
-                    },
-                    transform: Matrix::translation(0, -1, 0),
+                    }.into(),
+                    transform: Matrix4::translation(0, -1, 0).into(),
                     ..Plane::default()
                 };
 
@@ -348,23 +352,49 @@ demonstrate! {
 
                 let ray = Ray::new((0, 0, -3), (0.0, -sqrt(2) / 2.0, sqrt(2) / 2.0));
 
-                let intersection = Intersection { t: sqrt(2), uv: None, object: world.objects.last().unwrap().as_ref() };
+                let intersection = Intersection { t: sqrt(2), uv: None, face: None, instance: None, material: None, object: world.objects.last().unwrap().as_ref() };
                 let intersection_state = ray.intersection_state(&intersection, &[]);
 
-                let actual_color = world.reflected_color(&intersection_state, 1);
+                let actual_color = world.reflected_color(&intersection_state, 1, &mut vec![]);
 
                 assert_eq!(actual_color, Color::new(0.19032, 0.2379, 0.14274));
             }
+
+            it "should be scaled by the reflective map" {
+                let plane = Plane {
+                    material: Material {
+                        reflective: 0.5,
+                        maps: MaterialMaps {
+                            reflective: Some(Box::new(FlatPattern::new(0.5, 0.5, 0.5))),
+                            ..MaterialMaps::default()
+                        },
+                        ..Material::default()
+                    }.into(),
+                    transform: Matrix4::translation(0, -1, 0).into(),
+                    ..Plane::default()
+                };
+
+                world.objects.push(Arc::new(plane));
+
+                let ray = Ray::new((0, 0, -3), (0.0, -sqrt(2) / 2.0, sqrt(2) / 2.0));
+
+                let intersection = Intersection { t: sqrt(2), uv: None, face: None, instance: None, material: None, object: world.objects.last().unwrap().as_ref() };
+                let intersection_state = ray.intersection_state(&intersection, &[]);
+
+                let actual_color = world.reflected_color(&intersection_state, 1, &mut vec![]);
+
+                assert_eq!(actual_color, Color::new(0.09516, 0.11895, 0.07137));
+            }
         } // context "reflected color"
 
         context "refracted color" {
             it "should be computed for an opaque material" {
                 let ray = Ray::new((0, 0, -5), (0, 0, 1));
-                let intersection = Intersection { t: 4.0, uv: None, object: world.objects[0].as_ref() };
+                let intersection = Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: world.objects[0].as_ref() };
                 let intersection_state = ray.intersection_state(&intersection, &[]);
                 let expected_color = COLOR_BLACK;
 
-                assert_eq!(world.refracted_color(&intersection_state, 5), expected_color);
+                assert_eq!(world.refracted_color(&intersection_state, 5, &mut vec![]), expected_color);
             }
 
             it "should be computed for a refractive material, at the maximum recursion depth" {
@@ -383,15 +413,15 @@ demonstrate! {
                         transparency: 1.0,
                         refractive_index: 1.5,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Sphere::default()
                 });
 
-                let intersection = Intersection { t: 4.0, uv: None, object: world.objects[0].as_ref() };
+                let intersection = Intersection { t: 4.0, uv: None, face: None, instance: None, material: None, object: world.objects[0].as_ref() };
                 let intersection_state = ray.intersection_state(&intersection, &[]);
                 let expected_color = COLOR_BLACK;
 
-                assert_eq!(world.refracted_color(&intersection_state, 0), expected_color);
+                assert_eq!(world.refracted_color(&intersection_state, 0, &mut vec![]), expected_color);
             }
 
             it "should return black in case of total internal refraction" {
@@ -410,17 +440,17 @@ demonstrate! {
                         transparency: 1.0,
                         refractive_index: 1.5,
                         ..Material::default()
-                    },
+                    }.into(),
                     ..Sphere::default()
                 });
 
                 // We're taking the intersection from inside the sphere.
                 //
-                let intersection = Intersection { t: 5.0, uv: None, object: world.objects[0].as_ref() };
+                let intersection = Intersection { t: 5.0, uv: None, face: None, instance: None, material: None, object: world.objects[0].as_ref() };
                 let intersection_state = ray.intersection_state(&intersection, &[]);
                 let expected_color = COLOR_BLACK;
 
-                assert_eq!(world.refracted_color(&intersection_state, 0), expected_color);
+                assert_eq!(world.refracted_color(&intersection_state, 0, &mut vec![]), expected_color);
             }
 
             // This fails, and it's not clear why; the alternative refractive indexes algorithm failed
@@ -441,21 +471,21 @@ demonstrate! {
             //                     specular: 0.2,
             //                     ambient: 1.0, // added
             //                     ..Material::default()
-            //                 },
+            //                 }.into(),
             //                 ..Sphere::default()
             //             }),
             //             Arc::new(Sphere {
-            //                 transform: Matrix::scaling(0.5, 0.5, 0.5),
+            //                 transform: Matrix4::scaling(0.5, 0.5, 0.5).into(),
             //                 material: Material {
             //                     transparency: 1.0, // added
             //                     refractive_index: 1.5, // added
             //                     ..Material::default()
-            //                 },
+            //                 }.into(),
             //                 ..Sphere::default()
             //             }),
             //         ],
             //         light_source: PointLight {
-            //             position: Tuple::point(-10, 10, -10),
+            //             position: Point3::new(-10, 10, -10),
             //             intensity: COLOR_WHITE,
             //         },
             //     };
@@ -465,29 +495,227 @@ demonstrate! {
             //     let expected_color = Color::new(0, 0.99888, 0.04725);
             //
             //     let intersections = [
-            //         Intersection { t: -0.9899, uv: None, object: world.objects[0].as_ref() },
-            //         Intersection { t: -0.4899, uv: None, object: world.objects[1].as_ref() },
-            //         Intersection { t: 0.4899, uv: None, object: world.objects[1].as_ref() },
-            //         Intersection { t: 0.9899, uv: None, object: world.objects[0].as_ref() },
+            //         Intersection { t: -0.9899, uv: None, face: None, instance: None, material: None, object: world.objects[0].as_ref() },
+            //         Intersection { t: -0.4899, uv: None, face: None, instance: None, material: None, object: world.objects[1].as_ref() },
+            //         Intersection { t: 0.4899, uv: None, face: None, instance: None, material: None, object: world.objects[1].as_ref() },
+            //         Intersection { t: 0.9899, uv: None, face: None, instance: None, material: None, object: world.objects[0].as_ref() },
             //     ];
             //     let intersection_state = ray.intersection_state(&intersections[2], &intersections);
             //
-            //     assert_eq!(world.refracted_color(&intersection_state, 5), expected_color);
+            //     assert_eq!(world.refracted_color(&intersection_state, 5, &mut vec![]), expected_color);
             // }
         } // context "refracted color"
 
         context "shadowing" {
             it "should find when a point is not in the shadow" {
-                let point = Tuple::point(10, -10, 10);
+                let point = Point3::new(10, -10, 10);
 
-                assert!(world.is_shadowed(&point));
+                assert!(world.is_shadowed(&point, &mut vec![]));
             }
 
             it "should find when a point is in the shadow" {
-                let point = Tuple::point(-20, 20, -20);
+                let point = Point3::new(-20, 20, -20);
+
+                assert!(!world.is_shadowed(&point, &mut vec![]));
+            }
 
-                assert!(!world.is_shadowed(&point));
+            it "should ignore the objects beyond the light" {
+                world.light_source.position = Point3::new(0, 0, -5);
+
+                let point = Point3::new(0, 0, -10);
+
+                assert!(!world.is_shadowed(&point, &mut vec![]));
             }
         } // context "shadowing"
+
+        context "queries" {
+            it "should find the closest hit, in front of the ray origin" {
+                let ray = Ray::new((0, 0, 0), (0, 0, 1));
+
+                let hit = world.closest_hit(&ray, &mut vec![]).unwrap();
+
+                assert_eq!(hit.t, 0.5);
+                assert_eq!(world.intersections(&ray, &mut vec![]).unwrap(), hit);
+            }
+
+            it "should find if there is any hit, within the ray interval" {
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+                assert!(world.any_hit(&ray, &mut vec![]));
+                assert!(!world.any_hit(&ray.with_interval(0.0, 3.0), &mut vec![]));
+                assert!(!world.any_hit(&ray.with_interval(7.0, 10.0), &mut vec![]));
+            }
+        } // context "queries"
+
+        context "translucency" {
+            before {
+                #[allow(unused_variables, unused_mut)]
+                let mut world = World {
+                    objects: vec![Arc::new(Sphere {
+                        material: Material {
+                            refractive_index: 1.5,
+                            subsurface: Some(Subsurface::default()),
+                            ..Material::default()
+                        }
+                        .into(),
+                        ..Sphere::default()
+                    })],
+                    light_source: PointLight::new((0, 0, 10), (1, 1, 1)),
+                    fog: None,
+                    media: vec![],
+                };
+                #[allow(unused_variables)]
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+            }
+
+            it "should transmit the light through a translucent object lit from behind" {
+                let mut intersections = vec![];
+                let hit = world.intersections(&ray, &mut intersections).unwrap();
+                let intersection_state = ray.intersection_state(&hit, &intersections);
+
+                let actual_color = world.translucent_color(&intersection_state, &mut vec![]);
+
+                // Transmittance (thickness 2) * diffuse * (1 - schlick).
+                //
+                assert_eq!(actual_color, Color::new(0.24896, 0.24896, 0.24896));
+
+                // Plus ambient.
+                //
+                assert_eq!(world.color_at(&ray, 0, &mut vec![]), Color::new(0.34896, 0.34896, 0.34896));
+            }
+
+            it "should not transmit the light, when blocked before entering" {
+                world.objects.push(Arc::new(Sphere {
+                    transform: Matrix4::translation(0, 0, 5).into(),
+                    ..Sphere::default()
+                }));
+
+                let mut intersections = vec![];
+                let hit = world.intersections(&ray, &mut intersections).unwrap();
+                let intersection_state = ray.intersection_state(&hit, &intersections);
+
+                assert_eq!(world.translucent_color(&intersection_state, &mut vec![]), COLOR_BLACK);
+            }
+
+            it "should not transmit the light, when the light is in front of the surface" {
+                world.light_source.position = Point3::new(0, 0, -10);
+
+                let mut intersections = vec![];
+                let hit = world.intersections(&ray, &mut intersections).unwrap();
+                let intersection_state = ray.intersection_state(&hit, &intersections);
+
+                assert_eq!(world.translucent_color(&intersection_state, &mut vec![]), COLOR_BLACK);
+            }
+        } // context "translucency"
+
+        context "participating media" {
+            it "should blend the color towards the fog color, with the distance" {
+                world.fog = Some(Fog::new(Color::new(0.5, 0.5, 0.5), 0.1));
+
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+                assert_eq!(world.color_at(&ray, 1, &mut vec![]), Color::new(0.42000, 0.48380, 0.35622));
+
+                let missing_ray = Ray::new((0, 0, -5), (0, 1, 0));
+
+                assert_eq!(world.color_at(&missing_ray, 1, &mut vec![]), Color::new(0.5, 0.5, 0.5));
+            }
+
+            it "should leave the color of the missing rays unchanged, with a zero density fog" {
+                world.fog = Some(Fog::new(Color::new(0.5, 0.5, 0.5), 0.0));
+
+                let missing_ray = Ray::new((0, 0, -5), (0, 1, 0));
+
+                assert_eq!(world.color_at(&missing_ray, 1, &mut vec![]), COLOR_BLACK);
+            }
+
+            it "should attenuate the color behind a medium" {
+                world.media.push(Medium {
+                    color: COLOR_BLACK,
+                    ..Medium::new(Arc::new(Sphere {
+                        transform: Matrix4::translation(0, 0, -3).into(),
+                        ..Sphere::default()
+                    }))
+                });
+
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+
+                assert_eq!(world.color_at(&ray, 1, &mut vec![]), Color::new(0.14004, 0.17505, 0.10503));
+            }
+
+            it "should scatter the light inside a medium, unless shadowed" {
+                world.objects.clear();
+                world.light_source = PointLight::new((0, 0, 10), (1, 1, 1));
+                world.media.push(Medium {
+                    anisotropy: 0.5,
+                    ..Medium::new(Arc::new(Sphere::default()))
+                });
+
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+                let away_ray = Ray::new((0, 0, 5), (0, 0, -1));
+
+                let lit_color = world.color_at(&ray, 1, &mut vec![]);
+                let away_color = world.color_at(&away_ray, 1, &mut vec![]);
+
+                assert!(lit_color.r > 0.0);
+                assert!(away_color.r > 0.0);
+                assert!(lit_color.r > away_color.r);
+
+                world.objects.push(Arc::new(Sphere {
+                    transform: Matrix4::translation(0, 0, 5).into(),
+                    ..Sphere::default()
+                }));
+
+                // Only the ambient of the occluder, attenuated by the medium.
+                //
+                assert_eq!(world.color_at(&ray, 1, &mut vec![]), Color::new(0.03679, 0.03679, 0.03679));
+            }
+
+            it "should attenuate the light crossing a medium" {
+                world.light_source = PointLight::new((0, 0, -10), (1, 1, 1));
+                world.media.push(Medium::new(Arc::new(Sphere::default())));
+
+                assert_float_absolute_eq!(world.media_transmittance(&Point3::new(0, 0, 0), &mut vec![]), 0.60653, 1e-5);
+                assert_eq!(world.media_transmittance(&Point3::new(0, 5, 0), &mut vec![]), 1.0);
+            }
+
+            it "should combine overlapping media, regardless of their order" {
+                world.objects.clear();
+                world.light_source = PointLight::new((0, 0, 10), (1, 1, 1));
+
+                let ray = Ray::new((0, 0, -5), (0, 0, 1));
+                let small_medium = || Medium {
+                    color: Color::new(1.0, 0.5, 0.5),
+                    density: 1.0,
+                    ..Medium::new(Arc::new(Sphere {
+                        transform: Matrix4::scaling(0.5, 0.5, 0.5).into(),
+                        ..Sphere::default()
+                    }))
+                };
+                let large_medium = || Medium {
+                    color: Color::new(0.5, 0.5, 1.0),
+                    anisotropy: 0.5,
+                    ..Medium::new(Arc::new(Sphere::default()))
+                };
+
+                world.media = vec![small_medium(), large_medium()];
+                let small_first_color = world.color_at(&ray, 1, &mut vec![]);
+
+                world.media = vec![large_medium(), small_medium()];
+                let large_first_color = world.color_at(&ray, 1, &mut vec![]);
+
+                assert_eq!(small_first_color, large_first_color);
+
+                // Two coinciding media scatter and attenuate like a single one, with the densities summed.
+                //
+                world.media = vec![Medium::new(Arc::new(Sphere::default())), Medium::new(Arc::new(Sphere::default()))];
+                let coinciding_color = world.color_at(&ray, 1, &mut vec![]);
+
+                world.media = vec![Medium { density: 1.0, ..Medium::new(Arc::new(Sphere::default())) }];
+                let single_color = world.color_at(&ray, 1, &mut vec![]);
+
+                assert_eq!(coinciding_color, single_color);
+            }
+        } // context "participating media"
     }
 }
@@ -0,0 +1,197 @@
+use crate::lang::Float;
+use std::collections::HashMap;
+
+use crate::math::{Normal3, Point3, Vector3};
+
+// Loop subdivision of triangle meshes; indexes are 0-based.
+//
+// Each pass splits every face into 4, and smooths the vertex positions (including the existing ones),
+// so that repeated passes converge to a smooth surface. Boundary edges (edges with a single adjacent
+// face) use the boundary rules, so that open meshes don't shrink at the borders.
+//
+pub struct LoopSubdivision {
+    pub vertices: Vec<Point3>,
+    pub faces: Vec<[usize; 3]>,
+}
+
+// Data associated to an undirected edge; the key is (min vertex, max vertex).
+//
+struct EdgeData {
+    // Vertex opposite to the edge, for each adjacent face.
+    //
+    opposite_vertices: Vec<usize>,
+    // Index of the vertex inserted on the edge.
+    //
+    midpoint: usize,
+}
+
+impl LoopSubdivision {
+    pub fn new(vertices: Vec<Point3>, faces: Vec<[usize; 3]>) -> Self {
+        Self { vertices, faces }
+    }
+
+    pub fn subdivide(&mut self, passes: u8) {
+        for _ in 0..passes {
+            self.subdivide_once();
+        }
+    }
+
+    // Smooth vertex normals, computed as the area-weighted average of the adjacent faces normals.
+    // The face normals follow the Triangle convention (see `Triangle::new()`).
+    //
+    pub fn vertex_normals(&self) -> Vec<Normal3> {
+        let mut normals = vec![Vector3::new(0, 0, 0); self.vertices.len()];
+
+        for [p1i, p2i, p3i] in self.faces.iter() {
+            let p1 = self.vertices[*p1i];
+            let e1 = self.vertices[*p2i] - &p1;
+            let e2 = self.vertices[*p3i] - &p1;
+
+            // The cross product magnitude is twice the area, which is the intended weight.
+            //
+            let face_normal = e2.cross_product(&e1);
+
+            for vertex_i in &[*p1i, *p2i, *p3i] {
+                normals[*vertex_i] = normals[*vertex_i] + &face_normal;
+            }
+        }
+
+        normals
+            .into_iter()
+            .map(|normal| {
+                if normal.magnitude() > 0.0 {
+                    normal.normalize().into()
+                } else {
+                    normal.into()
+                }
+            })
+            .collect()
+    }
+
+    fn edge_key(a: usize, b: usize) -> (usize, usize) {
+        (a.min(b), a.max(b))
+    }
+
+    fn subdivide_once(&mut self) {
+        let old_vertices_count = self.vertices.len();
+
+        // Collect the edges, and assign the midpoint indexes (after the existing vertices).
+
+        let mut edges = HashMap::<(usize, usize), EdgeData>::new();
+
+        for face in self.faces.iter() {
+            for i in 0..3 {
+                let (a, b, opposite) = (face[i], face[(i + 1) % 3], face[(i + 2) % 3]);
+                let next_midpoint = old_vertices_count + edges.len();
+
+                edges
+                    .entry(Self::edge_key(a, b))
+                    .or_insert_with(|| EdgeData {
+                        opposite_vertices: vec![],
+                        midpoint: next_midpoint,
+                    })
+                    .opposite_vertices
+                    .push(opposite);
+            }
+        }
+
+        // Neighbors of each vertex; for boundary vertices, also the boundary neighbors.
+
+        let mut neighbors = vec![vec![]; old_vertices_count];
+        let mut boundary_neighbors = vec![vec![]; old_vertices_count];
+
+        for ((a, b), edge) in edges.iter() {
+            neighbors[*a].push(*b);
+            neighbors[*b].push(*a);
+
+            if edge.opposite_vertices.len() == 1 {
+                boundary_neighbors[*a].push(*b);
+                boundary_neighbors[*b].push(*a);
+            }
+        }
+
+        let mut new_vertices = vec![Point3::origin(); old_vertices_count + edges.len()];
+
+        // Edge (odd) vertices.
+
+        for ((a, b), edge) in edges.iter() {
+            let (a, b) = (self.vertices[*a], self.vertices[*b]);
+
+            new_vertices[edge.midpoint] = if let [c, d] = edge.opposite_vertices[..] {
+                let (c, d) = (self.vertices[c], self.vertices[d]);
+
+                Self::weighted_point(&[
+                    (a, 3.0 / 8.0),
+                    (b, 3.0 / 8.0),
+                    (c, 1.0 / 8.0),
+                    (d, 1.0 / 8.0),
+                ])
+            } else {
+                // Boundary (or non-manifold) edge.
+                //
+                Self::weighted_point(&[(a, 0.5), (b, 0.5)])
+            };
+        }
+
+        // Existing (even) vertices.
+
+        for (vertex_i, vertex) in self.vertices.iter().enumerate() {
+            new_vertices[vertex_i] = if let [b1, b2] = boundary_neighbors[vertex_i][..] {
+                let (b1, b2) = (self.vertices[b1], self.vertices[b2]);
+
+                Self::weighted_point(&[(*vertex, 3.0 / 4.0), (b1, 1.0 / 8.0), (b2, 1.0 / 8.0)])
+            } else if boundary_neighbors[vertex_i].is_empty() && !neighbors[vertex_i].is_empty() {
+                let n = neighbors[vertex_i].len();
+
+                // Warren's weights.
+                //
+                let beta = if n > 3 {
+                    3.0 / (8.0 * n as Float)
+                } else {
+                    3.0 / 16.0
+                };
+
+                let mut weighted_points = vec![(*vertex, 1.0 - n as Float * beta)];
+
+                for neighbor_i in neighbors[vertex_i].iter() {
+                    weighted_points.push((self.vertices[*neighbor_i], beta));
+                }
+
+                Self::weighted_point(&weighted_points)
+            } else {
+                // Isolated, or non-manifold boundary vertex; it's kept as is.
+                //
+                *vertex
+            };
+        }
+
+        let mut new_faces = Vec::with_capacity(self.faces.len() * 4);
+
+        for [a, b, c] in self.faces.iter() {
+            let ab = edges[&Self::edge_key(*a, *b)].midpoint;
+            let bc = edges[&Self::edge_key(*b, *c)].midpoint;
+            let ca = edges[&Self::edge_key(*c, *a)].midpoint;
+
+            new_faces.push([*a, ab, ca]);
+            new_faces.push([ab, *b, bc]);
+            new_faces.push([ca, bc, *c]);
+            new_faces.push([ab, bc, ca]);
+        }
+
+        self.vertices = new_vertices;
+        self.faces = new_faces;
+    }
+
+    // The weights are expected to sum to 1 (affine combination); the points are summed as vectors from
+    // the origin, since points can't be added to each other.
+    //
+    fn weighted_point(weighted_points: &[(Point3, Float)]) -> Point3 {
+        let result = weighted_points
+            .iter()
+            .fold(Vector3::new(0, 0, 0), |result, (point, weight)| {
+                result + &(point.to_vector() * *weight)
+            });
+
+        Point3::origin() + &result
+    }
+}
@@ -0,0 +1,71 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    describe "LoopSubdivision" {
+        use crate::math::*;
+        use crate::utils::LoopSubdivision;
+
+        it "Subdividing a single (boundary) triangle" {
+            let vertices = vec![
+                Point3::new(0, 0, 0),
+                Point3::new(8, 0, 0),
+                Point3::new(0, 8, 0),
+            ];
+            let mut subdivision = LoopSubdivision::new(vertices, vec![[0, 1, 2]]);
+
+            subdivision.subdivide(1);
+
+            assert_eq!(subdivision.vertices.len(), 6);
+            assert_eq!(subdivision.faces.len(), 4);
+
+            // Boundary rules: the corners are pulled towards the neighbors, and the midpoints are exact.
+            //
+            assert_eq!(subdivision.vertices[0], Point3::new(1, 1, 0));
+            assert!(subdivision.vertices.contains(&Point3::new(4, 0, 0)));
+            assert!(subdivision.vertices.contains(&Point3::new(4, 4, 0)));
+            assert!(subdivision.vertices.contains(&Point3::new(0, 4, 0)));
+        }
+
+        it "Subdividing a closed mesh (tetrahedron)" {
+            let vertices = vec![
+                Point3::new(1, 1, 1),
+                Point3::new(1, -1, -1),
+                Point3::new(-1, 1, -1),
+                Point3::new(-1, -1, 1),
+            ];
+            let faces = vec![[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]];
+            let mut subdivision = LoopSubdivision::new(vertices, faces);
+
+            subdivision.subdivide(1);
+
+            assert_eq!(subdivision.vertices.len(), 10);
+            assert_eq!(subdivision.faces.len(), 16);
+
+            // Interior rule, with 3 neighbors: v * 7/16 + Σneighbors * 3/16.
+            //
+            assert_eq!(subdivision.vertices[0], Point3::new(0.25, 0.25, 0.25));
+
+            // Interior edge rule: a/b * 3/8 + c/d * 1/8.
+            //
+            assert!(subdivision.vertices.contains(&Point3::new(0.5, 0, 0)));
+
+            subdivision.subdivide(2);
+
+            assert_eq!(subdivision.faces.len(), 256);
+        }
+
+        it "Computing the vertex normals" {
+            let vertices = vec![
+                Point3::new(0, 0, 0),
+                Point3::new(1, 0, 0),
+                Point3::new(1, 1, 0),
+                Point3::new(0, 1, 0),
+            ];
+            let subdivision = LoopSubdivision::new(vertices, vec![[0, 1, 2], [0, 2, 3]]);
+
+            for normal in subdivision.vertex_normals() {
+                assert_eq!(normal, Normal3::new(0, 0, -1));
+            }
+        }
+    }
+}
@@ -1,9 +1,14 @@
+mod loop_subdivision;
 mod obj_parser;
 mod ppm_encoder;
 
+pub use loop_subdivision::LoopSubdivision;
 pub use obj_parser::ObjParser;
 pub use ppm_encoder::PpmEncoder;
 
+#[cfg(test)]
+mod loop_subdivision_test;
+
 #[cfg(test)]
 mod obj_parser_test;
 
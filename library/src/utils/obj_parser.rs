@@ -7,23 +7,26 @@ use std::{
 
 use regex::Regex;
 
+use super::LoopSubdivision;
 use crate::{
-    math::{Matrix, Tuple},
-    space::{Group, Shape, Triangle},
+    lang::Float,
+    math::{Matrix4, Normal3, Point3},
+    space::{Group, MeshFace, Shape, Triangle, TriangleMesh},
 };
 
 use ParsedElement::*;
 
 lazy_static::lazy_static! {
-    // Faces with texture vertices (`f a/b/c...`) are decoded as faces with normals only, as texture
-    // vertices are not supported.
+    // Faces with texture vertices are in the `f v/vt[/] ...` and `f v/vt/vn ...` forms; the texture
+    // vertex indexes are optional (`f v// ...`, `f v//vn ...`).
     // The "Faces with texture" regex could be merged into the bare "Faces" one, but it gets too messy.
 
     static ref VERTEX_REGEX: Regex = Regex::new(r"^v (-?\d+(?:\.\d+)?) (-?\d+(?:\.\d+)?) (-?\d+(?:\.\d+)?)$").unwrap();
+    static ref TEXTURE_VERTEX_REGEX: Regex = Regex::new(r"^vt (-?\d+(?:\.\d+)?) (-?\d+(?:\.\d+)?)(?: -?\d+(?:\.\d+)?)?$").unwrap();
     static ref VERTEX_NORMAL_REGEX: Regex = Regex::new(r"^vn (-?\d+(?:\.\d+)?) (-?\d+(?:\.\d+)?) (-?\d+(?:\.\d+)?)$").unwrap();
     static ref FACES_REGEX: Regex = Regex::new(r"^f (\d+) (\d+(?: \d+)+)$").unwrap();
-    static ref FACES_WITH_TEXTURE_REGEX: Regex = Regex::new(r"^f (\d+)/\d*/ (\d+)/\d*/ (\d+)/\d*/$").unwrap();
-    static ref FACE_WITH_NORMAL_REGEX: Regex = Regex::new(r"^f (\d+)/\d*/(\d+) (\d+)/\d*/(\d+) (\d+)/\d*/(\d+)$").unwrap();
+    static ref FACES_WITH_TEXTURE_REGEX: Regex = Regex::new(r"^f (\d+)/(\d*)/? (\d+)/(\d*)/? (\d+)/(\d*)/?$").unwrap();
+    static ref FACE_WITH_NORMAL_REGEX: Regex = Regex::new(r"^f (\d+)/(\d*)/(\d+) (\d+)/(\d*)/(\d+) (\d+)/(\d*)/(\d+)$").unwrap();
     static ref GROUP_REGEX: Regex = Regex::new(r"^g (\w+)$").unwrap();
 }
 
@@ -34,11 +37,19 @@ const DEFAULT_GROUP_NAME: &str = "default";
 
 #[derive(Debug)]
 enum ParsedElement {
-    Vertex(Tuple),
-    VertexNormal(Tuple),
+    Vertex(Point3),
+    TextureVertex((Float, Float)),
+    VertexNormal(Normal3),
     Faces(Vec<(usize, usize, usize)>),
-    Face(usize, usize, usize),
-    FaceWithNormal((usize, usize), (usize, usize), (usize, usize)),
+    // The last value is the (optional) texture vertex indexes.
+    //
+    Face(usize, usize, usize, Option<[usize; 3]>),
+    FaceWithNormal(
+        (usize, usize),
+        (usize, usize),
+        (usize, usize),
+        Option<[usize; 3]>,
+    ),
     Group(String),
     Invalid,
 }
@@ -47,8 +58,9 @@ pub struct ObjParser {
     // WATCH OUT!!! DON'T ACCESS VERTICES/NORMALS DIRECTLY, WHILE PARSING!!!
     // The indexes are 1-based, which are extremely easy to mistake.
     //
-    vertices: Vec<Tuple>,
-    normals: Vec<Tuple>,
+    vertices: Vec<Point3>,
+    texture_vertices: Vec<(Float, Float)>,
+    normals: Vec<Normal3>,
     // The values are only Face/FaceWithNormal.
     //
     groups_data: HashMap<String, Vec<ParsedElement>>,
@@ -67,6 +79,7 @@ impl ObjParser {
 
         let mut parser = Self {
             vertices: vec![],
+            texture_vertices: vec![],
             normals: vec![],
             groups_data: groups,
         };
@@ -78,18 +91,15 @@ impl ObjParser {
 
             match parsed_element {
                 Vertex(vertex) => parser.vertices.push(vertex),
+                TextureVertex(texture_vertex) => parser.texture_vertices.push(texture_vertex),
                 VertexNormal(normal) => parser.normals.push(normal),
                 Faces(vertex_indexes) => {
                     for (p1i, p2i, p3i) in vertex_indexes {
                         let group = parser.groups_data.entry(current_group_name.to_string());
-                        group.and_modify(|group| group.push(Face(p1i, p2i, p3i)));
+                        group.and_modify(|group| group.push(Face(p1i, p2i, p3i, None)));
                     }
                 }
-                Face(p1i, p2i, p3i) => {
-                    let group = parser.groups_data.entry(current_group_name.to_string());
-                    group.and_modify(|group| group.push(Face(p1i, p2i, p3i)));
-                }
-                FaceWithNormal(_, _, _) => {
+                Face(..) | FaceWithNormal(..) => {
                     let group = parser.groups_data.entry(current_group_name.to_string());
                     group.and_modify(|group| group.push(parsed_element));
                 }
@@ -113,6 +123,9 @@ impl ObjParser {
 
     // In the book, this doesn't have a specified API; it's referenced as `"group_name" from parser`.
     //
+    // Triangles don't support texture coordinates, so the texture vertices are ignored; use `mesh()`
+    // in order to keep them.
+    //
     pub fn group(&self, group_name: &str) -> Arc<Group> {
         let parsed_elements = self.groups_data.get(group_name).unwrap();
 
@@ -124,7 +137,74 @@ impl ObjParser {
             })
             .collect();
 
-        Group::new(Matrix::identity(4), triangles)
+        Group::new(Matrix4::identity(), triangles)
+    }
+
+    // For testing purposes.
+    //
+    pub fn default_mesh(&self) -> Arc<TriangleMesh> {
+        self.mesh(DEFAULT_GROUP_NAME)
+    }
+
+    // Compact alternative to `group()`: the group faces are converted to a single mesh, whose buffers
+    // include only the vertices/texture vertices/normals referenced by the group.
+    //
+    pub fn mesh(&self, group_name: &str) -> Arc<TriangleMesh> {
+        let parsed_elements = self.groups_data.get(group_name).unwrap();
+
+        // Map the (1-based) parser indexes to the (0-based) mesh ones.
+        //
+        let mut vertices_map = HashMap::new();
+        let mut uvs_map = HashMap::new();
+        let mut normals_map = HashMap::new();
+        let mut vertices = vec![];
+        let mut uvs = vec![];
+        let mut normals = vec![];
+
+        let mut map_vertex = |i: usize| {
+            *vertices_map.entry(i).or_insert_with(|| {
+                vertices.push(self.vertex(i));
+                (vertices.len() - 1) as u32
+            })
+        };
+
+        let mut map_uvs = |[uv1i, uv2i, uv3i]: [usize; 3]| {
+            [uv1i, uv2i, uv3i].map(|i| {
+                *uvs_map.entry(i).or_insert_with(|| {
+                    uvs.push(self.texture_vertex(i));
+                    (uvs.len() - 1) as u32
+                })
+            })
+        };
+
+        let mut map_normal = |i: usize| {
+            *normals_map.entry(i).or_insert_with(|| {
+                normals.push(self.normal(i));
+                (normals.len() - 1) as u32
+            })
+        };
+
+        let faces = parsed_elements
+            .iter()
+            .map(|parsed_element| match parsed_element {
+                Face(p1i, p2i, p3i, uvis) => MeshFace {
+                    uvs: uvis.map(&mut map_uvs),
+                    ..MeshFace::new([map_vertex(*p1i), map_vertex(*p2i), map_vertex(*p3i)])
+                },
+                FaceWithNormal((p1i, n1i), (p2i, n2i), (p3i, n3i), uvis) => MeshFace {
+                    uvs: uvis.map(&mut map_uvs),
+                    ..MeshFace::smooth(
+                        [map_vertex(*p1i), map_vertex(*p2i), map_vertex(*p3i)],
+                        [map_normal(*n1i), map_normal(*n2i), map_normal(*n3i)],
+                    )
+                },
+                _ => {
+                    panic!("{:?}", parsed_element)
+                }
+            })
+            .collect();
+
+        Arc::new(TriangleMesh::new(vertices, normals, uvs, faces))
     }
 
     // Convenience method for exporting the groups as tree, with the group as leaves of a new root group.
@@ -137,27 +217,93 @@ impl ObjParser {
             .map(|group_name| self.group(group_name) as Arc<dyn Shape>)
             .collect::<Vec<_>>();
 
-        Group::new(Matrix::identity(4), groups)
+        Group::new(Matrix4::identity(), groups)
+    }
+
+    // Applies the given number of Loop subdivision passes to the faces of all the groups (so that the
+    // shared edges are subdivided consistently).
+    //
+    // After subdivision, all the faces have (smooth) vertex normals, which replace the parsed ones;
+    // the normal indexes are the same as the vertex ones. The texture vertices are discarded, since the
+    // new vertices don't have any.
+    //
+    pub fn subdivide(&mut self, passes: u8) {
+        // Flatten the faces of all the groups, keeping track of the groups boundaries; the group names
+        // order is fixed, since HashMap iteration order is not guaranteed to be stable.
+        //
+        let group_names = self.groups_data.keys().cloned().collect::<Vec<_>>();
+        let mut faces = vec![];
+        let mut groups_faces_count = vec![];
+
+        for group_name in group_names.iter() {
+            let parsed_elements = &self.groups_data[group_name];
+
+            for parsed_element in parsed_elements.iter() {
+                let face = match parsed_element {
+                    Face(p1i, p2i, p3i, _) | FaceWithNormal((p1i, _), (p2i, _), (p3i, _), _) => {
+                        [p1i - 1, p2i - 1, p3i - 1]
+                    }
+                    _ => panic!("{:?}", parsed_element),
+                };
+
+                faces.push(face);
+            }
+
+            groups_faces_count.push(parsed_elements.len());
+        }
+
+        let mut subdivision = LoopSubdivision::new(self.vertices.clone(), faces);
+        subdivision.subdivide(passes);
+
+        self.normals = subdivision.vertex_normals();
+        self.vertices = subdivision.vertices;
+
+        // Each pass splits a face into 4, and the children faces are stored in the same order as their
+        // parents.
+        //
+        let children_count = 4_usize.pow(passes as u32);
+        let mut faces = subdivision.faces.into_iter();
+
+        for (group_name, group_faces_count) in group_names.iter().zip(groups_faces_count) {
+            let group_faces = faces
+                .by_ref()
+                .take(group_faces_count * children_count)
+                .map(|[p1i, p2i, p3i]| {
+                    FaceWithNormal(
+                        (p1i + 1, p1i + 1),
+                        (p2i + 1, p2i + 1),
+                        (p3i + 1, p3i + 1),
+                        None,
+                    )
+                })
+                .collect();
+
+            self.groups_data.insert(group_name.clone(), group_faces);
+        }
     }
 
-    pub fn vertex(&self, i: usize) -> Tuple {
+    pub fn vertex(&self, i: usize) -> Point3 {
         self.vertices[i - 1]
     }
 
-    pub fn normal(&self, i: usize) -> Tuple {
+    pub fn texture_vertex(&self, i: usize) -> (Float, Float) {
+        self.texture_vertices[i - 1]
+    }
+
+    pub fn normal(&self, i: usize) -> Normal3 {
         self.normals[i - 1]
     }
 
     fn triangle_from_parsed_element(&self, element: &ParsedElement) -> Triangle {
         match element {
-            Face(p1i, p2i, p3i) => {
+            Face(p1i, p2i, p3i, _) => {
                 let p1 = self.vertex(*p1i);
                 let p2 = self.vertex(*p2i);
                 let p3 = self.vertex(*p3i);
 
                 Triangle::new(p1, p2, p3)
             }
-            FaceWithNormal((p1i, n1i), (p2i, n2i), (p3i, n3i)) => {
+            FaceWithNormal((p1i, n1i), (p2i, n2i), (p3i, n3i), _) => {
                 let p1 = self.vertex(*p1i);
                 let p2 = self.vertex(*p2i);
                 let p3 = self.vertex(*p3i);
@@ -175,17 +321,22 @@ impl ObjParser {
 
     fn parse_line(line: String) -> ParsedElement {
         if let Some(captures) = VERTEX_REGEX.captures(&line) {
-            let x: f64 = captures[1].parse().unwrap();
-            let y: f64 = captures[2].parse().unwrap();
-            let z: f64 = captures[3].parse().unwrap();
+            let x: Float = captures[1].parse().unwrap();
+            let y: Float = captures[2].parse().unwrap();
+            let z: Float = captures[3].parse().unwrap();
 
-            ParsedElement::Vertex(Tuple::point(x, y, z))
+            ParsedElement::Vertex(Point3::new(x, y, z))
+        } else if let Some(captures) = TEXTURE_VERTEX_REGEX.captures(&line) {
+            let u: Float = captures[1].parse().unwrap();
+            let v: Float = captures[2].parse().unwrap();
+
+            ParsedElement::TextureVertex((u, v))
         } else if let Some(captures) = VERTEX_NORMAL_REGEX.captures(&line) {
-            let x: f64 = captures[1].parse().unwrap();
-            let y: f64 = captures[2].parse().unwrap();
-            let z: f64 = captures[3].parse().unwrap();
+            let x: Float = captures[1].parse().unwrap();
+            let y: Float = captures[2].parse().unwrap();
+            let z: Float = captures[3].parse().unwrap();
 
-            ParsedElement::VertexNormal(Tuple::vector(x, y, z))
+            ParsedElement::VertexNormal(Normal3::new(x, y, z))
         } else if let Some(captures) = FACES_REGEX.captures(&line) {
             let mut faces = vec![];
 
@@ -206,22 +357,22 @@ impl ObjParser {
             ParsedElement::Faces(faces)
         } else if let Some(captures) = FACES_WITH_TEXTURE_REGEX.captures(&line) {
             let p1i: usize = captures[1].parse().unwrap();
-            let p2i: usize = captures[2].parse().unwrap();
-            let p3i: usize = captures[3].parse().unwrap();
+            let p2i: usize = captures[3].parse().unwrap();
+            let p3i: usize = captures[5].parse().unwrap();
+            let uvis = Self::texture_vertex_indexes([&captures[2], &captures[4], &captures[6]]);
 
-            ParsedElement::Face(p1i, p2i, p3i)
+            ParsedElement::Face(p1i, p2i, p3i, uvis)
         } else if let Some(captures) = FACE_WITH_NORMAL_REGEX.captures(&line) {
             // MWAHAHAHA
 
-            let values = captures
+            let values = [1, 3, 4, 6, 7, 9]
                 .iter()
-                .skip(1)
-                .map(|c| c.unwrap().as_str())
-                .map(|c| c.parse().unwrap())
+                .map(|i| captures[*i].parse().unwrap())
                 .collect::<Vec<_>>();
+            let uvis = Self::texture_vertex_indexes([&captures[2], &captures[5], &captures[8]]);
 
             if let [v1, n1, v2, n2, v3, n3] = values.as_slice() {
-                FaceWithNormal((*v1, *n1), (*v2, *n2), (*v3, *n3))
+                FaceWithNormal((*v1, *n1), (*v2, *n2), (*v3, *n3), uvis)
             } else {
                 unreachable!()
             }
@@ -233,4 +384,14 @@ impl ObjParser {
             Invalid
         }
     }
+
+    // The texture vertex indexes are used only if all the face vertices have one.
+    //
+    fn texture_vertex_indexes(captures: [&str; 3]) -> Option<[usize; 3]> {
+        if captures.iter().any(|capture| capture.is_empty()) {
+            None
+        } else {
+            Some(captures.map(|capture| capture.parse().unwrap()))
+        }
+    }
 }
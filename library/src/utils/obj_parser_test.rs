@@ -7,8 +7,8 @@ demonstrate! {
         use super::ASSETS_PATH;
         use indoc::indoc;
         use crate::utils::ObjParser;
-        use crate::math::Tuple;
-        use crate::space::Triangle;
+        use crate::math::*;
+        use crate::space::{Triangle, TriangleMesh};
         use std::{io::BufReader, fs::File, path::Path};
 
         it "Ignoring unrecognized lines" {
@@ -35,10 +35,10 @@ demonstrate! {
 
             let parser = ObjParser::parse(input.as_bytes()).unwrap();
 
-            assert_eq!(parser.vertex(1), Tuple::point(-1, 1, 0));
-            assert_eq!(parser.vertex(2), Tuple::point(-1, 0.5, 0));
-            assert_eq!(parser.vertex(3), Tuple::point(1, 0, 0));
-            assert_eq!(parser.vertex(4), Tuple::point(1, 1, 0));
+            assert_eq!(parser.vertex(1), Point3::new(-1, 1, 0));
+            assert_eq!(parser.vertex(2), Point3::new(-1, 0.5, 0));
+            assert_eq!(parser.vertex(3), Point3::new(1, 0, 0));
+            assert_eq!(parser.vertex(4), Point3::new(1, 1, 0));
         }
 
         it "Parsing triangle faces" {
@@ -117,6 +117,122 @@ demonstrate! {
             assert_eq!(t2.p3, parser.vertex(4));
         }
 
+        it "Converting a group to a triangle mesh" {
+            let file_path = Path::new(ASSETS_PATH).join("triangles.obj");
+            let file_reader = BufReader::new(File::open(file_path).unwrap());
+
+            let parser = ObjParser::parse(file_reader).unwrap();
+
+            let mesh: &TriangleMesh = &parser.mesh("SecondGroup");
+
+            // Only the referenced vertices are included.
+            //
+            assert_eq!(mesh.vertices(), &[parser.vertex(1), parser.vertex(3), parser.vertex(4)]);
+            assert_eq!(mesh.faces().len(), 1);
+            assert_eq!(mesh.faces()[0].vertices, [0, 1, 2]);
+            assert_eq!(mesh.faces()[0].normals, None);
+        }
+
+        it "Converting faces with normals to a triangle mesh" {
+            let input = indoc! {"
+                v 0 1 0
+                v -1 0 0
+                v 1 0 0
+
+                vn -1 0 0
+                vn 1 0 0
+                vn 0 1 0
+
+                f 1//3 2//1 3//2
+            "};
+
+            let parser = ObjParser::parse(input.as_bytes()).unwrap();
+
+            let mesh = parser.default_mesh();
+            let face = mesh.faces()[0];
+            let [n1i, n2i, n3i] = face.normals.unwrap();
+
+            assert_eq!(mesh.normals()[n1i as usize], parser.normal(3));
+            assert_eq!(mesh.normals()[n2i as usize], parser.normal(1));
+            assert_eq!(mesh.normals()[n3i as usize], parser.normal(2));
+        }
+
+        it "Converting faces with texture vertices to a triangle mesh" {
+            let input = indoc! {"
+                v 0 1 0
+                v -1 0 0
+                v 1 0 0
+
+                vt 0.5 1
+                vt 0 0
+                vt 1 0 0
+
+                vn 0 0 -1
+
+                f 1/1/1 2/2/1 3/3/1
+                f 1/3 2/2 3/1
+                f 1//1 2//1 3//1
+            "};
+
+            let parser = ObjParser::parse(input.as_bytes()).unwrap();
+
+            let mesh = parser.default_mesh();
+            let faces = mesh.faces();
+            let [uv1i, uv2i, uv3i] = faces[0].uvs.unwrap();
+
+            assert_eq!(mesh.uvs()[uv1i as usize], (0.5, 1.0));
+            assert_eq!(mesh.uvs()[uv2i as usize], (0.0, 0.0));
+            assert_eq!(mesh.uvs()[uv3i as usize], (1.0, 0.0));
+            assert!(faces[0].normals.is_some());
+
+            let [uv1i, uv2i, uv3i] = faces[1].uvs.unwrap();
+
+            assert_eq!(mesh.uvs()[uv1i as usize], (1.0, 0.0));
+            assert_eq!(mesh.uvs()[uv2i as usize], (0.0, 0.0));
+            assert_eq!(mesh.uvs()[uv3i as usize], (0.5, 1.0));
+            assert!(faces[1].normals.is_none());
+
+            assert!(faces[2].uvs.is_none());
+            assert_eq!(mesh.uvs().len(), 3);
+        }
+
+        it "Subdividing faces produces smooth triangles" {
+            let input = indoc! {"
+                v 1 1 1
+                v 1 -1 -1
+                v -1 1 -1
+                v -1 -1 1
+
+                f 1 2 3
+                f 1 4 2
+                g Bottom
+                f 1 3 4
+                f 2 4 3
+            "};
+
+            let mut parser = ObjParser::parse(input.as_bytes()).unwrap();
+
+            parser.subdivide(2);
+
+            let default_group = parser.default_group();
+            let bottom_group = parser.group("Bottom");
+
+            assert_eq!(default_group.children.len(), 32);
+            assert_eq!(bottom_group.children.len(), 32);
+
+            for child in default_group.children.iter().chain(bottom_group.children.iter()) {
+                let triangle = child.as_any().downcast_ref::<Triangle>().unwrap();
+
+                let (n1, n2, n3) = triangle.vertex_normals.unwrap();
+
+                // The subdivided surface is smooth, so the vertex normals are close to the face one.
+                //
+                for vertex_normal in &[n1, n2, n3] {
+                    assert!(vertex_normal.dot_product(&Vector3::from(triangle.normal)) > 0.5);
+                }
+            }
+        }
+
         // This can't be tested with the current design, although it's covered by the previous.
         //
         // it "Converting an OBJ file to a group" {
@@ -150,9 +266,9 @@ demonstrate! {
 
             let parser = ObjParser::parse(input.as_bytes()).unwrap();
 
-            assert_eq!(parser.normal(1), Tuple::vector(0, 0, 1));
-            assert_eq!(parser.normal(2), Tuple::vector(0.707, 0, -0.707));
-            assert_eq!(parser.normal(3), Tuple::vector(1, 2, 3));
+            assert_eq!(parser.normal(1), Normal3::new(0, 0, 1));
+            assert_eq!(parser.normal(2), Normal3::new(0.707, 0, -0.707));
+            assert_eq!(parser.normal(3), Normal3::new(1, 2, 3));
         }
 
         it "Faces with normals" {
@@ -4,64 +4,112 @@ use quote::quote;
 
 use proc_macro::TokenStream;
 
-// This should have a parameter handling the containers, which don't have a material; currently, they
-// can't use this, since they can't redefine the macro accessors, and it's not possible to create another
-// macro, since there can't be two trait imlementation blocks.
+// Implements the ShapeAccessors trait; all the shapes have the same fields (`id`, `parent`,
+// `world_transforms`, `transform` and the optional `material`).
 //
-#[proc_macro_derive(ShapeAccessors)]
+// Since there can't be two implementation blocks of the same trait, the containers (marked with the
+// `container` attribute) implement Shape themselves, in order to override the hit/commit methods; for
+// the other shapes, the (empty) Shape implementation is generated.
+//
+// The shapes whose material overrides another one (marked with the `material_override` attribute) don't
+// get a default material assigned by `material_mut()`, since it would silently hide the overridden one.
+//
+#[proc_macro_derive(ShapeAccessors, attributes(container, material_override))]
 pub fn shape_accessors_derive(input: TokenStream) -> TokenStream {
     let ast: syn::DeriveInput = syn::parse(input).unwrap();
 
     let name = &ast.ident;
 
-    let gen = quote! {
-        use crate::properties::Material as ShapeAccessorsMaterial;
-        use crate::math::Matrix as ShapeAccessorsMatrix;
-        use crate::space::Shape as ShapeAccessorsShape;
-        use std::sync::Arc as ShapeAccessorsArc;
-        use std::sync::Weak as ShapeAccessorsWeak;
+    let is_container = ast
+        .attrs
+        .iter()
+        .any(|attribute| attribute.path.is_ident("container"));
+
+    let is_material_override = ast
+        .attrs
+        .iter()
+        .any(|attribute| attribute.path.is_ident("material_override"));
+
+    let own_material = if is_material_override {
+        quote! {
+            self.material
+                .as_mut()
+                .expect("the overridden material can't be modified; assign an override first")
+        }
+    } else {
+        quote! {
+            self.material.get_or_insert_with(Default::default)
+        }
+    };
 
-        #[cfg(test)]
-        use std::any::Any as ShapeAccessorsAny;
+    let shape_impl = if is_container {
+        quote! {}
+    } else {
+        quote! {
+            impl crate::space::Shape for #name {}
+        }
+    };
 
-        impl Shape for #name {
+    let gen = quote! {
+        impl crate::space::ShapeAccessors for #name {
             fn id(&self) -> u32 {
                 self.id
             }
 
             // The parent/children methods encapsulate (as possible) the typical access pattern.
 
-            fn parent(&self) -> Option<ShapeAccessorsArc<dyn ShapeAccessorsShape>> {
-                ShapeAccessorsWeak::upgrade(&self.parent)
+            fn parent(&self) -> Option<std::sync::Arc<dyn crate::space::Shape>> {
+                self.parent.get()
             }
 
-            fn parent_mut(&mut self) -> &mut ShapeAccessorsWeak<dyn ShapeAccessorsShape> {
-                &mut self.parent
+            fn set_parent(&self, parent: std::sync::Weak<dyn crate::space::Shape>) {
+                self.parent.set(parent)
             }
 
-            fn transform(&self) -> &ShapeAccessorsMatrix {
+            fn world_transforms(&self) -> &crate::space::WorldTransformsCache {
+                &self.world_transforms
+            }
+
+            fn world_transforms_mut(&mut self) -> &mut crate::space::WorldTransformsCache {
+                &mut self.world_transforms
+            }
+
+            fn transform(&self) -> &crate::math::Transform {
                 &self.transform
             }
 
-            fn transform_mut(&mut self) -> &mut ShapeAccessorsMatrix {
-                &mut self.transform
+            // The cached world transforms are based on the transform, so they're invalidated.
+            //
+            fn transform_mut(&mut self) -> &mut crate::math::Matrix4 {
+                self.world_transforms.invalidate();
+                self.transform.matrix_mut()
             }
 
-            fn material(&self) -> &ShapeAccessorsMaterial {
-                &self.material
+            fn own_material(&self) -> Option<&crate::properties::MaterialHandle> {
+                self.material.as_ref()
             }
 
-            fn material_mut(&mut self) -> &mut ShapeAccessorsMaterial {
-                &mut self.material
+            // If the shape has no material of its own, a default one is assigned (unless the material
+            // is an override). Materials are shared (see MaterialHandle), so they can be modified only
+            // while not shared; shared ones are updated via their handle.
+            //
+            fn material_mut(&mut self) -> &mut crate::properties::Material {
+                let material = #own_material;
+
+                material
+                    .get_mut()
+                    .expect("a shared material can't be modified through a shape")
             }
 
             // Not actually a Shape "accessor", but it's the exception, and this design is the simplest.
             //
             #[cfg(test)]
-            fn as_any(&self) -> &dyn ShapeAccessorsAny {
+            fn as_any(&self) -> &dyn std::any::Any {
                 self
             }
         }
+
+        #shape_impl
     };
 
     gen.into()
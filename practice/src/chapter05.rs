@@ -2,7 +2,7 @@ use std::{f64::consts::PI, sync::Arc};
 
 use library::{
     interface::Image,
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     properties::Color,
     space::Shape,
     space::{Intersection, Ray, Sphere},
@@ -38,25 +38,22 @@ pub fn practice() {
     //     .translate(10, 0, 0);
 
     let mut sphere = Sphere::default();
-    let transformation = Matrix::translation(10, 0, 0)
-        * &Matrix::rotation(Axis::Z, -PI / 4.0)
-        * &Matrix::scaling(6.25, 12.5, 12.5);
-    sphere.transform = transformation;
+    let transformation = Matrix4::translation(10, 0, 0)
+        * &Matrix4::rotation(Axis::Z, -PI / 4.0)
+        * &Matrix4::scaling(6.25, 12.5, 12.5);
+    sphere.transform = transformation.into();
 
     let sphere = Arc::new(sphere);
 
-    let ray_origin = Tuple::point(0, 0, eye_z);
+    let ray_origin = Point3::new(0, 0, eye_z);
 
     for y in -center_y..center_y {
         println!("Computing y: {}", y);
 
         for x in -center_x..center_x {
-            let ray_direction = Tuple::vector(x as f64, y as f64, display_z - eye_z);
+            let ray_direction = Vector3::new(x as f64, y as f64, display_z - eye_z);
 
-            let ray = Ray {
-                origin: ray_origin,
-                direction: ray_direction,
-            };
+            let ray = Ray::unbounded(ray_origin, ray_direction);
 
             if hit(&ray, &sphere).is_some() {
                 interface.write_pixel(x, y, hit_color);
@@ -6,10 +6,10 @@ use std::{
 
 use library::{
     interface::Image,
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     properties::FlatPattern,
     properties::COLOR_BLACK,
-    space::{Intersection, PointLight, Ray, Shape, Sphere},
+    space::{Intersection, PointLight, Ray, Shape, ShapeAccessors, Sphere},
     Axis,
 };
 use sdl2_interface::Sdl2Interface;
@@ -33,16 +33,17 @@ pub fn practice() {
     let (origin_x, origin_y) = ((WALL_SIZE / 2) as i16, (WALL_SIZE / 2) as i16);
 
     let mut sphere = Sphere::default();
-    sphere.material.pattern = Box::new(FlatPattern::new(1, 0.2, 1));
-    sphere.transform = Matrix::translation(10, 0, 0)
-        * &Matrix::rotation(Axis::Z, -PI / 4.0)
-        * &Matrix::scaling(6.25, 12.5, 12.5);
+    sphere.material_mut().pattern = Box::new(FlatPattern::new(1, 0.2, 1));
+    sphere.transform = (Matrix4::translation(10, 0, 0)
+        * &Matrix4::rotation(Axis::Z, -PI / 4.0)
+        * &Matrix4::scaling(6.25, 12.5, 12.5))
+        .into();
 
     let sphere = Arc::new(sphere);
 
     let light = PointLight::new(light_position, (1, 1, 1));
 
-    let eye_position = Tuple::point(0, 0, eye_z);
+    let eye_position = Point3::new(0, 0, eye_z);
 
     let mut pixels_buffer = vec![vec![COLOR_BLACK; WALL_SIZE as usize]; WALL_SIZE as usize];
     let pixels_buffer_mtx = Mutex::new(&mut pixels_buffer);
@@ -57,19 +58,19 @@ pub fn practice() {
 
             for (buffer_x, interface_x) in (-origin_x..origin_x).enumerate() {
                 let eye_ray_direction =
-                    Tuple::vector(interface_x as f64, interface_y as f64, wall_z - eye_z)
+                    Vector3::new(interface_x as f64, interface_y as f64, wall_z - eye_z)
                         .normalize();
 
-                let eye_ray = Ray {
-                    origin: eye_position,
-                    direction: eye_ray_direction,
-                };
+                let eye_ray = Ray::unbounded(eye_position, eye_ray_direction);
 
                 if let Some(hit) = hit(&eye_ray, &sphere) {
                     let hit_point = eye_ray.position(hit.t);
                     let intersection = Intersection {
                         t: 0.0,
                         uv: None,
+                        face: None,
+                        instance: None,
+                        material: None,
                         object: &Sphere::default(), // phony
                     };
                     let hit_normal = sphere.normal(&hit_point, &intersection);
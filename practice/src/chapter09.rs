@@ -1,7 +1,7 @@
 use std::{f64::consts::PI, sync::Arc};
 
 use library::{
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     properties::{FlatPattern, Material},
     space::Plane,
     space::{Camera, PointLight, Shape, Sphere, World},
@@ -21,56 +21,65 @@ fn prepare_world() -> World {
     let light_source = PointLight::new((-10, 10, -10), (1, 1, 1));
 
     let floor = Plane {
-        material: prepare_material(),
+        material: prepare_material().into(),
         ..Plane::default()
     };
 
     let left_wall = Plane {
-        transform: Matrix::rotation(Axis::X, -PI / 2.0)
+        transform: Matrix4::rotation(Axis::X, -PI / 2.0)
             .rotate(Axis::Y, -PI / 4.0)
-            .translate(0, 0, 5),
-        material: prepare_material(),
+            .translate(0, 0, 5)
+            .into(),
+        material: prepare_material().into(),
         ..Plane::default()
     };
 
     let right_wall = Plane {
-        transform: Matrix::rotation(Axis::X, -PI / 2.0)
+        transform: Matrix4::rotation(Axis::X, -PI / 2.0)
             .rotate(Axis::Y, PI / 4.0)
-            .translate(0, 0, 5),
-        material: prepare_material(),
+            .translate(0, 0, 5)
+            .into(),
+        material: prepare_material().into(),
         ..Plane::default()
     };
 
     let middle = Sphere {
-        transform: Matrix::translation(-0.5, 1.0, 0.5),
+        transform: Matrix4::translation(-0.5, 1.0, 0.5).into(),
         material: Material {
             pattern: Box::new(FlatPattern::new(0.1, 1, 0.5)),
             diffuse: 0.7,
             specular: 0.3,
             ..Material::default()
-        },
+        }
+        .into(),
         ..Sphere::default()
     };
 
     let right = Sphere {
-        transform: Matrix::scaling(0.5, 0.5, 0.5).translate(1.5, 0.5, -0.5),
+        transform: Matrix4::scaling(0.5, 0.5, 0.5)
+            .translate(1.5, 0.5, -0.5)
+            .into(),
         material: Material {
             pattern: Box::new(FlatPattern::new(0.5, 1, 0.1)),
             diffuse: 0.7,
             specular: 0.3,
             ..Material::default()
-        },
+        }
+        .into(),
         ..Sphere::default()
     };
 
     let left = Sphere {
-        transform: Matrix::scaling(0.33, 0.33, 0.33).translate(-1.5, 0.33, -0.75),
+        transform: Matrix4::scaling(0.33, 0.33, 0.33)
+            .translate(-1.5, 0.33, -0.75)
+            .into(),
         material: Material {
             pattern: Box::new(FlatPattern::new(1, 0.8, 0.1)),
             diffuse: 0.7,
             specular: 0.3,
             ..Material::default()
-        },
+        }
+        .into(),
         ..Sphere::default()
     };
 
@@ -86,17 +95,20 @@ fn prepare_world() -> World {
     World {
         objects,
         light_source,
+        fog: None,
+        media: vec![],
     }
 }
 
 fn prepare_camera() -> Camera {
     let mut camera = Camera::new(400, 200, PI / 3.0);
 
-    camera.transform = Matrix::view_transform(
-        &Tuple::point(0, 1.5, -5),
-        &Tuple::point(0, 1, 0),
-        &Tuple::vector(0, 1, 0),
-    );
+    camera.transform = Matrix4::view_transform(
+        &Point3::new(0, 1.5, -5),
+        &Point3::new(0, 1, 0),
+        &Vector3::new(0, 1, 0),
+    )
+    .into();
 
     camera
 }
@@ -1,7 +1,7 @@
 use std::{f64::consts::PI, sync::Arc};
 
 use library::{
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     properties::*,
     space::Plane,
     space::{Camera, PointLight, Shape, Sphere, World},
@@ -38,32 +38,32 @@ fn random_pattern() -> Box<dyn Pattern> {
 
     match pattern_index {
         0 => Box::new(RingPattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             ..RingPattern::default()
         }),
         1 => Box::new(CheckersPattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             previous_pattern: Some(Box::new(StripePattern {
-                transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-                color_a: random_color(),
-                color_b: random_color(),
+                transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+                color_a: random_color().into(),
+                color_b: random_color().into(),
                 ..StripePattern::default()
             })),
         }),
         2 => Box::new(GradientPattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             ..GradientPattern::default()
         }),
         3 => Box::new(StripePattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             ..StripePattern::default()
         }),
         // Ugly!
@@ -76,13 +76,13 @@ fn random_pattern() -> Box<dyn Pattern> {
     }
 }
 
-fn random_rotation() -> Matrix {
+fn random_rotation() -> Matrix4 {
     if ROTATE_SPHERES {
-        Matrix::rotation(Axis::X, rand::thread_rng().gen_range(0.0, 2.0 * PI))
-            * &Matrix::rotation(Axis::Y, rand::thread_rng().gen_range(0.0, 2.0 * PI))
-            * &Matrix::rotation(Axis::Z, rand::thread_rng().gen_range(0.0, 2.0 * PI))
+        Matrix4::rotation(Axis::X, rand::thread_rng().gen_range(0.0, 2.0 * PI))
+            * &Matrix4::rotation(Axis::Y, rand::thread_rng().gen_range(0.0, 2.0 * PI))
+            * &Matrix4::rotation(Axis::Z, rand::thread_rng().gen_range(0.0, 2.0 * PI))
     } else {
-        Matrix::identity(4)
+        Matrix4::identity()
     }
 }
 
@@ -97,34 +97,37 @@ fn add_spheres(objects: &mut Vec<Arc<dyn Shape>>) {
     }
 
     let middle = Sphere {
-        transform: Matrix::translation(
+        transform: (Matrix4::translation(
             MIDDLE_SPHERE_COORDS.0,
             MIDDLE_SPHERE_COORDS.1,
             MIDDLE_SPHERE_COORDS.2,
-        ) * &random_rotation(),
-        material: prepare_material(),
+        ) * &random_rotation())
+            .into(),
+        material: prepare_material().into(),
         ..Sphere::default()
     };
 
     let right = Sphere {
-        transform: Matrix::translation(
+        transform: (Matrix4::translation(
             RIGHT_SPHERE_COORDS.0,
             RIGHT_SPHERE_COORDS.1,
             RIGHT_SPHERE_COORDS.2,
-        ) * &Matrix::scaling(0.5, 0.5, 0.5)
-            * &random_rotation(),
-        material: prepare_material(),
+        ) * &Matrix4::scaling(0.5, 0.5, 0.5)
+            * &random_rotation())
+            .into(),
+        material: prepare_material().into(),
         ..Sphere::default()
     };
 
     let left = Sphere {
-        transform: Matrix::translation(
+        transform: (Matrix4::translation(
             LEFT_SPHERE_COORDS.0,
             LEFT_SPHERE_COORDS.1,
             LEFT_SPHERE_COORDS.2,
-        ) * &Matrix::scaling(0.33, 0.33, 0.33)
-            * &random_rotation(),
-        material: prepare_material(),
+        ) * &Matrix4::scaling(0.33, 0.33, 0.33)
+            * &random_rotation())
+            .into(),
+        material: prepare_material().into(),
         ..Sphere::default()
     };
 
@@ -143,23 +146,25 @@ fn add_walls(objects: &mut Vec<Arc<dyn Shape>>) {
     }
 
     let floor = Plane {
-        material: prepare_material(),
+        material: prepare_material().into(),
         ..Plane::default()
     };
 
     let left = Plane {
-        transform: Matrix::translation(0, 0, 5)
-            * &Matrix::rotation(Axis::Y, -PI / 4.0)
-            * &Matrix::rotation(Axis::X, -PI / 2.0),
-        material: prepare_material(),
+        transform: (Matrix4::translation(0, 0, 5)
+            * &Matrix4::rotation(Axis::Y, -PI / 4.0)
+            * &Matrix4::rotation(Axis::X, -PI / 2.0))
+            .into(),
+        material: prepare_material().into(),
         ..Plane::default()
     };
 
     let right = Plane {
-        transform: Matrix::translation(0, 0, 5)
-            * &Matrix::rotation(Axis::Y, PI / 4.0)
-            * &Matrix::rotation(Axis::X, -PI / 2.0),
-        material: prepare_material(),
+        transform: (Matrix4::translation(0, 0, 5)
+            * &Matrix4::rotation(Axis::Y, PI / 4.0)
+            * &Matrix4::rotation(Axis::X, -PI / 2.0))
+            .into(),
+        material: prepare_material().into(),
         ..Plane::default()
     };
 
@@ -179,17 +184,20 @@ fn prepare_world() -> World {
     World {
         objects,
         light_source,
+        fog: None,
+        media: vec![],
     }
 }
 
 fn prepare_camera() -> Camera {
     let mut camera = Camera::new(SCREEN_WIDTH, SCREEN_WIDTH / 2, PI / 3.0);
 
-    camera.transform = Matrix::view_transform(
-        &Tuple::point(0, 1.5, -5),
-        &Tuple::point(0, 1, 0),
-        &Tuple::vector(0, 1, 0),
-    );
+    camera.transform = Matrix4::view_transform(
+        &Point3::new(0, 1.5, -5),
+        &Point3::new(0, 1, 0),
+        &Vector3::new(0, 1, 0),
+    )
+    .into();
 
     camera
 }
@@ -1,7 +1,8 @@
 use std::{f64::consts::PI, sync::Arc};
 
 use library::{
-    math::{Matrix, Tuple},
+    lang::Float,
+    math::{Matrix4, Point3, Vector3},
     properties::*,
     space::Plane,
     space::{Camera, PointLight, Shape, Sphere, World},
@@ -20,50 +21,50 @@ const SCREEN_WIDTH: u16 = 400; // height is half
 
 const LIGHT_POSITION: (i32, i32, i32) = (-8, 10, -10);
 const PATTERN: Option<u32> = None; // Some(n: ring, checkers+stripe, gradient, stripe, flat), or None for random
-const PATTERN_SCALE: f64 = 0.33;
-const REFRACTIVE_INDEX: f64 = 1.07;
+const PATTERN_SCALE: Float = 0.33;
+const REFRACTIVE_INDEX: Float = 1.07;
 const ROTATE_SPHERES: bool = true;
 
 #[rustfmt::skip]
 fn add_objects(objects: &mut Vec<Arc<dyn Shape>>) {
     let left_sphere = Sphere {
-        transform: random_rotation().scale(0.33, 0.33, 0.33).translate(-1.5, 0.33, -0.75),
-        material: random_material(None),
+        transform: random_rotation().scale(0.33, 0.33, 0.33).translate(-1.5, 0.33, -0.75).into(),
+        material: random_material(None).into(),
         ..Sphere::default()
     };
 
     let middle_sphere = Sphere {
-        transform: random_rotation().translate(-0.5, 1.0, 0.5),
-        material: random_material(Some(MaterialQuality::Refractive)),
+        transform: random_rotation().translate(-0.5, 1.0, 0.5).into(),
+        material: random_material(Some(MaterialQuality::Refractive)).into(),
         ..Sphere::default()
     };
 
     let back_sphere = Sphere {
-        transform: random_rotation().scale(0.20, 0.20, 0.20).translate(0.0, 0.20, 3.0),
-        material: random_material(None),
+        transform: random_rotation().scale(0.20, 0.20, 0.20).translate(0.0, 0.20, 3.0).into(),
+        material: random_material(None).into(),
         ..Sphere::default()
     };
 
     let right_sphere = Sphere {
-        transform: random_rotation().scale(0.5, 0.5, 0.5).translate(1.5, 0.5, -0.5),
-        material: random_material(None),
+        transform: random_rotation().scale(0.5, 0.5, 0.5).translate(1.5, 0.5, -0.5).into(),
+        material: random_material(None).into(),
         ..Sphere::default()
     };
 
     let left_wall = Plane {
-        transform: Matrix::rotation(Axis::X, -PI / 2.0).rotate(Axis::Y, -PI / 4.0).translate(0, 0, 5),
-        material: random_material(None),
+        transform: Matrix4::rotation(Axis::X, -PI / 2.0).rotate(Axis::Y, -PI / 4.0).translate(0, 0, 5).into(),
+        material: random_material(None).into(),
         ..Plane::default()
     };
 
     let floor = Plane {
-        material: random_material(None),
+        material: random_material(None).into(),
         ..Plane::default()
     };
 
     let right_wall = Plane {
-        transform: Matrix::rotation(Axis::X, -PI / 2.0).rotate(Axis::Y, PI / 4.0).translate(0, 0, 5),
-        material: random_material(Some(MaterialQuality::Reflective)),
+        transform: Matrix4::rotation(Axis::X, -PI / 2.0).rotate(Axis::Y, PI / 4.0).translate(0, 0, 5).into(),
+        material: random_material(Some(MaterialQuality::Reflective)).into(),
         ..Plane::default()
     };
 
@@ -98,32 +99,32 @@ fn random_pattern() -> Box<dyn Pattern> {
 
     match pattern_index {
         0 => Box::new(RingPattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             ..RingPattern::default()
         }),
         1 => Box::new(CheckersPattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             previous_pattern: Some(Box::new(StripePattern {
-                transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-                color_a: random_color(),
-                color_b: random_color(),
+                transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+                color_a: random_color().into(),
+                color_b: random_color().into(),
                 ..StripePattern::default()
             })),
         }),
         2 => Box::new(GradientPattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             ..GradientPattern::default()
         }),
         3 => Box::new(StripePattern {
-            transform: Matrix::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE),
-            color_a: random_color(),
-            color_b: random_color(),
+            transform: Matrix4::scaling(PATTERN_SCALE, PATTERN_SCALE, PATTERN_SCALE).into(),
+            color_a: random_color().into(),
+            color_b: random_color().into(),
             ..StripePattern::default()
         }),
         4 => Box::new(FlatPattern {
@@ -134,13 +135,13 @@ fn random_pattern() -> Box<dyn Pattern> {
     }
 }
 
-fn random_rotation() -> Matrix {
+fn random_rotation() -> Matrix4 {
     if ROTATE_SPHERES {
-        Matrix::rotation(Axis::X, rand::thread_rng().gen_range(0.0, 2.0 * PI))
-            * &Matrix::rotation(Axis::Y, rand::thread_rng().gen_range(0.0, 2.0 * PI))
-            * &Matrix::rotation(Axis::Z, rand::thread_rng().gen_range(0.0, 2.0 * PI))
+        Matrix4::rotation(Axis::X, rand::thread_rng().gen_range(0.0, 2.0 * PI))
+            * &Matrix4::rotation(Axis::Y, rand::thread_rng().gen_range(0.0, 2.0 * PI))
+            * &Matrix4::rotation(Axis::Z, rand::thread_rng().gen_range(0.0, 2.0 * PI))
     } else {
-        Matrix::identity(4)
+        Matrix4::identity()
     }
 }
 
@@ -160,7 +161,7 @@ fn random_material(fancy_material: Option<MaterialQuality>) -> Material {
         None => (0.0, 0.0, 0.0),
     };
 
-    let reflective = reflective as u32 as f64;
+    let reflective = reflective as u32 as Float;
 
     Material {
         pattern,
@@ -187,17 +188,20 @@ fn prepare_world() -> World {
     World {
         objects,
         light_source,
+        fog: None,
+        media: vec![],
     }
 }
 
 fn prepare_camera() -> Camera {
     let mut camera = Camera::new(SCREEN_WIDTH, SCREEN_WIDTH / 2, PI / 3.0);
 
-    camera.transform = Matrix::view_transform(
-        &Tuple::point(0, 1.5, -5),
-        &Tuple::point(0, 1, 0),
-        &Tuple::vector(0, 1, 0),
-    );
+    camera.transform = Matrix4::view_transform(
+        &Point3::new(0, 1.5, -5),
+        &Point3::new(0, 1, 0),
+        &Vector3::new(0, 1, 0),
+    )
+    .into();
 
     camera
 }
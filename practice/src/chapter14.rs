@@ -1,7 +1,7 @@
 use std::{f64::consts::PI, sync::Arc};
 
 use library::{
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     space::*,
     Axis,
 };
@@ -13,7 +13,9 @@ const LIGHT_POSITION: (i32, i32, i32) = (-8, 10, -10);
 
 fn hexagon_corner() -> Arc<dyn Shape> {
     Arc::new(Sphere {
-        transform: Matrix::scaling(0.25, 0.25, 0.25).translate(0, 0, -1),
+        transform: Matrix4::scaling(0.25, 0.25, 0.25)
+            .translate(0, 0, -1)
+            .into(),
         ..Sphere::default()
     })
 }
@@ -22,15 +24,16 @@ fn hexagon_edge() -> Arc<dyn Shape> {
     Arc::new(Cylinder {
         minimum: 0.0,
         maximum: 1.0,
-        transform: Matrix::scaling(0.25, 1.0, 0.25)
+        transform: Matrix4::scaling(0.25, 1.0, 0.25)
             .rotate(Axis::Z, -PI / 2.0)
             .rotate(Axis::Y, -PI / 6.0)
-            .translate(0, 0, -1),
+            .translate(0, 0, -1)
+            .into(),
         ..Cylinder::default()
     })
 }
 
-fn hexagon_side(transform: Matrix) -> Arc<dyn Shape> {
+fn hexagon_side(transform: Matrix4) -> Arc<dyn Shape> {
     let children = vec![hexagon_corner(), hexagon_edge()];
 
     Group::new(transform, children)
@@ -39,7 +42,7 @@ fn hexagon_side(transform: Matrix) -> Arc<dyn Shape> {
 fn hexagon() -> Arc<dyn Shape> {
     let sides = (0..6)
         .map(|n| {
-            let transform = Matrix::rotation(Axis::Y, n as f64 * PI / 3.0);
+            let transform = Matrix4::rotation(Axis::Y, n as f64 * PI / 3.0);
             hexagon_side(transform)
         })
         .collect::<Vec<_>>();
@@ -47,7 +50,7 @@ fn hexagon() -> Arc<dyn Shape> {
     // Transformation added to make it look nicer.
     //
     Group::new(
-        Matrix::rotation(Axis::X, -PI / 6.0)
+        Matrix4::rotation(Axis::X, -PI / 6.0)
             .rotate(Axis::Y, PI / 6.0)
             .translate(-0.35, 1.0, 0.0),
         sides,
@@ -74,17 +77,20 @@ fn prepare_world() -> World {
     World {
         objects,
         light_source,
+        fog: None,
+        media: vec![],
     }
 }
 
 fn prepare_camera() -> Camera {
     let mut camera = Camera::new(SCREEN_WIDTH, SCREEN_WIDTH / 2, PI / 3.0);
 
-    camera.transform = Matrix::view_transform(
-        &Tuple::point(0, 1.5, -5),
-        &Tuple::point(0, 1, 0),
-        &Tuple::vector(0, 1, 0),
-    );
+    camera.transform = Matrix4::view_transform(
+        &Point3::new(0, 1.5, -5),
+        &Point3::new(0, 1, 0),
+        &Vector3::new(0, 1, 0),
+    )
+    .into();
 
     camera
 }
@@ -1,7 +1,7 @@
 use std::{f64::consts::PI, fs::File, io::BufReader, path::Path, sync::Arc};
 
 use library::{
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     space::*,
     utils::ObjParser,
 };
@@ -38,17 +38,20 @@ fn prepare_world() -> World {
     World {
         objects,
         light_source,
+        fog: None,
+        media: vec![],
     }
 }
 
 fn prepare_camera() -> Camera {
     let mut camera = Camera::new(SCREEN_WIDTH, SCREEN_WIDTH / 2, PI / 3.0);
 
-    camera.transform = Matrix::view_transform(
-        &Tuple::point(50, -50, -20),
-        &Tuple::point(-70, 30, -10),
-        &Tuple::vector(0, 1, 0),
-    );
+    camera.transform = Matrix4::view_transform(
+        &Point3::new(50, -50, -20),
+        &Point3::new(-70, 30, -10),
+        &Vector3::new(0, 1, 0),
+    )
+    .into();
 
     camera
 }
@@ -1,7 +1,7 @@
 use std::{f64::consts::PI, sync::Arc};
 
 use library::{
-    math::{Matrix, Tuple},
+    math::{Matrix4, Point3, Vector3},
     properties::{Color, FlatPattern, Material, COLOR_BLUE, COLOR_RED},
     space::*,
     Axis,
@@ -22,16 +22,18 @@ fn random_color() -> Color {
 
 fn add_walls(objects: &mut Vec<Arc<dyn Shape>>) {
     let left_wall = Plane {
-        transform: Matrix::rotation(Axis::X, -PI / 2.0)
+        transform: Matrix4::rotation(Axis::X, -PI / 2.0)
             .rotate(Axis::Y, -PI / 4.0)
-            .translate(0, 0, 5),
+            .translate(0, 0, 5)
+            .into(),
         material: Material {
             pattern: Box::new(FlatPattern {
                 color: random_color(),
                 ..FlatPattern::default()
             }),
             ..Material::default()
-        },
+        }
+        .into(),
         ..Plane::default()
     };
 
@@ -42,21 +44,24 @@ fn add_walls(objects: &mut Vec<Arc<dyn Shape>>) {
                 ..FlatPattern::default()
             }),
             ..Material::default()
-        },
+        }
+        .into(),
         ..Plane::default()
     };
 
     let right_wall = Plane {
-        transform: Matrix::rotation(Axis::X, -PI / 2.0)
+        transform: Matrix4::rotation(Axis::X, -PI / 2.0)
             .rotate(Axis::Y, PI / 4.0)
-            .translate(0, 0, 5),
+            .translate(0, 0, 5)
+            .into(),
         material: Material {
             pattern: Box::new(FlatPattern {
                 color: random_color(),
                 ..FlatPattern::default()
             }),
             ..Material::default()
-        },
+        }
+        .into(),
         ..Plane::default()
     };
 
@@ -74,11 +79,14 @@ fn add_csg(objects: &mut Vec<Arc<dyn Shape>>) {
             }),
             transparency: 0.9,
             ..Material::default()
-        },
+        }
+        .into(),
         ..Sphere::default()
     });
     let cube: Arc<dyn Shape> = Arc::new(Cube {
-        transform: Matrix::scaling(0.5, 0.5, 0.5).translate(-0.2, 0.0, -0.5),
+        transform: Matrix4::scaling(0.5, 0.5, 0.5)
+            .translate(-0.2, 0.0, -0.5)
+            .into(),
         material: Material {
             pattern: Box::new(FlatPattern {
                 color: COLOR_BLUE,
@@ -86,7 +94,8 @@ fn add_csg(objects: &mut Vec<Arc<dyn Shape>>) {
             }),
             transparency: 0.9,
             ..Material::default()
-        },
+        }
+        .into(),
         ..Cube::default()
     });
 
@@ -94,7 +103,7 @@ fn add_csg(objects: &mut Vec<Arc<dyn Shape>>) {
         csg::Operation::Difference,
         sphere,
         cube,
-        Matrix::translation(0.0, 1.0, 0.0),
+        Matrix4::translation(0.0, 1.0, 0.0),
     );
 
     objects.push(csg);
@@ -117,17 +126,20 @@ fn prepare_world() -> World {
     World {
         objects,
         light_source,
+        fog: None,
+        media: vec![],
     }
 }
 
 fn prepare_camera() -> Camera {
     let mut camera = Camera::new(SCREEN_WIDTH, SCREEN_WIDTH / 2, PI / 3.0);
 
-    camera.transform = Matrix::view_transform(
-        &Tuple::point(0, 1.5, -5),
-        &Tuple::point(0, 1, 0),
-        &Tuple::vector(0, 1, 0),
-    );
+    camera.transform = Matrix4::view_transform(
+        &Point3::new(0, 1.5, -5),
+        &Point3::new(0, 1, 0),
+        &Vector3::new(0, 1, 0),
+    )
+    .into();
 
     camera
 }